@@ -0,0 +1,56 @@
+//! Benchmarks comparing cached and uncached one-shot inserts.
+//! 
+//! Each iteration opens a fresh in-memory database and inserts a single row, which is
+//! the worst case for `prepare_cached` - the cache entry is created and then thrown away.
+
+use criterion::*;
+use exemplar::*;
+
+use rusqlite::Connection;
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("people")]
+struct Person {
+    name: String,
+    age: u16,
+    alive: bool,
+}
+
+fn open() -> Connection {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE people (name, age, alive);")
+        .unwrap();
+
+    conn
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let alice = Person {
+        name: "Alice".to_owned(),
+        age: 21,
+        alive: true,
+    };
+
+    c.bench_function("one-shot insert (cached)", |b| b.iter_batched(
+        open,
+        |conn| {
+            alice.insert(&conn).unwrap();
+            conn
+        },
+        BatchSize::SmallInput
+    ));
+
+    c.bench_function("one-shot insert (uncached)", |b| b.iter_batched(
+        open,
+        |conn| {
+            alice.insert_uncached(&conn).unwrap();
+            conn
+        },
+        BatchSize::SmallInput
+    ));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
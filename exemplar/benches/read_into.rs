@@ -0,0 +1,61 @@
+//! Benchmarks comparing `from_row` and the allocation-reusing `read_into`.
+
+use criterion::*;
+use exemplar::*;
+
+use rusqlite::Connection;
+
+#[derive(Debug, Default, PartialEq, Eq, Model)]
+#[table("users")]
+struct User {
+    username: String,
+    email: String,
+    bio: String,
+    #[column("pwd")]
+    password: Vec<u8>,
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE users (username, email, bio, pwd);")
+        .unwrap();
+
+    for i in 0..1000 {
+        let user = User {
+            username: format!("user{i}"),
+            email: format!("user{i}@example.com"),
+            bio: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.".repeat(4),
+            password: vec![i as u8; 64],
+        };
+
+        user.insert(&conn).unwrap();
+    }
+
+    let mut stmt = conn.prepare("SELECT * FROM users")
+        .unwrap();
+
+    c.bench_function("retrieve 1000 (from_row)", |b| b.iter(|| {
+        stmt
+            .query_and_then([], User::from_row)
+            .unwrap()
+            .map(Result::unwrap)
+            .for_each(|u| {
+                black_box(u);
+            })
+    }));
+
+    c.bench_function("retrieve 1000 (read_into)", |b| b.iter(|| {
+        let mut user = User::default();
+        let mut rows = stmt.query([]).unwrap();
+
+        while let Some(row) = rows.next().unwrap() {
+            user.read_into(row).unwrap();
+            black_box(&user);
+        }
+    }));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
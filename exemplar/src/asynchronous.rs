@@ -0,0 +1,206 @@
+//! Executor-agnostic async support (requires the `blocking` feature.)
+//! 
+//! `rusqlite` is a synchronous library, so "async" here means offloading the blocking work onto some other thread
+//! and awaiting the outcome. This module splits that into two pieces:
+//! 
+//! - [`Offload`], which abstracts over *how* blocking work is moved off the async executor. The default implementation,
+//!   [`Unblock`], uses the [`blocking`](https://docs.rs/blocking) crate's thread pool and works with any executor
+//!   (`smol`, `async-std`, `tokio`, or a bare `block_on`.) If your runtime has its own facility (like `tokio::task::spawn_blocking`),
+//!   you can implement [`Offload`] for it.
+//! - [`AsyncConnection`], which owns a [`Connection`] and runs closures against it via an [`Offload`] implementation.
+//! 
+//! [`AsyncModel`] is then implemented for every [`Model`], providing `async` counterparts to common methods.
+//! For trait objects, [`DynModelExt`] provides the same for `Box<dyn Model + Send>`.
+//! 
+//! # Example
+//! ```rust
+//! # use exemplar::Model;
+//! # use exemplar::asynchronous::{AsyncConnection, AsyncModel};
+//! # use rusqlite::Connection;
+//! #[derive(Debug, PartialEq, Model)]
+//! #[table("people")]
+//! struct Person {
+//!     name: String,
+//!     age: u16,
+//! }
+//! 
+//! # fn main() -> rusqlite::Result<()> {
+//! # futures_lite::future::block_on(async {
+//! let conn = AsyncConnection::new(
+//!     Connection::open_in_memory()?
+//! );
+//! 
+//! conn.call(|conn| conn.execute_batch("CREATE TABLE people (name, age);")).await?;
+//! 
+//! Person { name: "Alice".to_owned(), age: 21 }
+//!     .insert_async(&conn)
+//!     .await?;
+//! 
+//! assert_eq!(Person::select_all_async(&conn).await?.len(), 1);
+//! # Ok(())
+//! # })
+//! # }
+//! ```
+//! 
+//! # Trait Objects
+//! [`Model`]'s object-safe methods (such as [`insert`](Model::insert), [`to_params`](Model::to_params) and [`metadata_dyn`](Model::metadata_dyn))
+//! are usable through a `dyn Model`, but (as `rusqlite` is synchronous) can't be `await`ed directly. If you hold boxed models in a plugin system,
+//! [`DynModelExt::insert_async`] offloads the insertion through an [`AsyncConnection`] - which slots neatly into `async-trait`-style interfaces:
+//! 
+//! ```ignore
+//! #[async_trait]
+//! trait Sink {
+//!     async fn write(&self, model: Box<dyn Model + Send>) -> rusqlite::Result<()>;
+//! }
+//! 
+//! struct Database(AsyncConnection);
+//! 
+//! #[async_trait]
+//! impl Sink for Database {
+//!     async fn write(&self, model: Box<dyn Model + Send>) -> rusqlite::Result<()> {
+//!         model.insert_async(&self.0).await
+//!     }
+//! }
+//! ```
+
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use rusqlite::Connection;
+use rusqlite::Result;
+
+use crate::Model;
+
+/// An interface for moving blocking work off of an async executor.
+/// 
+/// Implement this to plug in your runtime's own blocking facility; otherwise, use [`Unblock`].
+pub trait Offload {
+    /// Run `f` somewhere it is allowed to block, and resolve to its output.
+    fn offload<F, T>(&self, f: F) -> impl Future<Output = T> + Send
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static;
+}
+
+/// An [`Offload`] implementation backed by the [`blocking`](https://docs.rs/blocking) crate's thread pool.
+/// 
+/// Works with any executor.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Unblock;
+
+impl Offload for Unblock {
+    fn offload<F, T>(&self, f: F) -> impl Future<Output = T> + Send
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static
+    {
+        blocking::unblock(f)
+    }
+}
+
+/// A shared [`Connection`] that runs work through an [`Offload`] implementation.
+/// 
+/// Cloning an `AsyncConnection` is cheap and yields a handle to the same underlying [`Connection`].
+/// Work is serialized through a [`Mutex`], as SQLite connections can only be used by one thread at a time.
+#[derive(Debug)]
+pub struct AsyncConnection<O = Unblock> {
+    conn: Arc<Mutex<Connection>>,
+    offload: O,
+}
+
+impl AsyncConnection {
+    /// Wrap the provided [`Connection`], offloading work with [`Unblock`].
+    pub fn new(conn: Connection) -> Self {
+        Self::with_offload(conn, Unblock)
+    }
+}
+
+impl<O> AsyncConnection<O>
+where
+    O: Offload
+{
+    /// Wrap the provided [`Connection`], offloading work with a custom [`Offload`] implementation.
+    pub fn with_offload(conn: Connection, offload: O) -> Self {
+        Self {
+            conn: Arc::new(Mutex::new(conn)),
+            offload,
+        }
+    }
+
+    /// Run the provided closure against the underlying [`Connection`] and await its outcome.
+    /// 
+    /// If a previous closure panicked while holding the connection, the connection is still handed out
+    /// rather than propagating the panic.
+    pub async fn call<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut Connection) -> T + Send + 'static,
+        T: Send + 'static
+    {
+        let conn = Arc::clone(&self.conn);
+
+        self.offload.offload(move || {
+            let mut conn = conn
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            f(&mut conn)
+        }).await
+    }
+}
+
+impl<O> Clone for AsyncConnection<O>
+where
+    O: Clone
+{
+    fn clone(&self) -> Self {
+        Self {
+            conn: Arc::clone(&self.conn),
+            offload: self.offload.clone(),
+        }
+    }
+}
+
+/// `async` counterparts to common [`Model`] methods, implemented for every [`Model`].
+/// 
+/// As the work runs on another thread, methods take models by value (or return owned models.)
+pub trait AsyncModel: Model + Send + Sized + 'static {
+    /// Asynchronous version of [`Model::insert`].
+    fn insert_async<O>(self, conn: &AsyncConnection<O>) -> impl Future<Output = Result<()>> + Send
+    where
+        O: Offload + Sync
+    {
+        conn.call(move |conn| self.insert(conn))
+    }
+
+    /// Asynchronous version of [`Model::select_all`].
+    fn select_all_async<O>(conn: &AsyncConnection<O>) -> impl Future<Output = Result<Vec<Self>>> + Send
+    where
+        O: Offload + Sync
+    {
+        conn.call(|conn| Self::select_all(conn))
+    }
+}
+
+impl<M> AsyncModel for M
+where
+    M: Model + Send + Sized + 'static
+{}
+
+/// `async` counterparts to the object-safe [`Model`] methods, for boxed trait objects.
+/// 
+/// See the [module documentation](self#trait-objects) for an example of use with `async-trait`.
+pub trait DynModelExt {
+    /// Asynchronous version of [`Model::insert`], for trait objects.
+    fn insert_async<O>(self, conn: &AsyncConnection<O>) -> impl Future<Output = Result<()>> + Send
+    where
+        O: Offload + Sync;
+}
+
+impl DynModelExt for Box<dyn Model + Send> {
+    fn insert_async<O>(self, conn: &AsyncConnection<O>) -> impl Future<Output = Result<()>> + Send
+    where
+        O: Offload + Sync
+    {
+        conn.call(move |conn| self.insert(conn))
+    }
+}
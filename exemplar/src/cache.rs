@@ -0,0 +1,156 @@
+//! A read-through cache for models, keyed by primary key.
+//! 
+//! [`ModelCache`] holds a bounded number of models in memory, evicting the least recently used one when full.
+//! Lookups that miss fall back to querying the model's table by its [`#[primary_key]`](crate::macros::Model#primary_key) column:
+//! 
+//! ```rust
+//! # use exemplar::Model;
+//! # use exemplar::cache::ModelCache;
+//! # use rusqlite::Connection;
+//! #[derive(Debug, Model)]
+//! #[table("users")]
+//! pub struct User {
+//!     #[primary_key]
+//!     pub id: i64,
+//!     pub name: String,
+//! }
+//! 
+//! # fn main() -> rusqlite::Result<()> {
+//! # let conn = Connection::open_in_memory()?;
+//! # conn.execute_batch("
+//! #     CREATE TABLE users (id INTEGER PRIMARY KEY, name);
+//! #     INSERT INTO users VALUES (1, 'Alice');
+//! # ")?;
+//! let mut cache = ModelCache::<User, i64>::new(128);
+//! 
+//! // The first lookup queries the database...
+//! assert_eq!(cache.get(&conn, 1)?.unwrap().name, "Alice");
+//! // ...while later ones are served from memory.
+//! assert_eq!(cache.get(&conn, 1)?.unwrap().name, "Alice");
+//! 
+//! assert!(cache.get(&conn, 2)?.is_none());
+//! # Ok(())
+//! # }
+//! ```
+//! 
+//! The cache has no way of knowing when the underlying rows change - use [`ModelCache::invalidate`] (or [`ModelCache::clear`])
+//! after writing to the table.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use rusqlite::Connection;
+use rusqlite::Result;
+use rusqlite::ToSql;
+
+use crate::Model;
+use crate::PrimaryKey;
+use crate::unsupported_primary_key;
+
+/// A least-recently-used cache of models of type `M`, keyed by their primary key (of type `K`).
+/// 
+/// See the [module documentation](self) for an example.
+pub struct ModelCache<M, K> {
+    capacity: usize,
+    entries: HashMap<K, M>,
+    /// Keys in order of use, from least to most recent.
+    recency: VecDeque<K>,
+}
+
+impl<M, K> ModelCache<M, K>
+where
+    M: Model + PrimaryKey,
+    K: ToSql + Eq + Hash + Clone
+{
+    /// Create an empty cache that holds at most `capacity` models.
+    /// 
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "A ModelCache must have a non-zero capacity.");
+
+        Self {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            recency: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Get the model with the provided primary key, loading it from the database behind `conn` if it isn't cached.
+    /// 
+    /// Returns `None` if no such row exists; misses of this kind aren't cached.
+    /// 
+    /// # Errors
+    /// Fails if the query does, or if the model has a composite primary key. (Models without one can't be cached at all.)
+    /// 
+    /// # Performance
+    /// Hits are a map lookup, plus a linear scan to update recency. Misses use [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the query SQL statement.
+    pub fn get(&mut self, conn: &Connection, pk: K) -> Result<Option<&M>> {
+        if self.entries.contains_key(&pk) {
+            self.touch(&pk);
+            return Ok(self.entries.get(&pk))
+        }
+
+        let meta = M::metadata();
+
+        let [column] = meta.primary_key else {
+            return Err(unsupported_primary_key(&meta, "must have exactly one #[primary_key] column to be cached"))
+        };
+
+        let Some(model) = M::select_by(conn, column, &pk)?.into_iter().next() else {
+            return Ok(None)
+        };
+
+        if self.entries.len() == self.capacity {
+            if let Some(lru) = self.recency.pop_front() {
+                self.entries.remove(&lru);
+            }
+        }
+
+        self.recency.push_back(pk.clone());
+
+        Ok(Some(self.entries.entry(pk).or_insert(model)))
+    }
+
+    /// Remove the model with the provided primary key from the cache, returning it if it was present.
+    pub fn invalidate(&mut self, pk: &K) -> Option<M> {
+        let model = self.entries.remove(pk)?;
+        self.recency.retain(|key| key != pk);
+
+        Some(model)
+    }
+
+    /// Remove every model from the cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Check if the model with the provided primary key is cached, without loading it or updating its recency.
+    #[must_use]
+    pub fn contains(&self, pk: &K) -> bool {
+        self.entries.contains_key(pk)
+    }
+
+    /// The number of cached models.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Mark a cached key as the most recently used.
+    fn touch(&mut self, pk: &K) {
+        if let Some(idx) = self.recency.iter().position(|key| key == pk) {
+            let key = self.recency.remove(idx).expect("Index should be in bounds.");
+            self.recency.push_back(key);
+        }
+    }
+}
@@ -0,0 +1,320 @@
+//! Built-in [`#[bind]`/`#[extr]`](crate::macros::Model#bindextr) functions for common conversions.
+//! 
+//! # Example
+//! ```rust
+//! # use exemplar::Model;
+//! #[derive(Model)]
+//! #[table("flags")]
+//! pub struct Flag {
+//!     pub name: String,
+//!     #[bind(exemplar::convert::bind_strict_bool)]
+//!     #[extr(exemplar::convert::extr_strict_bool)]
+//!     pub enabled: bool,
+//! }
+//! ```
+
+#[cfg(feature = "json")]
+use std::collections::{
+    BTreeSet,
+    HashSet,
+};
+
+use std::error::Error as StdError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use std::time::{
+    Duration,
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use rusqlite::Error;
+
+use rusqlite::types::{
+    FromSqlError,
+    ToSqlOutput,
+    Value,
+    ValueRef,
+};
+
+use crate::{
+    BindResult,
+    ExtrResult,
+};
+
+/// Bind a `bool` as an `INTEGER` that is guaranteed to be either `0` or `1`.
+/// 
+/// Intended to be paired with [`extr_strict_bool`].
+pub fn bind_strict_bool(value: &bool) -> BindResult {
+    Ok(ToSqlOutput::Owned(
+        Value::Integer(*value as i64)
+    ))
+}
+
+/// Extract a `bool` from an `INTEGER`, erroring if the stored value is anything other than `0` or `1`.
+/// 
+/// `rusqlite`'s own [`FromSql`](rusqlite::types::FromSql) implementation for `bool` treats any non-zero integer as `true`;
+/// this function instead rejects such values with [`FromSqlError::OutOfRange`], which helps surface corrupt or legacy data early.
+pub fn extr_strict_bool(value: &ValueRef) -> ExtrResult<bool> {
+    match value.as_i64()? {
+        0 => Ok(false),
+        1 => Ok(true),
+        i => Err(FromSqlError::OutOfRange(i))
+    }
+}
+
+/// Bind a [`SystemTime`] as an `INTEGER` number of seconds since the Unix epoch.
+/// 
+/// Times before the epoch are stored as negative integers. Sub-second precision is truncated (towards the epoch.)
+/// 
+/// Intended to be paired with [`extr_unix_secs`].
+pub fn bind_unix_secs(value: &SystemTime) -> BindResult {
+    let secs = match value.duration_since(UNIX_EPOCH) {
+        Ok(since) => i64::try_from(since.as_secs()),
+        Err(before) => i64::try_from(before.duration().as_secs()).map(i64::wrapping_neg)
+    };
+
+    let secs = secs.map_err(|err| {
+        Error::ToSqlConversionFailure(Box::new(err))
+    })?;
+
+    Ok(ToSqlOutput::Owned(
+        Value::Integer(secs)
+    ))
+}
+
+/// Extract a [`SystemTime`] from an `INTEGER` number of seconds since the Unix epoch.
+/// 
+/// Negative integers are interpreted as times before the epoch. Values that can't be represented
+/// as a [`SystemTime`] on the current platform are rejected with [`FromSqlError::OutOfRange`].
+pub fn extr_unix_secs(value: &ValueRef) -> ExtrResult<SystemTime> {
+    let secs = value.as_i64()?;
+    let offset = Duration::from_secs(secs.unsigned_abs());
+
+    let time = if secs >= 0 {
+        UNIX_EPOCH.checked_add(offset)
+    }
+    else {
+        UNIX_EPOCH.checked_sub(offset)
+    };
+
+    time.ok_or(FromSqlError::OutOfRange(secs))
+}
+
+/// Bind a [`Duration`] as an `INTEGER` number of nanoseconds.
+/// 
+/// This preserves the full precision of a [`Duration`], at the cost of range - anything longer than [`i64::MAX`] nanoseconds
+/// (roughly 292 years) is rejected with an error.
+/// 
+/// Intended to be paired with [`extr_duration`].
+pub fn bind_duration(value: &Duration) -> BindResult {
+    let nanos = i64::try_from(value.as_nanos()).map_err(|err| {
+        Error::ToSqlConversionFailure(Box::new(err))
+    })?;
+
+    Ok(ToSqlOutput::Owned(
+        Value::Integer(nanos)
+    ))
+}
+
+/// Extract a [`Duration`] from an `INTEGER` number of nanoseconds.
+/// 
+/// Negative integers are rejected with [`FromSqlError::OutOfRange`].
+pub fn extr_duration(value: &ValueRef) -> ExtrResult<Duration> {
+    let nanos = value.as_i64()?;
+
+    u64::try_from(nanos)
+        .map(Duration::from_nanos)
+        .map_err(|_| FromSqlError::OutOfRange(nanos))
+}
+
+/// Bind an `f32` as a `REAL`.
+/// 
+/// The value is widened to an `f64` losslessly. Intended to be paired with [`extr_f32`].
+pub fn bind_f32(value: &f32) -> BindResult {
+    Ok(ToSqlOutput::Owned(
+        Value::Real(*value as f64)
+    ))
+}
+
+/// Extract an `f32` from a `REAL` (or `INTEGER`), with explicit narrowing.
+/// 
+/// SQLite stores all floating point numbers as 64-bit, so values are rounded to the nearest `f32` on read - this loses precision
+/// for anything that wasn't originally an `f32`. Unlike `rusqlite`'s built-in conversion (which silently turns out-of-range values into infinity),
+/// finite values outside the range of an `f32` are rejected with an error. Infinities and NaN are passed through unchanged.
+pub fn extr_f32(value: &ValueRef) -> ExtrResult<f32> {
+    let wide = match value {
+        ValueRef::Integer(i) => *i as f64,
+        _ => value.as_f64()?
+    };
+
+    let narrow = wide as f32;
+
+    if wide.is_finite() && narrow.is_infinite() {
+        return Err(FromSqlError::Other(
+            format!("the value {wide} is out of range for an f32").into()
+        ))
+    }
+
+    Ok(narrow)
+}
+
+/// Bind a `char` as a single-character `TEXT`.
+/// 
+/// Intended to be paired with [`extr_char`].
+pub fn bind_char(value: &char) -> BindResult {
+    Ok(ToSqlOutput::Owned(
+        Value::Text(value.to_string())
+    ))
+}
+
+/// Extract a `char` from a single-character `TEXT`.
+/// 
+/// Empty strings and strings containing more than one character (Unicode scalar value) are rejected with an error.
+pub fn extr_char(value: &ValueRef) -> ExtrResult<char> {
+    let str = value.as_str()?;
+    let mut chars = str.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(char), None) => Ok(char),
+        _ => Err(FromSqlError::Other(
+            format!("expected a single character, found {str:?}").into()
+        ))
+    }
+}
+
+/// Extract a [`String`] from `TEXT`, trimming leading and trailing whitespace.
+/// 
+/// Useful for legacy databases that store space-padded (`CHAR(n)`-style) values. Strings are bound as-is, so no bind function
+/// is needed - but note that trimming is one-way, and values written back will not be re-padded.
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("accounts")]
+/// pub struct Account {
+///     #[extr(exemplar::convert::extr_trimmed_string)]
+///     pub code: String,
+/// }
+/// ```
+pub fn extr_trimmed_string(value: &ValueRef) -> ExtrResult<String> {
+    Ok(value.as_str()?.trim().to_owned())
+}
+
+/// Bind a [`Path`] (or [`PathBuf`]) as `TEXT`, erroring if it is not valid UTF-8.
+/// 
+/// Intended to be paired with [`extr_path`]. If you'd rather replace invalid sequences than error, use [`bind_path_lossy`].
+pub fn bind_path(value: &Path) -> BindResult {
+    let Some(str) = value.to_str() else {
+        return Err(Error::ToSqlConversionFailure(
+            format!("the path {value:?} is not valid UTF-8").into()
+        ))
+    };
+
+    Ok(ToSqlOutput::Owned(
+        Value::Text(str.to_owned())
+    ))
+}
+
+/// Bind a [`Path`] (or [`PathBuf`]) as `TEXT`, replacing any invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+/// 
+/// Note that this is lossy - a path containing invalid UTF-8 will not round-trip. 
+pub fn bind_path_lossy(value: &Path) -> BindResult {
+    let str = value.to_string_lossy().into_owned();
+
+    Ok(ToSqlOutput::Owned(
+        Value::Text(str)
+    ))
+}
+
+/// Extract a [`PathBuf`] from `TEXT`, erroring if it is not valid UTF-8.
+pub fn extr_path(value: &ValueRef) -> ExtrResult<PathBuf> {
+    let path = value.as_str()?;
+    let path = PathBuf::from(path);
+
+    Ok(path)
+}
+
+/// Extract a [`PathBuf`] from `TEXT` (or a `BLOB`), replacing any invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+pub fn extr_path_lossy(value: &ValueRef) -> ExtrResult<PathBuf> {
+    let bytes = value.as_bytes()?;
+    let path = String::from_utf8_lossy(bytes).into_owned();
+
+    Ok(PathBuf::from(path))
+}
+
+/// Bind any [`Display`] type as `TEXT`, using its string representation.
+/// 
+/// Intended to be paired with [`extr_from_str`]; this is what [`#[as_text]`](crate::macros::Model#as_text) expands to.
+pub fn bind_display<T>(value: &T) -> BindResult
+where
+    T: Display + ?Sized
+{
+    Ok(ToSqlOutput::Owned(
+        Value::Text(value.to_string())
+    ))
+}
+
+/// Extract any [`FromStr`] type from `TEXT`, by parsing it.
+/// 
+/// Parse errors are wrapped in [`FromSqlError::Other`].
+pub fn extr_from_str<T>(value: &ValueRef) -> ExtrResult<T>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn StdError + Send + Sync>>
+{
+    value
+        .as_str()?
+        .parse()
+        .map_err(|err: T::Err| FromSqlError::Other(err.into()))
+}
+
+/// Bind any [`Serialize`](serde::Serialize) type as JSON `TEXT`.
+/// 
+/// Intended to be paired with [`extr_json`]. Serialization is deterministic for types with a defined iteration order - like [`Vec`] and
+/// [`BTreeSet`] (which is always written as a sorted array) - so the stored text is stable across runs. For [`HashSet`], use [`bind_json_set`] instead.
+#[cfg(feature = "json")]
+pub fn bind_json<T>(value: &T) -> BindResult
+where
+    T: serde::Serialize + ?Sized
+{
+    let json = serde_json::to_string(value)
+        .map_err(|err| Error::ToSqlConversionFailure(err.into()))?;
+
+    Ok(ToSqlOutput::Owned(
+        Value::Text(json)
+    ))
+}
+
+/// Bind a [`HashSet`] as a *sorted* JSON array in `TEXT`.
+/// 
+/// [`HashSet`] iteration order is unspecified (and varies between runs), so serializing one directly produces unstable output.
+/// This sorts the elements first, producing exactly the same text as [`bind_json`] would for the equivalent [`BTreeSet`] - which is
+/// useful for content-addressed storage and diffs. Intended to be paired with [`extr_json`].
+#[cfg(feature = "json")]
+pub fn bind_json_set<T, S>(value: &HashSet<T, S>) -> BindResult
+where
+    T: serde::Serialize + Ord
+{
+    let sorted: BTreeSet<&T> = value.iter().collect();
+
+    bind_json(&sorted)
+}
+
+/// Extract any [`Deserialize`](serde::Deserialize) type from JSON `TEXT`.
+/// 
+/// Deserialization errors are wrapped in [`FromSqlError::Other`].
+#[cfg(feature = "json")]
+pub fn extr_json<T>(value: &ValueRef) -> ExtrResult<T>
+where
+    T: serde::de::DeserializeOwned
+{
+    serde_json::from_str(value.as_str()?)
+        .map_err(|err| FromSqlError::Other(err.into()))
+}
@@ -0,0 +1,84 @@
+//! Adapters for running [`Model`] methods through "interact"-style closures.
+//! 
+//! Several async connection pools - most notably [`deadpool-sqlite`](https://docs.rs/deadpool-sqlite) - hand out connections
+//! by running a closure on a blocking thread, with a signature along the lines of:
+//! 
+//! ```ignore
+//! async fn interact<F, R>(&self, f: F) -> Result<R, InteractError>
+//! where
+//!     F: FnOnce(&mut Connection) -> R + Send + 'static,
+//!     R: Send + 'static;
+//! ```
+//! 
+//! Writing those closures by hand is repetitive, and borrowing from the surrounding scope isn't allowed. [`InteractModel`] is implemented for every
+//! [`Model`], and provides methods that take (or return) models by value and produce ready-made closures instead:
+//! 
+#![cfg_attr(feature = "deadpool", doc = "```rust")]
+#![cfg_attr(not(feature = "deadpool"), doc = "```ignore")]
+//! # use exemplar::Model;
+//! # use exemplar::interact::InteractModel;
+//! # use deadpool_sqlite::{Config, Runtime};
+//! #[derive(Debug, PartialEq, Model)]
+//! #[table("people")]
+//! struct Person {
+//!     name: String,
+//!     age: u16,
+//! }
+//! 
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let pool = Config::new(":memory:").create_pool(Runtime::Tokio1)?;
+//! let conn = pool.get().await?;
+//! 
+//! conn.interact(|conn| conn.execute_batch("CREATE TABLE people (name, age);")).await??;
+//! 
+//! let alice = Person { name: "Alice".to_owned(), age: 21 };
+//! 
+//! conn.interact(alice.insert_interact()).await??;
+//! 
+//! let people = conn.interact(Person::select_all_interact()).await??;
+//! 
+//! assert_eq!(people, vec![Person { name: "Alice".to_owned(), age: 21 }]);
+//! # Ok(())
+//! # }
+//! ```
+//! 
+//! The closures only depend on `rusqlite`, so they work with anything that runs an `FnOnce(&mut Connection)` - including
+//! [`AsyncConnection::call`](crate::asynchronous::AsyncConnection::call), when the `blocking` feature is enabled. Note that the pool must be
+//! built against the same version of `rusqlite` as Exemplar.
+
+use rusqlite::Connection;
+use rusqlite::Result;
+
+use crate::Model;
+use crate::OnConflict;
+
+/// Closure-producing counterparts to common [`Model`] methods, implemented for every [`Model`].
+/// 
+/// See the [module documentation](self) for an example of use with `deadpool-sqlite`.
+pub trait InteractModel: Model + Send + Sized + 'static {
+    /// Produce a closure that runs [`Model::insert`] on the provided connection.
+    fn insert_interact(self) -> impl FnOnce(&mut Connection) -> Result<()> + Send + 'static {
+        move |conn| self.insert(conn)
+    }
+
+    /// Produce a closure that runs [`Model::insert_or`] on the provided connection.
+    fn insert_or_interact(self, strategy: OnConflict) -> impl FnOnce(&mut Connection) -> Result<()> + Send + 'static {
+        move |conn| self.insert_or(conn, strategy)
+    }
+
+    /// Produce a closure that runs [`Model::insert_all_atomic`] on the provided connection.
+    fn insert_all_interact(items: Vec<Self>) -> impl FnOnce(&mut Connection) -> Result<()> + Send + 'static {
+        move |conn| Self::insert_all_atomic(conn, &items)
+    }
+
+    /// Produce a closure that runs [`Model::select_all`] on the provided connection.
+    fn select_all_interact() -> impl FnOnce(&mut Connection) -> Result<Vec<Self>> + Send + 'static {
+        |conn| Self::select_all(conn)
+    }
+}
+
+impl<M> InteractModel for M
+where
+    M: Model + Send + Sized + 'static
+{}
@@ -78,22 +78,54 @@
 //! - See the aformentioned [macro](crate::macros::Model)'s documentation to get started.
 //! - For handling `enum`s in models, check out the [`sql_enum`] macro.
 //! - For working with "anonymous" record types, look at the [`record`] macro.
+//! - For ready-made `#[bind]`/`#[extr]` functions, see the [`convert`] module.
+//! - For use with connection pools that run closures (like `deadpool-sqlite`), see the [`interact`] module.
+//! - For a read-through cache of models keyed by primary key, see the [`cache`] module.
+//! 
+//! # Cargo Features
+//! - `returning` - enables methods that rely on SQLite's `RETURNING` clause (3.35.0+), such as `Model::insert_if_absent`.
+//! - `blocking` - enables the [`asynchronous`] module, which provides executor-agnostic `async` methods built on the `blocking` crate.
+//! - `no-inline` - omits the `#[inline]` hints from derived [`Model`] methods, leaving inlining decisions to the compiler. This can noticeably
+//!   reduce binary size in programs with many models, at the cost of some speed in hot paths (like [`Model::from_row`] in a tight loop.)
+//! - `json` - enables [`#[bind]`/`#[extr]`](crate::macros::Model#bindextr) functions in [`convert`] for storing fields as JSON text, such as [`convert::bind_json`],
+//!   and [`ModelMeta::describe`] for describing models as JSON.
+//! - `array` - enables `rusqlite`'s `array` feature and [`Model::select_in`], which binds a list of values as a single `rarray` parameter.
+//!   This also enables `rusqlite`'s `modern_sqlite` feature, as `rarray` relies on APIs newer than the minimum SQLite version supported by default.
+//! - `deadpool` - adds no functionality; it pulls in `deadpool-sqlite` and `tokio` to test the [`interact`] module against a real pool.
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+#[cfg(feature = "blocking")]
+pub mod asynchronous;
+pub mod cache;
+pub mod convert;
+pub mod interact;
+
 mod macros;
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
 use std::ops::Deref;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
 
 use rusqlite::Connection;
+use rusqlite::Params;
 use rusqlite::Result;
 use rusqlite::Row;
+use rusqlite::Rows;
 use rusqlite::Statement;
 use rusqlite::ToSql;
 
 use rusqlite::types::{
     ToSqlOutput,
-    FromSqlResult
+    FromSql,
+    FromSqlError,
+    FromSqlResult,
+    Value,
+    ValueRef,
 };
 
 pub use crate::macros::*;
@@ -101,6 +133,8 @@ pub use crate::macros::*;
 /// Type alias for the outcome of converting a value to an SQL-friendly representation.
 /// 
 /// Note that the lifetime parameter is explicitly fixed as `'static` for the benefit of [`Model::to_params`].
+/// `#[bind]` functions that need to borrow from the field (such as a blob returned as `Cow<'_, [u8]>`) can return any
+/// `Result<T>` where `T: ToSql` instead; see [the `Model` derive](crate::macros::Model#bindextr).
 /// 
 /// # Example
 /// ```rust
@@ -119,8 +153,30 @@ pub use crate::macros::*;
 /// ```
 pub type BindResult = Result<ToSqlOutput<'static>>;
 
+/// Type alias for the outcome of converting a type into `N` SQL-friendly representations, as done by a `#[bind_multi]` function.
+/// 
+/// The outputs are bound in the same order as the columns listed in the attribute.
+/// 
+/// # Example
+/// ```rust
+/// # use exemplar::BindMultiResult;
+/// # use rusqlite::types::Value;
+/// # use rusqlite::types::ToSqlOutput;
+/// /// `#[bind_multi]` function used to split a (latitude, longitude) pair into two columns.
+/// pub fn split_coords(value: &(f64, f64)) -> BindMultiResult<2> {
+///     Ok([
+///         ToSqlOutput::Owned(Value::Real(value.0)),
+///         ToSqlOutput::Owned(Value::Real(value.1)),
+///     ])
+/// }
+/// ```
+pub type BindMultiResult<const N: usize> = Result<[ToSqlOutput<'static>; N]>;
+
 /// Type alias for the outcome of extracting a value from a [`Row`].
 /// 
+/// Extractors are not obligated to use this alias - any `Result<T, E>` where `E: Into<Box<dyn Error + Send + Sync>>`
+/// is accepted, and errors other than [`FromSqlError`] are wrapped in [`FromSqlError::Other`].
+/// 
 /// # Example
 /// ```rust
 /// # use std::path::PathBuf;
@@ -131,12 +187,92 @@ pub type BindResult = Result<ToSqlOutput<'static>>;
 /// pub fn extr_path(value: &ValueRef) -> ExtrResult<PathBuf> {
 ///     let path = value.as_str()?;
 ///     let path = PathBuf::from(path);
-///
+/// 
 ///     Ok(path)
 /// }
 /// ```
 pub type ExtrResult<T> = FromSqlResult<T>;
 
+/// An interface for types that know how to convert themselves to and from SQL, without implementing [`ToSql`] and [`FromSql`].
+/// 
+/// Derived [`Model`]s pick up `SqlConvert` implementations automatically - a field whose type implements `SqlConvert` (but not [`ToSql`]/[`FromSql`])
+/// is bound and extracted through it, no [`#[bind]`/`#[extr]`](crate::macros::Model#bindextr) attributes required. This keeps the conversion
+/// logic next to the type, rather than scattered across every model that uses it. `Option<T>` is covered too, with `None` mapping to `NULL`.
+/// 
+/// If a type implements both `SqlConvert` *and* [`ToSql`]/[`FromSql`], the latter take precedence. Field attributes (and [`#[convert]`](crate::macros::Model#convert) rules)
+/// always take precedence over both.
+/// 
+/// # Example
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use exemplar::{BindResult, ExtrResult, Model, SqlConvert};
+/// # use rusqlite::types::{ToSqlOutput, Value, ValueRef};
+/// pub struct HomeDir(PathBuf);
+/// 
+/// impl SqlConvert for HomeDir {
+///     fn bind(&self) -> BindResult {
+///         Ok(ToSqlOutput::Owned(
+///             Value::Text(self.0.to_string_lossy().into_owned())
+///         ))
+///     }
+/// 
+///     fn extr(value: &ValueRef) -> ExtrResult<Self> {
+///         Ok(Self(value.as_str()?.into()))
+///     }
+/// }
+/// 
+/// #[derive(Model)]
+/// #[table("users")]
+/// pub struct User {
+///     pub username: String,
+///     pub home_dir: HomeDir,
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be converted to or from SQL",
+    label = "no `ToSql`/`FromSql` or `SqlConvert` implementation for `{Self}`",
+    note = "implement rusqlite's `ToSql` and `FromSql` or exemplar's `SqlConvert` for `{Self}`, or use #[bind]/#[extr] on the field"
+)]
+pub trait SqlConvert {
+    /// Convert `self` to an SQL-friendly representation, like a [`#[bind]`](crate::macros::Model#bindextr) function.
+    fn bind(&self) -> BindResult;
+
+    /// Extract an instance of the implementing type from an SQL value, like an [`#[extr]`](crate::macros::Model#bindextr) function.
+    fn extr(value: &ValueRef) -> ExtrResult<Self>
+    where
+        Self: Sized;
+}
+
+impl<T> SqlConvert for Option<T>
+where
+    T: SqlConvert
+{
+    fn bind(&self) -> BindResult {
+        match self {
+            Some(value) => value.bind(),
+            None => Ok(ToSqlOutput::Owned(Value::Null)),
+        }
+    }
+
+    fn extr(value: &ValueRef) -> ExtrResult<Self> {
+        match value {
+            ValueRef::Null => Ok(None),
+            value => T::extr(value).map(Some),
+        }
+    }
+}
+
+/// `Cow<'static, str>` doesn't implement [`FromSql`], so it's extracted through this (always as an owned string.)
+impl SqlConvert for Cow<'static, str> {
+    fn bind(&self) -> BindResult {
+        Ok(ToSqlOutput::Owned(Value::Text(self.to_string())))
+    }
+
+    fn extr(value: &ValueRef) -> ExtrResult<Self> {
+        String::column_result(*value).map(Cow::Owned)
+    }
+}
+
 /// Type alias for a boxed slice of named query parameters.
 pub type Parameters<'a> = Box<[(&'a str, Parameter<'a>)]>;
 
@@ -146,7 +282,48 @@ pub type Parameters<'a> = Box<[(&'a str, Parameter<'a>)]>;
 /// 
 /// # Object Safety
 /// `Model` is mostly object safe, so you *can* have a [`dyn Model`](Model). The only caveat is that 
-/// the [`from_row`](Model::from_row) method is bounded to `Self: Sized` - you can't get a concrete `Self` from a trait object.
+/// methods like [`from_row`](Model::from_row) are bounded to `Self: Sized` - you can't get a concrete `Self` from a trait object.
+/// 
+/// # Transactions and Savepoints
+/// Both [`Transaction`](rusqlite::Transaction) and [`Savepoint`](rusqlite::Savepoint) dereference to [`Connection`], so a reference to either
+/// can be passed to any method taking a `&Connection` - no dedicated overloads are needed:
+/// 
+/// ```rust
+/// # use exemplar::*;
+/// # use rusqlite::*;
+/// #[derive(Model)]
+/// #[table("people")]
+/// pub struct Person {
+///     pub name: String,
+/// }
+/// 
+/// # fn main() -> Result<()> {
+/// # let mut conn = Connection::open_in_memory()?;
+/// # conn.execute_batch("CREATE TABLE people (name);")?;
+/// let mut tx = conn.transaction()?;
+/// 
+/// Person { name: "Alice".to_owned() }.insert(&tx)?;
+/// 
+/// {
+///     let sp = tx.savepoint()?;
+///     Person { name: "Bob".to_owned() }.insert(&sp)?;
+///     // Dropping a savepoint without committing rolls it back.
+/// }
+/// 
+/// tx.commit()?;
+/// 
+/// assert_eq!(Person::select_all(&conn)?.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+/// 
+/// Methods that use [`prepare_cached`](rusqlite::Connection::prepare_cached) (like [`insert`](Model::insert)) always go through the statement cache of the
+/// *underlying* connection. Cached statements hold no data and are reset after every use, so this is safe: rolling back a savepoint (or transaction)
+/// undoes the rows written through them, but leaves the cache intact - later calls, inside or outside of the savepoint, reuse the same prepared statements.
+/// 
+/// The exceptions are methods that need to own their transaction (like [`insert_all_atomic`](Model::insert_all_atomic) and [`sync_with`](Model::sync_with)),
+/// which take a `&mut Connection`. Within an existing transaction or savepoint, prefer methods that use savepoints internally
+/// (like [`insert_all_returning_rowids`](Model::insert_all_returning_rowids) and [`upsert_all`](Model::upsert_all)), as savepoints can be nested.
 pub trait Model {
     /// Attempt to extract an instance of `Self` from the provided [`Row`].
     /// 
@@ -165,205 +342,2683 @@ pub trait Model {
     /// ```
     /// 
     /// Note that this method is *not* object safe - you can't get a concrete `Self` from a [`dyn Model`](Model).
+    /// 
+    /// # Column Resolution
+    /// Each field is looked up by its column name (then by any [aliases](crate::macros::Model#column).) If none of the names are present in the row,
+    /// an [`InvalidColumnName`](rusqlite::Error::InvalidColumnName) error is returned.
+    /// 
+    /// Models that opt in with [`#[positional_fallback]`](crate::macros::Model#positional_fallback) instead fall back to reading such fields *by position* -
+    /// the `n`th field (ignoring `#[skip]`ped fields) is read from the `n`th column, and an error is only returned if that position is also out of bounds.
+    #[must_use = "this returns the extracted model, and has no other effect"]
     fn from_row(row: &Row) -> Result<Self>
     where
         Self: Sized;
-    
-    /// Attempt to insert `self` into the database behind the provided connection.
+
+    /// Attempt to extract an instance of `Self` from a map of column names to owned [`Value`]s.
     /// 
-    /// This method is a convenience shorthand for [`Model::insert_or`] with the [`Abort`](OnConflict::Abort) conflict resolution strategy.
+    /// This bridges generic layers that produce value maps (rather than [`Row`]s) into typed models. Each field is looked up by its
+    /// column name (then by any [aliases](crate::macros::Model#column)), applying any `#[extr]` functions; fields marked with `#[skip]` are
+    /// initialized with [`Default::default`]. Keys that don't correspond to a column are ignored.
     /// 
-    /// # Performance
-    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the insertion SQL statement,
-    /// so any calls after the first with the same connection and `self` type should be *almost* as fast as reusing a [`Statement`].
+    /// # Errors
+    /// A missing column is an error - [`InvalidColumnName`](rusqlite::Error::InvalidColumnName), with the column's primary name - *even* for
+    /// `Option` fields. Represent `NULL` explicitly with [`Value::Null`].
     /// 
-    /// If your program is extremely write-heavy, consider using [`Model::insert_with`], which avoids the overhead of a map lookup.
-    fn insert(&self, conn: &Connection) -> Result<()>;
-    
-    /// Attempt to insert `self` into the database behind the provided connection, using the provided [conflict resolution strategy](OnConflict).
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use std::collections::HashMap;
+    /// # use rusqlite::types::Value;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
     /// 
-    /// # Performance
-    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the insertion SQL statement,
-    /// so any calls after the first with the same connection and `self` type should be *almost* as fast as reusing a [`Statement`].
+    /// # fn main() -> rusqlite::Result<()> {
+    /// let map = HashMap::from([
+    ///     ("name".to_owned(), Value::Text("Alice".to_owned())),
+    ///     ("age".to_owned(), Value::Integer(21)),
+    /// ]);
     /// 
-    /// If your program is extremely write-heavy, consider using [`Model::insert_with`], which avoids the overhead of a map lookup.
-    fn insert_or(&self, conn: &Connection, strategy: OnConflict) -> Result<()>;
-    
-    /// Attempt to bind `self` to the provided statement and execute it.
+    /// let alice = Person::from_value_map(&map)?;
     /// 
-    /// This method serves two purposes:
-    /// - Enabling insertions into secondary tables (such as in-memory caches.)
-    /// - Squeezing out a few hundred extra nanoseconds of performance on insert operations. [`insert`](Model::insert)
-    ///   and [`insert_or`](Model::insert_or) use [`prepare_cached`](https://docs.rs/rusqlite/latest/rusqlite/struct.Connection.html#method.prepare_cached)
-    ///   to make the API convenient, but this incurs a map lookup on every call. [`insert_with`](Model::insert_with) can therefore
-    ///   help you squeeze out a bit more speed if your program is extremely write-heavy.
+    /// assert_eq!(alice.name, "Alice");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use = "this returns the extracted model, and has no other effect"]
+    fn from_value_map(map: &HashMap<String, Value>) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Attempt to overwrite `self` with the contents of the provided [`Row`].
     /// 
-    /// # Usage
-    /// Exemplar binds fields to statements as *named parameters.* Take this example model type:
+    /// This is an allocation-reusing alternative to [`Model::from_row`] for tight read loops. `String` and `Vec<u8>` fields
+    /// (without `#[extr]`) are cleared and refilled in place, reusing their existing buffers; all other fields are simply reassigned.
+    /// Fields marked with `#[skip]` are left untouched.
     /// 
-    /// ```rust
-    /// # use exemplar::*;
-    /// #[derive(Model)]
-    /// #[table("foos")]
-    /// pub struct Foo {
-    ///     pub bar: String,
-    ///     pub baz: String,
+    /// ```ignore
+    /// let mut person = Person::default();
+    /// let mut rows = stmt.query([])?;
+    /// 
+    /// while let Some(row) = rows.next()? {
+    ///     person.read_into(row)?;
+    ///     // ...
     /// }
     /// ```
     /// 
-    /// Exemplar will bind `bar` to `:bar` and `baz` to `:baz`, which can be used in a query like this:
+    /// Columns are resolved in the same way as [`Model::from_row`], including the (opt-in) positional fallback.
     /// 
-    /// ```sql
-    /// INSERT INTO foos (bar, baz) 
-    /// VALUES(:bar, :baz);
-    /// ```
+    /// # Errors
+    /// If extraction fails partway through, `self` may be left partially overwritten.
+    fn read_into(&mut self, row: &Row) -> Result<()>;
+
+    /// Resolve the index of each of the model's columns in the output of the provided [`Statement`], for use with [`Model::from_row_cached`].
     /// 
-    /// Or, in Rust:
+    /// Columns are resolved exactly as in [`Model::from_row`] (by name, then alias, then position), but only *once* - rather than for every row.
     /// 
+    /// # Example
     /// ```rust
     /// # use exemplar::*;
     /// # use rusqlite::*;
-    /// # #[derive(Model)]
-    /// # #[table("foos")]
-    /// # struct Foo { bar: String, baz: String }
-    /// # fn main() -> Result<()> {
-    /// let conn = Connection::open_in_memory()?;
-    /// 
-    /// conn.execute("CREATE TABLE foos (bar, baz);", [])?;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
     /// 
-    /// let mut stmt = conn.prepare("
-    ///     INSERT INTO foos (bar, baz) 
-    ///     VALUES(:bar, :baz);
-    /// ")?;
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+    /// # ")?;
+    /// let mut stmt = conn.prepare("SELECT age, name FROM people")?;
+    /// let map = Person::column_indices(&stmt)?;
     /// 
-    /// let foo = Foo {
-    ///     bar: "my_bar".to_string(),
-    ///     baz: "my_baz".to_string(),
-    /// };
+    /// assert_eq!(map.indices(), &[1, 0]);
     /// 
-    /// foo.insert_with(&mut stmt)?;
+    /// let people = stmt
+    ///     .query_and_then([], |row| Person::from_row_cached(row, &map))?
+    ///     .collect::<Result<Vec<_>>>()?;
+    /// # assert_eq!(people.len(), 2);
     /// # Ok(())
     /// # }
     /// ```
-    fn insert_with(&self, stmt: &mut Statement) -> Result<()>;
-    
-    /// Generate a slice of named [`Parameters`] from an instance of the implementing type.
-    ///  
-    /// # Performance
-    /// This method allocates at least once, in order to [`Box`] the returned slice.
     /// 
-    /// If the implementing type has any fields annotated with `#[bind]`, an additional boxing will be incurred for each annotated field.
-    fn to_params(&self) -> Result<Parameters>;
-
-    /// Static dispatch version of [`Model::metadata_dyn`].
-    fn metadata() -> ModelMeta
+    /// # Errors
+    /// A column that can't be resolved is an error, as in [`Model::from_row`].
+    fn column_indices(stmt: &Statement) -> Result<ColumnMap<Self>>
     where
         Self: Sized;
 
-    /// Retrieve [`ModelMeta`] (model metadata) associated with the implementing type.
-    /// 
-    /// This is the dynamic dispatch version of [`Model::metadata`].
-    /// If (for whatever reason) you find yourself needing to dynamically reflect on [`Model`] properties, then this is for you.
+    /// Attempt to extract an instance of `Self` from the provided [`Row`], using column indices resolved ahead of time with [`Model::column_indices`].
     /// 
-    /// # Performance
-    /// Despite the name of this method, [`ModelMeta`] consists solely of `'static` data generated at compile time, making it trivially copyable.
-    /// 
-    /// The only overhead on this call is therefore dynamic dispatch and several shallow copies.
-    fn metadata_dyn(&self) -> ModelMeta;
-}
+    /// This skips the per-row name lookups of [`Model::from_row`], which can add up in the hottest read loops. The map must have been
+    /// resolved from the same statement that produced the row; otherwise, columns will be read from the wrong positions.
+    #[must_use = "this returns the extracted model, and has no other effect"]
+    fn from_row_cached(row: &Row, map: &ColumnMap<Self>) -> Result<Self>
+    where
+        Self: Sized;
 
-/// Possible conflict resolution strategies when using [`Model::insert_or`].
-/// 
-/// The default setting (used by [`Model::insert`]) is [`Abort`](OnConflict::Abort).
-/// 
-/// Sourced from the [SQLite docs](https://www.sqlite.org/lang_conflict.html).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum OnConflict {
-    /// When an applicable constraint violation occurs, error and revert any changes made by the current SQL statement.
-    /// Prior SQL statements in the same transaction are unaffected, and the transaction remains active.
-    /// 
-    /// This is the default behavior.
-    Abort,
-    /// When an applicable constraint violation occurs, error but do *not* revert any changes made by the current SQL statement.
-    /// Prior SQL statements in the same transaction are unaffected, and the transaction remains active.
-    Fail,
-    /// When an applicable constraint violation occurs, skip the row that contains the constraint violation and continue processing
-    /// the current SQL statement. Other rows, prior statements and the current transaction are unaffected.
+    /// Attempt to extract an instance of `Self` from the provided [`Row`], wrapped in an [`Arc`].
     /// 
-    /// When encountering a foreign key constraint error, this behaves like [`Abort`](Self::Abort).
-    Ignore,
-    /// - When a uniqueness or primary key constraint violation occurs, delete the offending rows and continue.
-    /// - If a `NOT NULL` constraint violation occurs, the offending column will either be replaced with the default value (if one exists) or [`Abort`](Self::Abort) behavior will be used.
-    /// - If a `CHECK` or foreign key constraint violation occurs, [`Abort`](Self::Abort) behavior will be used.
-    Replace,
-    /// When an applicable constraint violation occurs, abort the current SQL statement and roll back the current transaction.
-    /// If no transaction is active, [`Abort`](Self::Abort) behavior will be used.
-    Rollback,
-}
-
-impl Default for OnConflict {
-    fn default() -> Self {
-        Self::Abort
-    }
-}
-
-/// [`Cow`](std::borrow::Cow)-like type for query parameters.
-/// 
-/// Necessary to efficiently implement [`Model::to_params`] - while most fields can be directly referenced as
-/// a [`dyn ToSql`](ToSql), those with `#[bind]` and `#[extr]` parameters require a non-trivial conversion step.
-/// 
-/// [`Self::Borrowed`] is used for the former case, while [`Self::Boxed`] is used for the latter case. 
-/// 
-/// This type implements [`ToSql`] and dereferences to `dyn ToSql`, so it can be used just like any other query parameter.
-pub enum Parameter<'a> {
-    /// A borrowed [`ToSql`] trait object.
-    Borrowed(&'a dyn ToSql),
-    /// A boxed [`ToSql`] trait object.
-    Boxed(Box<dyn ToSql + 'a>)
-}
-
-impl<'a> ToSql for Parameter<'a> {
-    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
-        match self {
-            Self::Borrowed(param) => param.to_sql(),
-            Self::Boxed(param) => param.to_sql(),
-        }
+    /// This is a convenience shorthand for [`Model::from_row`] followed by [`Arc::new`], intended for
+    /// use with [`query_and_then`](https://docs.rs/rusqlite/latest/rusqlite/struct.Statement.html#method.query_and_then)
+    /// when loading models into shared caches.
+    #[must_use = "this returns the extracted model, and has no other effect"]
+    fn from_row_arc(row: &Row) -> Result<Arc<Self>>
+    where
+        Self: Sized
+    {
+        Self::from_row(row).map(Arc::new)
     }
-}
 
-impl<'a> Deref for Parameter<'a> {
-    type Target = dyn ToSql + 'a;
-
-    fn deref(&self) -> &Self::Target {
-        match self {
-            Self::Borrowed(param) => *param,
-            Self::Boxed(param) => param,
-        }
+    /// Attempt to extract an instance of `Self` from the provided [`Row`], wrapped in an [`Rc`].
+    /// 
+    /// This is the single-threaded counterpart to [`Model::from_row_arc`].
+    #[must_use = "this returns the extracted model, and has no other effect"]
+    fn from_row_rc(row: &Row) -> Result<Rc<Self>>
+    where
+        Self: Sized
+    {
+        Self::from_row(row).map(Rc::new)
     }
-}
+    
+    /// Attempt to retrieve every row in the model table.
+    /// 
+    /// The generated query is `SELECT <columns> FROM <table>`, where `<columns>` are *only* those the model maps to - any other columns in the 
+    /// table are never read. This means you can define "slim" models over a subset of a wide table (say, for a list view) without paying
+    /// to fetch unneeded data like large blobs.
+    /// 
+    /// Rows are returned in whatever order SQLite produces them. If you need a specific order or filter, write the query yourself and use [`Model::from_row`].
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the query SQL statement,
+    /// so any calls after the first with the same connection and `Self` type should be *almost* as fast as reusing a [`Statement`].
+    #[must_use = "this returns the selected models, and has no other effect"]
+    fn select_all(conn: &Connection) -> Result<Vec<Self>>
+    where
+        Self: Sized;
 
-/// Metadata about a [`Model`] implementor.
-/// 
-/// Can be retrieved via the [`Model::metadata`] and [`Model::metadata_dyn`] methods.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ModelMeta {
-    /// The name of the model type.
+    /// Prepare the statement used by [`Model::select_all`], without executing it.
+    /// 
+    /// The statement has no parameters, and yields rows that can be passed to [`Model::from_row`]. Use this when you want to
+    /// drive the read loop yourself - for example, to stream rows without collecting them, or to reuse one statement across many runs.
+    /// 
+    /// Unlike [`Model::select_all`], the statement is prepared with [`prepare`](rusqlite::Connection::prepare) rather than
+    /// [`prepare_cached`](rusqlite::Connection::prepare_cached), as it is handed to the caller.
     /// 
     /// # Example
     /// ```rust
     /// # use exemplar::*;
+    /// # use rusqlite::Connection;
     /// #[derive(Model)]
-    /// #[table("foos")]
-    /// pub struct Foo { 
-    ///     pub field: String 
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
     /// }
     /// 
-    /// assert_eq!("Foo", Foo::metadata().model)
+    /// # fn main() -> rusqlite::Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("CREATE TABLE people (name);")?;
+    /// let mut stmt = Person::prepare_select_all(&conn)?;
+    /// 
+    /// for person in stmt.query_and_then([], Person::from_row)? {
+    ///     println!("{}", person?.name);
+    /// }
+    /// # Ok(())
+    /// # }
     /// ```
-    pub model: &'static str,
-    /// The name of the model table.
+    #[must_use = "this returns the prepared statement, and has no other effect"]
+    fn prepare_select_all(conn: &Connection) -> Result<Statement<'_>>
+    where
+        Self: Sized;
+
+    /// Render the model's columns as a comma-separated list, each qualified with the provided table alias.
+    /// 
+    /// This is intended for hand-written joins, where unqualified names could be ambiguous:
     /// 
-    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("users")]
+    /// pub struct User {
+    ///     pub username: String,
+    ///     #[column("pwd")]
+    ///     pub password: Vec<u8>,
+    /// }
+    /// 
+    /// let sql = format!(
+    ///     "SELECT {} FROM users u JOIN sessions s ON s.username = u.username",
+    ///     User::columns_for_select("u")
+    /// );
+    /// 
+    /// assert_eq!(sql, "SELECT u.username AS username, u.pwd AS pwd FROM users u JOIN sessions s ON s.username = u.username");
+    /// ```
+    /// 
+    /// Each column is aliased back to its own name (SQLite doesn't guarantee the name of an unaliased result column), so rows from such a query can be
+    /// read with [`Model::from_row`] as usual. Columns are listed in model order.
+    /// 
+    /// Fields computed with [`#[select_expr]`](crate::macros::Model#select_expr) are listed last, as `<expression> AS <column>`. The expression is emitted
+    /// verbatim (it isn't qualified), so write it with qualified names if it could be ambiguous in a join.
+    #[must_use]
+    fn columns_for_select(table_alias: &str) -> String
+    where
+        Self: Sized
+    {
+        let meta = Self::metadata();
+
+        meta
+            .columns
+            .iter()
+            .map(|column| format!("{table_alias}.{column} AS {column}"))
+            .chain(meta.computed.iter().map(|(column, expr)| format!("{expr} AS {column}")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Attempt to retrieve every row in the model table, paired with its `rowid`.
+    /// 
+    /// The generated query is `SELECT <columns>, rowid FROM <table>`. This lets you get at the implicit `rowid` without adding a field
+    /// for it to the model - useful for tables that don't alias it with an `INTEGER PRIMARY KEY`. Tables created `WITHOUT ROWID` don't have one,
+    /// and so return an error.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name);
+    /// #     INSERT INTO people VALUES ('Alice'), ('Bob');
+    /// # ")?;
+    /// for (rowid, person) in Person::select_all_with_rowid(&conn)? {
+    ///     println!("{rowid}: {}", person.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the query SQL statement.
+    #[must_use = "this returns the selected models, and has no other effect"]
+    fn select_all_with_rowid(conn: &Connection) -> Result<Vec<(i64, Self)>>
+    where
+        Self: Sized
+    {
+        let meta = Self::metadata();
+
+        // The rowid goes last, so the model's own columns keep the ordinals #[positional_fallback] relies on.
+        let rowid = meta.columns.len() + meta.computed.len();
+
+        let sql = format!(
+            "SELECT {}, rowid FROM {};",
+            select_list(&meta),
+            meta.table
+        );
+
+        conn.prepare_cached(&sql)?
+            .query_and_then([], |row| Ok((row.get(rowid)?, Self::from_row(row)?)))?
+            .collect()
+    }
+
+    /// Stream every row in the model table through the provided callback, without collecting them.
+    /// 
+    /// This runs the same query as [`Model::select_all`], but only one model is held in memory at a time - making it suitable
+    /// for processing tables too large to load at once. Iteration stops at the first error, whether it comes from extracting a row or
+    /// from the callback itself, and that error is returned.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+    /// # ")?;
+    /// let mut total_age = 0;
+    /// 
+    /// Person::for_each(&conn, |person| {
+    ///     total_age += person.age;
+    ///     Ok(())
+    /// })?;
+    /// 
+    /// assert_eq!(total_age, 111);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn for_each<F>(conn: &Connection, mut f: F) -> Result<()>
+    where
+        Self: Sized,
+        F: FnMut(Self) -> Result<()>
+    {
+        let mut stmt = Self::prepare_select_all(conn)?;
+        let mut rows = stmt.query([])?;
+
+        while let Some(row) = rows.next()? {
+            f(Self::from_row(row)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Attempt to retrieve every row in the model table where `column` is equal to `value`.
+    /// 
+    /// The generated query is `SELECT <columns> FROM <table> WHERE <column> = ?`. Unlike [`Model::delete_where`], the column name
+    /// is checked against [`ModelMeta::columns`] before being interpolated, so it's safe to take it from untrusted input; unknown
+    /// columns return [`InvalidColumnName`](rusqlite::Error::InvalidColumnName).
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90), ('Carol', 21);
+    /// # ")?;
+    /// let people = Person::select_by(&conn, "age", 21)?;
+    /// 
+    /// assert_eq!(people.len(), 2);
+    /// assert!(Person::select_by(&conn, "1 = 1 OR age", 21).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the query SQL statement.
+    #[must_use = "this returns the selected models, and has no other effect"]
+    fn select_by<V>(conn: &Connection, column: &str, value: V) -> Result<Vec<Self>>
+    where
+        Self: Sized,
+        V: ToSql
+    {
+        let meta = Self::metadata();
+
+        if !meta.columns.contains(&column) {
+            return Err(rusqlite::Error::InvalidColumnName(column.to_owned()))
+        }
+
+        let sql = format!(
+            "SELECT {} FROM {} WHERE {column} = ?;",
+            select_list(&meta),
+            meta.table
+        );
+
+        conn.prepare_cached(&sql)?
+            .query_and_then([value], Self::from_row)?
+            .collect()
+    }
+
+    /// Attempt to retrieve every row in the model table where `column` is equal to any of `values`.
+    /// 
+    /// The generated query is `SELECT <columns> FROM <table> WHERE <column> IN rarray(?)`, with `values` bound as a single
+    /// [array](rusqlite::vtab::array) parameter - so there's no need to build a placeholder list, however many values there are.
+    /// As with [`Model::select_by`], the column name is checked against [`ModelMeta::columns`], and unknown columns return
+    /// [`InvalidColumnName`](rusqlite::Error::InvalidColumnName).
+    /// 
+    /// The `rarray` table-valued function must be registered on the connection beforehand, using [`load_module`](rusqlite::vtab::array::load_module).
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90), ('Carol', 45);
+    /// # ")?;
+    /// rusqlite::vtab::array::load_module(&conn)?;
+    /// 
+    /// let people = Person::select_in(&conn, "name", &["Alice", "Carol"])?;
+    /// 
+    /// assert_eq!(people.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the query SQL statement.
+    #[cfg(feature = "array")]
+    #[must_use = "this returns the selected models, and has no other effect"]
+    fn select_in<V>(conn: &Connection, column: &str, values: &[V]) -> Result<Vec<Self>>
+    where
+        Self: Sized,
+        V: ToSql
+    {
+        let meta = Self::metadata();
+
+        if !meta.columns.contains(&column) {
+            return Err(rusqlite::Error::InvalidColumnName(column.to_owned()))
+        }
+
+        let values = values
+            .iter()
+            .map(|value| to_owned_value(value.to_sql()?))
+            .collect::<Result<Vec<_>>>()?;
+
+        let sql = format!(
+            "SELECT {} FROM {} WHERE {column} IN rarray(?);",
+            select_list(&meta),
+            meta.table
+        );
+
+        conn.prepare_cached(&sql)?
+            .query_and_then([Rc::new(values)], Self::from_row)?
+            .collect()
+    }
+
+    /// Attempt to retrieve every row in the model table, as a map keyed by the value of `key_column`.
+    /// 
+    /// This is handy for building lookup indexes on a unique column. As with [`Model::select_by`], the column name is checked against
+    /// [`ModelMeta::columns`], and unknown columns return [`InvalidColumnName`](rusqlite::Error::InvalidColumnName).
+    /// 
+    /// If the column isn't actually unique, only one of the rows sharing a key is kept - which one is unspecified.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+    /// # ")?;
+    /// let people = Person::load_map_by::<String>(&conn, "name")?;
+    /// 
+    /// assert_eq!(people["Bob"].age, 90);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the query SQL statement.
+    #[must_use = "this returns the selected models, and has no other effect"]
+    fn load_map_by<K>(conn: &Connection, key_column: &str) -> Result<HashMap<K, Self>>
+    where
+        Self: Sized,
+        K: FromSql + Eq + std::hash::Hash
+    {
+        let meta = Self::metadata();
+
+        let Some(key_index) = meta.columns.iter().position(|column| *column == key_column) else {
+            return Err(rusqlite::Error::InvalidColumnName(key_column.to_owned()))
+        };
+
+        let sql = format!(
+            "SELECT {} FROM {};",
+            select_list(&meta),
+            meta.table
+        );
+
+        conn.prepare_cached(&sql)?
+            .query_and_then([], |row| Ok((row.get(key_index)?, Self::from_row(row)?)))?
+            .collect()
+    }
+
+    /// Write every row of the model's table to `out` as CSV, preceded by a header row of column names.
+    /// 
+    /// Rows are streamed one at a time, rather than collected into memory. Values are written as stored, without going
+    /// through [`Model::from_row`] - so any `#[bind]` conversions are already applied. Formatting follows RFC 4180:
+    /// 
+    /// - Records are separated by `CRLF` line breaks.
+    /// - Fields containing commas, double quotes or line breaks are enclosed in double quotes, with any double quotes doubled.
+    /// - `NULL` is written as an empty field, and blobs are written as lowercase hexadecimal.
+    /// 
+    /// Fields computed with [`#[select_expr]`](crate::macros::Model#select_expr) are not exported.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let conn = rusqlite::Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob, Jr.', 90);
+    /// # ")?;
+    /// let mut csv = Vec::new();
+    /// 
+    /// Person::export_csv(&conn, &mut csv)?;
+    /// 
+    /// assert_eq!(csv, b"name,age\r\nAlice,21\r\n\"Bob, Jr.\",90\r\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Errors
+    /// Query errors are returned as [`ExportError::Sql`], and I/O errors from `out` as [`ExportError::Io`].
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the query SQL statement.
+    /// Every record is written with a separate call to [`write_all`](std::io::Write::write_all), so consider wrapping `out` in a [`BufWriter`](std::io::BufWriter).
+    fn export_csv<W>(conn: &Connection, mut out: W) -> std::result::Result<(), ExportError>
+    where
+        Self: Sized,
+        W: std::io::Write
+    {
+        let meta = Self::metadata();
+        let mut record = String::new();
+
+        for (idx, column) in meta.columns.iter().enumerate() {
+            if idx > 0 {
+                record.push(',');
+            }
+
+            csv_field(&mut record, ValueRef::Text(column.as_bytes()));
+        }
+
+        record.push_str("\r\n");
+        out.write_all(record.as_bytes())?;
+
+        let sql = format!(
+            "SELECT {} FROM {};",
+            meta.columns.join(", "),
+            meta.table
+        );
+
+        let mut stmt = conn.prepare_cached(&sql)?;
+        let mut rows = stmt.query([])?;
+
+        while let Some(row) = rows.next()? {
+            record.clear();
+
+            for idx in 0..meta.columns.len() {
+                if idx > 0 {
+                    record.push(',');
+                }
+
+                csv_field(&mut record, row.get_ref(idx)?);
+            }
+
+            record.push_str("\r\n");
+            out.write_all(record.as_bytes())?;
+        }
+
+        Ok(out.flush()?)
+    }
+
+    /// Check whether the model's table exists in the database behind the provided connection.
+    /// 
+    /// If the [table name](crate::macros::Model#table) is schema-qualified (like `aux.users`), only that schema is searched. Otherwise,
+    /// the `main` and `temp` schemas are. As in SQLite itself, names are compared case-insensitively.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> rusqlite::Result<()> {
+    /// let conn = rusqlite::Connection::open_in_memory()?;
+    /// 
+    /// if !Person::table_exists(&conn)? {
+    ///     conn.execute_batch(&Person::metadata().create_table_sql_untyped())?;
+    /// }
+    /// 
+    /// assert!(Person::table_exists(&conn)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the query SQL statement.
+    #[must_use = "this returns whether the table exists, and has no other effect"]
+    fn table_exists(conn: &Connection) -> Result<bool>
+    where
+        Self: Sized
+    {
+        let table = Self::metadata().table;
+
+        let (sql, name) = match table.split_once('.') {
+            Some((schema, name)) => (
+                format!("SELECT EXISTS (SELECT 1 FROM {schema}.sqlite_master WHERE type = 'table' AND name = ?1 COLLATE NOCASE);"),
+                name
+            ),
+            None => (
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1 COLLATE NOCASE) \
+                    OR EXISTS (SELECT 1 FROM sqlite_temp_master WHERE type = 'table' AND name = ?1 COLLATE NOCASE);".to_owned(),
+                table
+            )
+        };
+
+        query_scalar(conn, &sql, [name])
+    }
+
+    /// Attempt to insert `self` into the database behind the provided connection.
+    /// 
+    /// This method is a convenience shorthand for [`Model::insert_or`] with the model's default conflict resolution strategy.
+    /// This is [`Abort`](OnConflict::Abort) unless overridden with the [`#[on_conflict]`](crate::macros::Model#on_conflict) attribute.
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the insertion SQL statement,
+    /// so any calls after the first with the same connection and `self` type should be *almost* as fast as reusing a [`Statement`].
+    /// 
+    /// If your program is extremely write-heavy, consider using [`Model::insert_with`], which avoids the overhead of a map lookup.
+    fn insert(&self, conn: &Connection) -> Result<()>;
+    
+    /// Attempt to insert `self` into the database behind the provided connection, using the provided [conflict resolution strategy](OnConflict).
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the insertion SQL statement,
+    /// so any calls after the first with the same connection and `self` type should be *almost* as fast as reusing a [`Statement`].
+    /// 
+    /// If your program is extremely write-heavy, consider using [`Model::insert_with`], which avoids the overhead of a map lookup.
+    fn insert_or(&self, conn: &Connection, strategy: OnConflict) -> Result<()>;
+
+    /// Attempt to insert `self` into the database behind the provided connection, using the provided [conflict resolution strategy](OnConflict),
+    /// and return the number of rows changed.
+    /// 
+    /// This is identical to [`Model::insert_or`], except the change count reported by SQLite is returned rather than discarded.
+    /// It's normally `1`, but will be `0` if the row was skipped under [`Ignore`](OnConflict::Ignore) - which is useful for auditing.
+    /// 
+    /// The count is the same as [`Connection::changes`] would report after [`Model::insert_or`], and doesn't rely on SQLite's `RETURNING` clause -
+    /// so unlike `Model::insert_if_absent`, this works on every supported SQLite version without the `returning` feature.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("CREATE TABLE people (name PRIMARY KEY, age);")?;
+    /// let alice = Person { name: "Alice".to_owned(), age: 21 };
+    /// 
+    /// assert_eq!(alice.insert_returning_changes(&conn, OnConflict::Ignore)?, 1);
+    /// assert_eq!(alice.insert_returning_changes(&conn, OnConflict::Ignore)?, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the insertion SQL statement, exactly like [`Model::insert_or`].
+    fn insert_returning_changes(&self, conn: &Connection, strategy: OnConflict) -> Result<usize>;
+
+    /// Attempt to insert `self` into the database behind the provided connection, using the provided [conflict resolution strategy](OnConflict).
+    /// 
+    /// Unlike [`Model::insert_or`], this is not generated code - it is implemented purely in terms of [`Model::metadata_dyn`]
+    /// (for the [insertion SQL](ModelMeta::insert_sql)) and [`Model::to_params`]. The two are equivalent in effect, but this makes
+    /// the dynamic path explicit when working with heterogeneous collections of trait objects:
+    /// 
+    /// ```ignore
+    /// let models: Vec<Box<dyn Model>> = vec![Box::new(alice), Box::new(rex)];
+    /// 
+    /// for model in &models {
+    ///     model.insert_dyn_or(&conn, OnConflict::Ignore)?;
+    /// }
+    /// ```
+    /// 
+    /// # Performance
+    /// In addition to the cost of [`Model::to_params`], this allocates a vector of parameter references on every call.
+    /// For concrete types, prefer [`Model::insert_or`].
+    fn insert_dyn_or(&self, conn: &Connection, strategy: OnConflict) -> Result<()> {
+        let sql = self
+            .metadata_dyn()
+            .insert_sql(strategy);
+
+        let params = self.to_params()?;
+
+        let params: Vec<_> = params
+            .iter()
+            .map(|(name, param)| (*name, param as &dyn ToSql))
+            .collect();
+
+        conn.prepare_cached(sql)?.execute(params.as_slice())?;
+
+        Ok(())
+    }
+
+    /// Attempt to insert `self` into the database behind the provided connection, bypassing the statement cache.
+    /// 
+    /// This method is equivalent to [`Model::insert`] (including its default conflict resolution strategy),
+    /// but prepares a fresh statement instead of using [`prepare_cached`](rusqlite::Connection::prepare_cached).
+    /// 
+    /// # Performance
+    /// For one-shot inserts (such as in tests that open a new in-memory database each time) this avoids the cost
+    /// of a cache lookup and entry that will never be reused. For repeated inserts on the same connection, prefer [`Model::insert`].
+    fn insert_uncached(&self, conn: &Connection) -> Result<()>;
+    
+    /// Attempt to bind `self` to the provided statement and execute it.
+    /// 
+    /// This method serves two purposes:
+    /// - Enabling insertions into secondary tables (such as in-memory caches.)
+    /// - Squeezing out a few hundred extra nanoseconds of performance on insert operations. [`insert`](Model::insert)
+    ///   and [`insert_or`](Model::insert_or) use [`prepare_cached`](https://docs.rs/rusqlite/latest/rusqlite/struct.Connection.html#method.prepare_cached)
+    ///   to make the API convenient, but this incurs a map lookup on every call. [`insert_with`](Model::insert_with) can therefore
+    ///   help you squeeze out a bit more speed if your program is extremely write-heavy.
+    /// 
+    /// # Usage
+    /// Exemplar binds fields to statements as *named parameters.* Take this example model type:
+    /// 
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo {
+    ///     pub bar: String,
+    ///     pub baz: String,
+    /// }
+    /// ```
+    /// 
+    /// Exemplar will bind `bar` to `:bar` and `baz` to `:baz`, which can be used in a query like this:
+    /// 
+    /// ```sql
+    /// INSERT INTO foos (bar, baz) 
+    /// VALUES(:bar, :baz);
+    /// ```
+    /// 
+    /// Or, in Rust:
+    /// 
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// # #[derive(Model)]
+    /// # #[table("foos")]
+    /// # struct Foo { bar: String, baz: String }
+    /// # fn main() -> Result<()> {
+    /// let conn = Connection::open_in_memory()?;
+    /// 
+    /// conn.execute("CREATE TABLE foos (bar, baz);", [])?;
+    /// 
+    /// let mut stmt = conn.prepare("
+    ///     INSERT INTO foos (bar, baz) 
+    ///     VALUES(:bar, :baz);
+    /// ")?;
+    /// 
+    /// let foo = Foo {
+    ///     bar: "my_bar".to_string(),
+    ///     baz: "my_baz".to_string(),
+    /// };
+    /// 
+    /// foo.insert_with(&mut stmt)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn insert_with(&self, stmt: &mut Statement) -> Result<()>;
+
+    /// Attempt to insert `self` into the database behind the provided connection, omitting any `Option` fields that are `None`.
+    /// 
+    /// Omitted columns are left out of the generated `INSERT` entirely, so their schema defaults apply (rather than an explicit `NULL`.)
+    /// If every field is omitted, `INSERT ... DEFAULT VALUES` is used. The model's default conflict strategy (see [`Model::insert`]) is respected.
+    /// 
+    /// Only fields whose type is *syntactically* an `Option` (e.g. `Option<T>` or `std::option::Option<T>`, but not a type alias) are eligible for omission.
+    /// 
+    /// # Performance
+    /// The statement is built at runtime (allocating several times), then prepared with [`prepare_cached`](rusqlite::Connection::prepare_cached).
+    /// Each distinct combination of omitted fields is a separate cache entry.
+    fn insert_sparse(&self, conn: &Connection) -> Result<()>;
+
+    /// Attempt to insert `self` into the database behind the provided connection, doing nothing if the row already exists.
+    /// 
+    /// Returns `true` if a row was newly inserted, and `false` if the insertion was skipped due to a conflict
+    /// (such as a uniqueness or primary key constraint violation.)
+    /// 
+    /// This uses an `INSERT ... ON CONFLICT DO NOTHING RETURNING 1` statement under the hood, and therefore requires SQLite 3.35.0 or newer.
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the insertion SQL statement,
+    /// so any calls after the first with the same connection and `self` type should be *almost* as fast as reusing a [`Statement`].
+    #[cfg(feature = "returning")]
+    fn insert_if_absent(&self, conn: &Connection) -> Result<bool>;
+
+    /// Attempt to insert every item in `items` into the database behind the provided connection, returning the rowid of each new row.
+    /// 
+    /// The returned rowids are in the same order as `items`, which makes this useful for building a dependent batch
+    /// (such as rows in a child table that reference the new rows.)
+    /// 
+    /// All insertions happen within a savepoint (so this can be used both inside and outside of a transaction);
+    /// if any insertion fails, none of them take effect.
+    /// 
+    /// # Conflicts
+    /// Rowids are collected with [`last_insert_rowid`](rusqlite::Connection::last_insert_rowid). If an insertion is skipped
+    /// (e.g. because the model uses `#[on_conflict("ignore")]`), the rowid reported for that item will be stale.
+    fn insert_all_returning_rowids(conn: &Connection, items: &[Self]) -> Result<Vec<i64>>
+    where
+        Self: Sized
+    {
+        with_savepoint(conn, "exemplar_insert_all", || {
+            items
+                .iter()
+                .map(|item| {
+                    item.insert(conn)?;
+                    Ok(conn.last_insert_rowid())
+                })
+                .collect()
+        })
+    }
+
+    /// Attempt to insert every item in `items` within a new transaction, with all-or-nothing semantics.
+    /// 
+    /// Every item is inserted with [`Abort`](OnConflict::Abort) conflict resolution, regardless of any [`#[on_conflict]`](crate::macros::Model#on_conflict)
+    /// override - so if *any* item violates a constraint, the transaction is rolled back and the error returned, leaving the table untouched.
+    /// Otherwise, the transaction is committed. This differs from inserting with [`Ignore`](OnConflict::Ignore), which would skip the offending items and keep the rest.
+    /// 
+    /// A transaction can't be nested, which is why this takes a `&mut` [`Connection`]. If you're already in a transaction, use
+    /// [`Model::insert_all_returning_rowids`] instead, which uses a savepoint.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let mut conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("CREATE TABLE people (name PRIMARY KEY, age);")?;
+    /// let people = [
+    ///     Person { name: "Alice".to_owned(), age: 21 },
+    ///     Person { name: "Alice".to_owned(), age: 22 },
+    /// ];
+    /// 
+    /// assert!(Person::insert_all_atomic(&mut conn, &people).is_err());
+    /// assert!(Person::select_all(&conn)?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// Insertions use [`prepare_cached`](rusqlite::Connection::prepare_cached), as with [`Model::insert_or`].
+    fn insert_all_atomic(conn: &mut Connection, items: &[Self]) -> Result<()>
+    where
+        Self: Sized
+    {
+        let tx = conn.transaction()?;
+
+        for item in items {
+            item.insert_or(&tx, OnConflict::Abort)?;
+        }
+
+        tx.commit()
+    }
+
+    /// Attempt to insert or update every item in `items`, using an `INSERT ... ON CONFLICT (...) DO UPDATE` statement ("upsert".)
+    /// 
+    /// `conflict_cols` names the column(s) of the `UNIQUE` or `PRIMARY KEY` constraint used to detect existing rows. When an item conflicts,
+    /// every *other* column of the existing row is overwritten with the item's value. (If there are no other columns, conflicting items are skipped.)
+    /// 
+    /// A single statement is prepared and reused for every item, and all items are written within a savepoint - so this can be used both inside and
+    /// outside of a transaction, and if any item fails, none of them take effect. Upserts require SQLite 3.24.0 or newer.
+    /// 
+    /// # Security
+    /// The column names are interpolated directly into the statement and are therefore *trusted SQL*. Never take them from user input.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name PRIMARY KEY, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21);
+    /// # ")?;
+    /// let people = [
+    ///     Person { name: "Alice".to_owned(), age: 22 },
+    ///     Person { name: "Bob".to_owned(), age: 90 },
+    /// ];
+    /// 
+    /// Person::upsert_all(&conn, &people, &["name"])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn upsert_all(conn: &Connection, items: &[Self], conflict_cols: &[&str]) -> Result<()>
+    where
+        Self: Sized
+    {
+        let meta = Self::metadata();
+        
+        let updates: Vec<_> = meta
+            .columns
+            .iter()
+            .filter(|column| !conflict_cols.contains(column))
+            .map(|column| format!("{column} = excluded.{column}"))
+            .collect();
+
+        let action = match updates.is_empty() {
+            true => "NOTHING".to_owned(),
+            false => format!("UPDATE SET {}", updates.join(", ")),
+        };
+
+        let sql = format!(
+            "{} ON CONFLICT ({}) DO {action};",
+            meta.insert_sql(OnConflict::Abort).trim_end_matches(';'),
+            conflict_cols.join(", ")
+        );
+
+        with_savepoint(conn, "exemplar_upsert_all", || {
+            let mut stmt = conn.prepare(&sql)?;
+
+            items
+                .iter()
+                .try_for_each(|item| item.insert_with(&mut stmt))
+        })
+    }
+
+    /// Reconcile the model table with `desired`, keyed on the model's [primary key](crate::macros::Model#primary_key).
+    /// 
+    /// Within a new transaction:
+    /// - Items whose key isn't in the table are inserted (with [`Abort`](OnConflict::Abort) conflict resolution.)
+    /// - Items whose key is in the table are compared against the stored row, and update it if any column differs.
+    /// - Rows whose key isn't among `desired` are deleted.
+    /// 
+    /// A [`SyncReport`] with the number of rows affected by each step is returned. If any step fails, the transaction is rolled back,
+    /// leaving the table untouched. As with [`Model::insert_all_atomic`], this takes a `&mut` [`Connection`] as transactions can't be nested.
+    /// 
+    /// Rows are compared on the SQL side of any `#[bind]` functions, exactly like [`Model::matches_row`] - so a column whose
+    /// stored representation differs from the bound one (for example, due to [type affinity](https://www.sqlite.org/datatype3.html#type_affinity))
+    /// is always considered changed. Such updates are harmless, but are counted.
+    /// 
+    /// Only available for models with a [primary key](crate::macros::Model#primary_key) - calling it on one without fails to compile.
+    /// 
+    /// # Errors
+    /// If `desired` contains several items with the same key, the insertion of the second fails with a constraint violation.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     #[primary_key]
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let mut conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name PRIMARY KEY, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+    /// # ")?;
+    /// let desired = [
+    ///     Person { name: "Alice".to_owned(), age: 22 },
+    ///     Person { name: "Carol".to_owned(), age: 45 },
+    /// ];
+    /// 
+    /// let report = Person::sync_with(&mut conn, &desired)?;
+    /// 
+    /// assert_eq!(report, SyncReport { inserted: 1, updated: 1, deleted: 1 });
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn sync_with(conn: &mut Connection, desired: &[Self]) -> Result<SyncReport>
+    where
+        Self: Sized + PrimaryKey
+    {
+        let meta = Self::metadata();
+
+        let key_indices: Vec<_> = meta
+            .primary_key
+            .iter()
+            .filter_map(|key| meta.columns.iter().position(|column| column == key))
+            .collect();
+
+        let key_of = |values: &[Value]| -> Vec<String> {
+            key_indices
+                .iter()
+                .map(|&idx| sql_literal(&values[idx]))
+                .collect()
+        };
+
+        let tx = conn.transaction()?;
+        let mut report = SyncReport::default();
+
+        let mut existing: HashMap<_, _> = {
+            let sql = format!(
+                "SELECT {} FROM {};",
+                meta.columns.join(", "),
+                meta.table
+            );
+
+            let mut stmt = tx.prepare(&sql)?;
+
+            let rows = stmt.query_and_then([], |row| {
+                (0..meta.columns.len())
+                    .map(|idx| row.get::<_, Value>(idx))
+                    .collect::<Result<Vec<_>>>()
+            })?;
+
+            rows
+                .map(|values| values.map(|values| (key_of(&values), values)))
+                .collect::<Result<_>>()?
+        };
+
+        let unkeyed: Vec<_> = meta
+            .columns
+            .iter()
+            .copied()
+            .filter(|column| !meta.primary_key.contains(column))
+            .collect();
+
+        for item in desired {
+            let values: Vec<_> = item
+                .to_owned_params()?
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect();
+
+            match existing.remove(&key_of(&values)) {
+                None => {
+                    item.insert_or(&tx, OnConflict::Abort)?;
+                    report.inserted += 1;
+                },
+                Some(stored) if stored != values => {
+                    item.update_columns(&tx, &unkeyed)?;
+                    report.updated += 1;
+                },
+                Some(_) => {}
+            }
+        }
+
+        if !existing.is_empty() {
+            let sql = format!(
+                "DELETE FROM {} WHERE {};",
+                meta.table,
+                primary_key_predicate(&meta)
+            );
+
+            let mut stmt = tx.prepare(&sql)?;
+
+            for stored in existing.into_values() {
+                for (key, &idx) in meta.primary_key.iter().zip(&key_indices) {
+                    let index = stmt
+                        .parameter_index(&format!(":{key}"))?
+                        .ok_or_else(|| rusqlite::Error::InvalidParameterName(key.to_string()))?;
+
+                    stmt.raw_bind_parameter(index, &stored[idx])?;
+                }
+
+                report.deleted += stmt.raw_execute()?;
+            }
+        }
+
+        tx.commit()?;
+
+        Ok(report)
+    }
+
+    /// Attempt to delete every row in the model table matching the provided predicate, returning the number of rows deleted.
+    /// 
+    /// The generated statement is `DELETE FROM <table> WHERE <predicate>`, with the provided parameters bound to any placeholders in the predicate.
+    /// 
+    /// # Security
+    /// The predicate is interpolated directly into the statement and is therefore *trusted SQL*. Never build it from user input - 
+    /// use placeholders and `params` instead.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+    /// # ")?;
+    /// let deleted = Person::delete_where(&conn, "age > ?", [65])?;
+    /// 
+    /// assert_eq!(deleted, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn delete_where(conn: &Connection, predicate: &str, params: impl Params) -> Result<usize>
+    where
+        Self: Sized
+    {
+        let sql = format!(
+            "DELETE FROM {} WHERE {};",
+            Self::metadata().table,
+            predicate
+        );
+
+        conn.execute(&sql, params)
+    }
+
+    /// Check whether any row in the model table matches the provided predicate.
+    /// 
+    /// The generated query is `SELECT EXISTS(SELECT 1 FROM <table> WHERE <predicate>)`, with the provided parameters bound to any placeholders in the predicate.
+    /// This is cheaper than fetching rows when you only need a boolean, as SQLite stops at the first match and no columns are read.
+    /// 
+    /// # Security
+    /// The predicate is interpolated directly into the statement and is therefore *trusted SQL*. Never build it from user input - 
+    /// use placeholders and `params` instead.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (name, age);
+    /// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+    /// # ")?;
+    /// assert!(Person::exists_where(&conn, "age > ?", [65])?);
+    /// assert!(!Person::exists_where(&conn, "age > ?", [100])?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use = "this returns whether a row exists, and has no other effect"]
+    fn exists_where(conn: &Connection, predicate: &str, params: impl Params) -> Result<bool>
+    where
+        Self: Sized
+    {
+        let sql = format!(
+            "SELECT EXISTS(SELECT 1 FROM {} WHERE {});",
+            Self::metadata().table,
+            predicate
+        );
+
+        query_scalar(conn, &sql, params)
+    }
+
+    /// Attempt to update only the named columns of the row matching `self`'s [primary key](crate::macros::Model#primary_key), returning the number of rows changed.
+    /// 
+    /// The generated statement is `UPDATE <table> SET <col> = :<col>, ... WHERE <pk> = :<pk> AND ...`, bound with the current values of `self`
+    /// (applying any `#[bind]` functions.) Columns that aren't named are left unchanged. This covers "save just these fields" without writing the whole row.
+    /// 
+    /// If `cols` is empty, nothing is executed and `0` is returned. Like [`Model::update`], this is only available for models with a primary key.
+    /// 
+    /// # Errors
+    /// Each name in `cols` is checked against [`ModelMeta::columns`]; unknown names return [`InvalidColumnName`](rusqlite::Error::InvalidColumnName). 
+    /// (So it's safe to take column names from untrusted input.)
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     #[primary_key]
+    ///     pub id: i64,
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (id PRIMARY KEY, name, age);
+    /// #     INSERT INTO people VALUES (1, 'Alice', 21);
+    /// # ")?;
+    /// let alice = Person { id: 1, name: "Alice".to_owned(), age: 22 };
+    /// 
+    /// assert_eq!(alice.update_columns(&conn, &["age"])?, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn update_columns(&self, conn: &Connection, cols: &[&str]) -> Result<usize>
+    where
+        Self: Sized + PrimaryKey
+    {
+        let meta = Self::metadata();
+
+        if let Some(unknown) = cols.iter().find(|col| !meta.columns.contains(col)) {
+            return Err(rusqlite::Error::InvalidColumnName(unknown.to_string()))
+        }
+
+        if cols.is_empty() {
+            return Ok(0)
+        }
+
+        let assignments: Vec<_> = cols
+            .iter()
+            .map(|col| format!("{col} = :{col}"))
+            .collect();
+
+        let sql = format!(
+            "UPDATE {} SET {} WHERE {};",
+            meta.table,
+            assignments.join(", "),
+            primary_key_predicate(&meta)
+        );
+
+        let mut stmt = conn.prepare_cached(&sql)?;
+
+        self.bind_to(&mut stmt)?;
+        stmt.raw_execute()
+    }
+
+    /// Attempt to update every column of the row matching `self`'s [primary key](crate::macros::Model#primary_key).
+    /// 
+    /// The generated statement is `UPDATE <table> SET <col> = :<col>, ... WHERE <pk> = :<pk> AND ...`, covering every column that isn't part of the key,
+    /// bound with the current values of `self` (applying any `#[bind]` functions.) If no row matches, nothing is changed.
+    /// 
+    /// This is [`Model::update_columns`] with every non-key column, except that it's only available for models with a primary key -
+    /// calling it on one without fails to compile, rather than at runtime.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Debug, PartialEq, Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     #[primary_key]
+    ///     pub id: i64,
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (id PRIMARY KEY, name, age);
+    /// #     INSERT INTO people VALUES (1, 'Alice', 21);
+    /// # ")?;
+    /// let alicia = Person { id: 1, name: "Alicia".to_owned(), age: 22 };
+    /// 
+    /// alicia.update(&conn)?;
+    /// 
+    /// assert_eq!(Person::select_all(&conn)?, vec![alicia]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// ```compile_fail
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> rusqlite::Result<()> {
+    /// # let conn = rusqlite::Connection::open_in_memory()?;
+    /// let alice = Person { name: "Alice".to_owned(), age: 21 };
+    /// 
+    /// alice.update(&conn)?; // error: the model `Person` has no #[primary_key]
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the update SQL statement.
+    fn update(&self, conn: &Connection) -> Result<()>
+    where
+        Self: Sized + PrimaryKey
+    {
+        let meta = Self::metadata();
+
+        let cols: Vec<_> = meta
+            .columns
+            .iter()
+            .copied()
+            .filter(|col| !meta.primary_key.contains(col))
+            .collect();
+
+        self.update_columns(conn, &cols)?;
+
+        Ok(())
+    }
+    
+    /// Attempt to delete the row matching `self`'s [primary key](crate::macros::Model#primary_key).
+    /// 
+    /// The generated statement is `DELETE FROM <table> WHERE <pk> = :<pk> AND ...`, with only the key columns bound (applying any `#[bind]` functions.)
+    /// Composite keys match on every key column. If no row matches, nothing is deleted.
+    /// 
+    /// Like [`Model::update`], this is only available for models with a primary key - deleting by matching every column is rarely what you want,
+    /// so calling it on a model without one fails to compile. Use [`Model::delete_where`] for arbitrary predicates.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     #[primary_key]
+    ///     pub id: i64,
+    ///     pub name: String,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("
+    /// #     CREATE TABLE people (id PRIMARY KEY, name);
+    /// #     INSERT INTO people VALUES (1, 'Alice'), (2, 'Bob');
+    /// # ")?;
+    /// // Only the key matters - the name doesn't have to match.
+    /// Person { id: 1, name: "Alicia".to_owned() }.delete(&conn)?;
+    /// 
+    /// assert_eq!(Person::select_all(&conn)?.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Performance
+    /// This method uses [`prepare_cached`](rusqlite::Connection::prepare_cached) to create the deletion SQL statement.
+    fn delete(&self, conn: &Connection) -> Result<()>
+    where
+        Self: Sized + PrimaryKey
+    {
+        let meta = Self::metadata();
+
+        let sql = format!(
+            "DELETE FROM {} WHERE {};",
+            meta.table,
+            primary_key_predicate(&meta)
+        );
+
+        let params = self.to_key_params()?;
+
+        let params: Vec<_> = params
+            .iter()
+            .map(|(name, param)| (*name, param as &dyn ToSql))
+            .collect();
+
+        conn.prepare_cached(&sql)?.execute(params.as_slice())?;
+
+        Ok(())
+    }
+
+    /// Generate a slice of named [`Parameters`] from an instance of the implementing type.
+    ///  
+    /// # Performance
+    /// This method allocates at least once, in order to [`Box`] the returned slice.
+    /// 
+    /// If the implementing type has any fields annotated with `#[bind]`, an additional boxing will be incurred for each annotated field.
+    #[must_use = "this returns the bound parameters, and has no other effect"]
+    fn to_params(&self) -> Result<Parameters<'_>>;
+
+    /// Bind the fields of `self` to the matching named parameters of the provided [`Statement`], without executing it.
+    /// 
+    /// Fields whose parameter (e.g. `:name`) doesn't appear in the statement are skipped, which makes this suitable for 
+    /// hand-written statements that only use some of a model's columns. Once bound, run the statement with
+    /// [`raw_execute`](rusqlite::Statement::raw_execute) or [`raw_query`](rusqlite::Statement::raw_query).
+    /// 
+    /// If you'd rather catch mismatches between the statement and the model, use [`Model::bind_to_strict`].
+    fn bind_to(&self, stmt: &mut Statement) -> Result<()> {
+        for (name, param) in self.to_params()?.iter() {
+            if let Some(index) = stmt.parameter_index(name)? {
+                stmt.raw_bind_parameter(index, param)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Strict version of [`Model::bind_to`].
+    /// 
+    /// Every field of `self` must have a matching named parameter in the provided [`Statement`]; otherwise,
+    /// nothing is bound and [`InvalidParameterName`](rusqlite::Error::InvalidParameterName) is returned with the first unused parameter name.
+    /// 
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> Result<()> {
+    /// # let conn = Connection::open_in_memory()?;
+    /// # conn.execute_batch("CREATE TABLE people (name, age);")?;
+    /// let alice = Person { name: "Alice".to_owned(), age: 21 };
+    /// 
+    /// // Oops - forgot about the age!
+    /// let mut stmt = conn.prepare("INSERT INTO people (name) VALUES(:name);")?;
+    /// 
+    /// assert!(alice.bind_to_strict(&mut stmt).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn bind_to_strict(&self, stmt: &mut Statement) -> Result<()> {
+        let params = self.to_params()?;
+
+        let indices = params
+            .iter()
+            .map(|(name, _)| {
+                stmt.parameter_index(name)?.ok_or_else(|| {
+                    rusqlite::Error::InvalidParameterName(name.to_string())
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for (index, (_, param)) in indices.into_iter().zip(params.iter()) {
+            stmt.raw_bind_parameter(index, param)?;
+        }
+
+        Ok(())
+    }
+
+    /// Generate a vector of `(column, value)` pairs from an instance of the implementing type, in column order.
+    /// 
+    /// Unlike [`Model::to_params`], the values are fully owned and the column names are not prefixed with a `:` - this makes the output
+    /// suitable for generic serialization, debugging, or comparison purposes.
+    /// 
+    /// # Errors
+    /// Fails if any field's [`ToSql`] implementation (or `#[bind]` function) fails, or if it produces an output
+    /// variant that can't be converted into an owned [`Value`] (such as a `ZeroBlob`.)
+    #[must_use = "this returns the bound parameters, and has no other effect"]
+    fn to_owned_params(&self) -> Result<Vec<(&'static str, Value)>> {
+        let params = self.to_params()?;
+        let columns = self.metadata_dyn().columns;
+
+        columns
+            .iter()
+            .zip(params.iter())
+            .map(|(column, (_, param))| {
+                Ok((*column, to_owned_value(param.to_sql()?)?))
+            })
+            .collect()
+    }
+
+    /// Generate a vector of owned, boxed parameters from an instance of the implementing type, in column order.
+    /// 
+    /// This is the positional counterpart to [`Model::to_params`], for binding a model to hand-written statements that use `?` placeholders.
+    /// The column names (in the same order) are available from [`ModelMeta::columns`]:
+    /// 
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("people")]
+    /// pub struct Person {
+    ///     pub name: String,
+    ///     pub age: u16,
+    /// }
+    /// 
+    /// # fn main() -> rusqlite::Result<()> {
+    /// # let conn = rusqlite::Connection::open_in_memory()?;
+    /// # conn.execute_batch("CREATE TABLE people (name, age);")?;
+    /// let alice = Person { name: "Alice".to_owned(), age: 21 };
+    /// 
+    /// let sql = format!(
+    ///     "INSERT INTO people ({}) VALUES (?, ?)",
+    ///     Person::metadata().columns.join(", ")
+    /// );
+    /// 
+    /// conn.execute(&sql, rusqlite::params_from_iter(alice.to_positional_params()?))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// # Errors
+    /// Fails under the same conditions as [`Model::to_owned_params`].
+    /// 
+    /// # Performance
+    /// Every value is copied into an owned [`Value`] and boxed. If you can use named parameters, prefer [`Model::to_params`] or [`Model::bind_to`].
+    #[must_use = "this returns the bound parameters, and has no other effect"]
+    fn to_positional_params(&self) -> Result<Vec<Box<dyn ToSql>>> {
+        let params = self
+            .to_owned_params()?
+            .into_iter()
+            .map(|(_, value)| Box::new(value) as Box<dyn ToSql>)
+            .collect();
+
+        Ok(params)
+    }
+
+    /// Iterate over the `(column, value)` pairs of an instance of the implementing type, in column order.
+    /// 
+    /// This is a convenience wrapper around [`Model::to_owned_params`], and is useful for writing generic dumpers:
+    /// 
+    /// ```rust
+    /// # use exemplar::*;
+    /// # use rusqlite::types::Value;
+    /// # use std::collections::HashMap;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo {
+    ///     pub bar: String,
+    ///     #[column("baz")]
+    ///     pub qux: i64,
+    /// }
+    /// 
+    /// # fn main() -> rusqlite::Result<()> {
+    /// let foo = Foo { bar: "my_bar".to_owned(), qux: 42 };
+    /// 
+    /// let map: HashMap<_, _> = foo.iter_columns()?.collect();
+    /// 
+    /// assert_eq!(map["bar"], Value::Text("my_bar".to_owned()));
+    /// assert_eq!(map["baz"], Value::Integer(42));
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// Because binding a field can fail, the iterator is wrapped in a [`Result`]. On a trait object, use [`Model::to_owned_params`] instead.
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    fn iter_columns(&self) -> Result<impl Iterator<Item = (&'static str, Value)>>
+    where
+        Self: Sized
+    {
+        self
+            .to_owned_params()
+            .map(Vec::into_iter)
+    }
+
+    /// Check whether the provided [`Row`] holds the same column values as `self`, without materializing a new instance.
+    /// 
+    /// Each column's stored [`Value`] is compared against the SQL representation of the corresponding field,
+    /// as produced by [`Model::to_owned_params`]. This makes it handy for asserting on database state in (property) tests:
+    /// 
+    /// ```ignore
+    /// let mut rows = stmt.query([])?;
+    /// let row = rows.next()?.unwrap();
+    /// 
+    /// assert!(alice.matches_row(row)?);
+    /// ```
+    /// 
+    /// Note that comparison happens on the SQL side of any `#[bind]` functions, and follows the semantics of [`Value`]'s
+    /// [`PartialEq`] implementation - so `REAL` columns holding `NaN` never match.
+    /// 
+    /// # Errors
+    /// Fails if `self` can't be bound (see [`Model::to_owned_params`]), or if any of the model's columns are missing from the row.
+    #[must_use = "this returns the result of the comparison, and has no other effect"]
+    fn matches_row(&self, row: &Row) -> Result<bool> {
+        for (column, value) in self.to_owned_params()? {
+            if row.get::<_, Value>(column)? != value {
+                return Ok(false)
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Render `self` as a self-contained `INSERT` statement, with every value inlined as an SQL literal.
+    /// 
+    /// This is intended for dumping live data into seed or fixture scripts - the output can be run as-is with
+    /// [`Connection::execute_batch`] or the `sqlite3` shell:
+    /// 
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("users")]
+    /// pub struct User {
+    ///     pub name: String,
+    ///     pub avatar: Option<Vec<u8>>,
+    /// }
+    /// 
+    /// # fn main() -> rusqlite::Result<()> {
+    /// let user = User { name: "O'Brien".to_owned(), avatar: Some(vec![0xCA, 0xFE]) };
+    /// 
+    /// assert_eq!(
+    ///     user.to_insert_statement()?,
+    ///     "INSERT INTO users (name, avatar) VALUES ('O''Brien', x'cafe');"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// 
+    /// Text is single-quoted with embedded quotes doubled, blobs are written as hex literals (`x'...'`) and `NULL`s as `NULL`.
+    /// Reals are written with enough precision to round-trip; as SQLite has no literal for them, infinities are written as
+    /// out-of-range numbers (`9e999`) and `NaN` as `NULL` (which is what SQLite would store anyway.)
+    /// 
+    /// # Errors
+    /// Fails if `self` can't be bound (see [`Model::to_owned_params`].)
+    #[must_use = "this returns the rendered statement, and has no other effect"]
+    fn to_insert_statement(&self) -> Result<String> {
+        let params = self.to_owned_params()?;
+
+        let columns = params
+            .iter()
+            .map(|(column, _)| *column)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let values = params
+            .iter()
+            .map(|(_, value)| sql_literal(value))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Ok(format!(
+            "INSERT INTO {} ({columns}) VALUES ({values});",
+            self.metadata_dyn().table
+        ))
+    }
+
+    /// Static dispatch version of [`Model::metadata_dyn`].
+    /// 
+    /// Like the other methods here that only produce a value, this is `#[must_use]` - discarding the result is almost certainly a bug:
+    /// 
+    /// ```compile_fail
+    /// # #![deny(unused_must_use)]
+    /// # use exemplar::Model;
+    /// # #[derive(Model)]
+    /// # #[table("people")]
+    /// # struct Person { name: String }
+    /// Person::metadata(); // error: unused return value of `exemplar::Model::metadata` that must be used
+    /// ```
+    #[must_use]
+    fn metadata() -> ModelMeta
+    where
+        Self: Sized;
+
+    /// Retrieve the name of the table the implementing type maps to.
+    /// 
+    /// By default this is the name given to [`#[table]`](crate::macros::Model#table); models can
+    /// defer to a function instead using [`#[resolve_table]`](crate::macros::Model#resolve_table).
+    /// 
+    /// Generated SQL is built on first use and then cached, so the resolved name should not change over the life of the program.
+    #[must_use]
+    fn resolve_table() -> &'static str
+    where
+        Self: Sized;
+
+    /// Retrieve [`ModelMeta`] (model metadata) associated with the implementing type.
+    /// 
+    /// This is the dynamic dispatch version of [`Model::metadata`].
+    /// If (for whatever reason) you find yourself needing to dynamically reflect on [`Model`] properties, then this is for you.
+    /// 
+    /// # Performance
+    /// Despite the name of this method, [`ModelMeta`] consists solely of `'static` data generated at compile time, making it trivially copyable.
+    /// 
+    /// The only overhead on this call is therefore dynamic dispatch and several shallow copies.
+    #[must_use]
+    fn metadata_dyn(&self) -> ModelMeta;
+}
+
+/// Trait for [`Model`]s with a [`#[primary_key]`](crate::macros::Model#primary_key), implemented by the derive macro.
+/// 
+/// Methods that identify rows by their primary key (like [`Model::update`] and [`Model::delete`]) require it, so calling them on a model without one
+/// fails to compile.
+#[diagnostic::on_unimplemented(
+    message = "the model `{Self}` has no #[primary_key]",
+    label = "this model has no primary key",
+    note = "mark the field(s) identifying a row with #[primary_key]"
+)]
+pub trait PrimaryKey: Model {
+    /// Generate a slice of named [`Parameters`] for just the primary key column(s) of `self`, in the same form as [`Model::to_params`].
+    /// 
+    /// Only the key fields are bound (applying any `#[bind]` functions), so this is cheaper than [`Model::to_params`] for statements
+    /// that identify a row, like the one run by [`Model::delete`].
+    #[must_use = "this returns the bound parameters, and has no other effect"]
+    fn to_key_params(&self) -> Result<Parameters<'_>>;
+}
+
+/// Possible conflict resolution strategies when using [`Model::insert_or`].
+/// 
+/// The default setting (used by [`Model::insert`]) is [`Abort`](OnConflict::Abort). This can be overridden per-model with the
+/// [`#[on_conflict]`](crate::macros::Model#on_conflict) attribute.
+/// 
+/// Sourced from the [SQLite docs](https://www.sqlite.org/lang_conflict.html).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnConflict {
+    /// When an applicable constraint violation occurs, error and revert any changes made by the current SQL statement.
+    /// Prior SQL statements in the same transaction are unaffected, and the transaction remains active.
+    /// 
+    /// This is the default behavior.
+    #[default]
+    Abort,
+    /// When an applicable constraint violation occurs, error but do *not* revert any changes made by the current SQL statement.
+    /// Prior SQL statements in the same transaction are unaffected, and the transaction remains active.
+    Fail,
+    /// When an applicable constraint violation occurs, skip the row that contains the constraint violation and continue processing
+    /// the current SQL statement. Other rows, prior statements and the current transaction are unaffected.
+    /// 
+    /// When encountering a foreign key constraint error, this behaves like [`Abort`](Self::Abort).
+    Ignore,
+    /// - When a uniqueness or primary key constraint violation occurs, delete the offending rows and continue.
+    /// - If a `NOT NULL` constraint violation occurs, the offending column will either be replaced with the default value (if one exists) or [`Abort`](Self::Abort) behavior will be used.
+    /// - If a `CHECK` or foreign key constraint violation occurs, [`Abort`](Self::Abort) behavior will be used.
+    Replace,
+    /// When an applicable constraint violation occurs, abort the current SQL statement and roll back the current transaction.
+    /// If no transaction is active, [`Abort`](Self::Abort) behavior will be used.
+    Rollback,
+}
+
+impl OnConflict {
+    /// Get the SQL keyword for this strategy, as used in an `INSERT OR <keyword>` statement.
+    /// 
+    /// Returns [`None`] for [`Abort`](Self::Abort), as it is the default and needs no clause.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::OnConflict;
+    /// let or = OnConflict::Replace
+    ///     .as_sql_keyword()
+    ///     .map(|keyword| format!(" OR {keyword}"))
+    ///     .unwrap_or_default();
+    /// 
+    /// assert_eq!(format!("INSERT{or} INTO people ..."), "INSERT OR REPLACE INTO people ...");
+    /// ```
+    #[must_use]
+    pub const fn as_sql_keyword(&self) -> Option<&'static str> {
+        match self {
+            Self::Abort => None,
+            Self::Fail => Some("FAIL"),
+            Self::Ignore => Some("IGNORE"),
+            Self::Replace => Some("REPLACE"),
+            Self::Rollback => Some("ROLLBACK"),
+        }
+    }
+}
+
+impl FromStr for OnConflict {
+    type Err = FromSqlError;
+
+    /// Parse a strategy from its SQL keyword (`"ABORT"`, `"FAIL"`, `"IGNORE"`, `"REPLACE"` or `"ROLLBACK"`), ignoring case.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        [Self::Abort, Self::Fail, Self::Ignore, Self::Replace, Self::Rollback]
+            .into_iter()
+            .find(|strategy| {
+                strategy
+                    .as_sql_keyword()
+                    .unwrap_or("ABORT")
+                    .eq_ignore_ascii_case(value)
+            })
+            .ok_or_else(|| {
+                FromSqlError::Other(
+                    format!("No conflict resolution strategy matches the value `{value}`").into()
+                )
+            })
+    }
+}
+
+/// [`Cow`]-like type for query parameters.
+/// 
+/// Necessary to efficiently implement [`Model::to_params`] - while most fields can be directly referenced as
+/// a [`dyn ToSql`](ToSql), those with `#[bind]` and `#[extr]` parameters require a non-trivial conversion step.
+/// 
+/// [`Self::Borrowed`] is used for the former case, while [`Self::Boxed`] is used for the latter case. 
+/// 
+/// This type implements [`ToSql`] and dereferences to `dyn ToSql`, so it can be used just like any other query parameter.
+pub enum Parameter<'a> {
+    /// A borrowed [`ToSql`] trait object.
+    Borrowed(&'a dyn ToSql),
+    /// A boxed [`ToSql`] trait object.
+    Boxed(Box<dyn ToSql + 'a>)
+}
+
+impl<'a> ToSql for Parameter<'a> {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        match self {
+            Self::Borrowed(param) => param.to_sql(),
+            Self::Boxed(param) => param.to_sql(),
+        }
+    }
+}
+
+impl<'a> Deref for Parameter<'a> {
+    type Target = dyn ToSql + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(param) => *param,
+            Self::Boxed(param) => param,
+        }
+    }
+}
+
+/// The outcome of [`Model::sync_with`] - the number of rows affected by each step of the reconciliation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SyncReport {
+    /// The number of rows inserted, as their key wasn't in the table.
+    pub inserted: usize,
+    /// The number of existing rows updated, as at least one of their columns differed.
+    pub updated: usize,
+    /// The number of rows deleted, as their key wasn't among the desired items.
+    pub deleted: usize,
+}
+
+/// The error returned by [`Model::export_csv`], which can fail on either side of the export.
+#[derive(Debug)]
+pub enum ExportError {
+    /// Querying the model's table failed.
+    Sql(rusqlite::Error),
+    /// Writing to the output failed.
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sql(err) => write!(f, "failed to query rows for export: {err}"),
+            Self::Io(err) => write!(f, "failed to write exported rows: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Sql(err) => Some(err),
+            Self::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for ExportError {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::Sql(err)
+    }
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// The indices of a [`Model`]'s columns in the output of a particular statement, as resolved by [`Model::column_indices`].
+/// 
+/// Used with [`Model::from_row_cached`] to skip resolving columns by name for every row.
+pub struct ColumnMap<M> {
+    indices: Box<[usize]>,
+    _model: PhantomData<fn() -> M>,
+}
+
+impl<M> ColumnMap<M> {
+    /// Implementation detail of [`Model::column_indices`].
+    #[doc(hidden)]
+    pub fn __new(indices: Box<[usize]>) -> Self {
+        Self {
+            indices,
+            _model: PhantomData
+        }
+    }
+
+    /// Get the resolved index of each column, in the same order as [`ModelMeta::columns`] (followed by any [computed](ModelMeta::computed) fields.)
+    #[must_use]
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+}
+
+// Implemented by hand, as the derives would needlessly require `M` to implement these traits too.
+
+impl<M> std::fmt::Debug for ColumnMap<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColumnMap")
+            .field("indices", &self.indices)
+            .finish()
+    }
+}
+
+impl<M> Clone for ColumnMap<M> {
+    fn clone(&self) -> Self {
+        Self::__new(self.indices.clone())
+    }
+}
+
+impl<M> PartialEq for ColumnMap<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.indices == other.indices
+    }
+}
+
+impl<M> Eq for ColumnMap<M> {}
+
+/// An iterator over the rows of a query, extracting each as a [`Model`] of type `M`.
+/// 
+/// This is functionally equivalent to calling [`query_and_then`](https://docs.rs/rusqlite/latest/rusqlite/struct.Statement.html#method.query_and_then)
+/// with [`Model::from_row`], but yields a concrete, nameable type that can be stored in structs or returned from functions.
+/// 
+/// # Example
+/// ```rust
+/// # use exemplar::*;
+/// # use rusqlite::*;
+/// #[derive(Model)]
+/// #[table("people")]
+/// pub struct Person {
+///     pub name: String,
+///     pub age: u16,
+/// }
+/// 
+/// # fn main() -> Result<()> {
+/// let conn = Connection::open_in_memory()?;
+/// 
+/// conn.execute_batch("
+///     CREATE TABLE people (name, age);
+///     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+/// ")?;
+/// 
+/// let mut stmt = conn.prepare("SELECT * FROM people WHERE age > ?")?;
+/// 
+/// for person in ModelRows::<Person>::new(&mut stmt, [30])? {
+///     assert_eq!(person?.name, "Bob");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ModelRows<'stmt, M> {
+    rows: Rows<'stmt>,
+    _model: PhantomData<fn() -> M>,
+}
+
+impl<'stmt, M> ModelRows<'stmt, M>
+where
+    M: Model
+{
+    /// Execute the provided statement with the provided parameters, returning an iterator over the resulting models.
+    pub fn new(stmt: &'stmt mut Statement<'_>, params: impl Params) -> Result<Self> {
+        let rows = stmt.query(params)?;
+
+        Ok(Self::from(rows))
+    }
+}
+
+impl<'stmt, M> From<Rows<'stmt>> for ModelRows<'stmt, M>
+where
+    M: Model
+{
+    fn from(rows: Rows<'stmt>) -> Self {
+        Self {
+            rows,
+            _model: PhantomData
+        }
+    }
+}
+
+impl<M> Iterator for ModelRows<'_, M>
+where
+    M: Model
+{
+    type Item = Result<M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.rows.next() {
+            Ok(Some(row)) => Some(M::from_row(row)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Execute a query that returns a single scalar value (such as `SELECT count(*) FROM ...`), returning it as a `T`.
+/// 
+/// This is shorthand for [`query_row`](rusqlite::Connection::query_row) with a closure that extracts the first column.
+/// Additional columns and rows are ignored; if there are no rows, [`QueryReturnedNoRows`](rusqlite::Error::QueryReturnedNoRows) is returned.
+/// 
+/// The statement is prepared with [`prepare_cached`](rusqlite::Connection::prepare_cached), so repeated calls with the same SQL are cheap.
+/// 
+/// # Example
+/// ```rust
+/// # use rusqlite::Connection;
+/// # fn main() -> rusqlite::Result<()> {
+/// # let conn = Connection::open_in_memory()?;
+/// # conn.execute_batch("
+/// #     CREATE TABLE people (name, age);
+/// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+/// # ")?;
+/// let adults: i64 = exemplar::query_scalar(&conn, "SELECT count(*) FROM people WHERE age >= ?", [18])?;
+/// 
+/// assert_eq!(adults, 2);
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "this returns the queried value"]
+pub fn query_scalar<T>(conn: &Connection, sql: &str, params: impl Params) -> Result<T>
+where
+    T: FromSql
+{
+    conn
+        .prepare_cached(sql)?
+        .query_row(params, |row| row.get(0))
+}
+
+/// Implementation detail of [`create_tables`].
+#[doc(hidden)]
+pub fn __create_tables(conn: &Connection, models: &[ModelMeta]) -> Result<()> {
+    with_savepoint(conn, "exemplar_create_tables", || {
+        models
+            .iter()
+            .try_for_each(|meta| {
+                conn.execute_batch(
+                    &meta.create_table_sql_untyped()
+                )
+            })
+    })
+}
+
+/// Implementation detail of `#[check(..., without_rowid)]`.
+/// 
+/// Panics unless `table` was declared `WITHOUT ROWID`, as reported by the `wr` column of [`pragma_table_list`](https://www.sqlite.org/pragma.html#pragma_table_list).
+#[doc(hidden)]
+pub fn __check_without_rowid(conn: &Connection, table: &str) {
+    let without_rowid: bool = conn
+        .query_row(
+            "SELECT wr FROM pragma_table_list WHERE type = 'table' AND name = ?",
+            [table],
+            |row| row.get(0)
+        )
+        .expect("Failed to query table options from pragma_table_list.");
+
+    assert!(without_rowid, "The model expects a WITHOUT ROWID table, but the schema's table ({table}) has a rowid.");
+}
+
+/// Implementation detail of `#[check]` for models with `#[sql_check]` constraints.
+/// 
+/// Panics unless the schema's SQL for `table` contains a `CHECK` constraint with the expression `expr`. Expressions are compared textually,
+/// ignoring whitespace and case.
+#[doc(hidden)]
+pub fn __check_constraint(conn: &Connection, table: &str, expr: &str) {
+    let normalize = |sql: &str| -> String {
+        sql
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase()
+    };
+
+    let sql: String = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?",
+            [table],
+            |row| row.get(0)
+        )
+        .expect("Failed to query table SQL from sqlite_master.");
+
+    let constraint = format!("CHECK({})", normalize(expr));
+
+    assert!(
+        normalize(&sql).contains(&constraint),
+        "The model declares a check constraint ({expr}) that the schema's table ({table}) lacks."
+    );
+}
+
+/// Implementation detail of `#[check(migrations = "...")]`.
+/// 
+/// Reads the `.sql` files in `dir` - relative to `file`, the source file containing the derive - in order of file name.
+/// `file` comes from `file!()`, which may be relative to the workspace root rather than the crate, so it's resolved against the first
+/// ancestor of `manifest_dir` it exists under.
+/// 
+/// Panics if the directory can't be read, or contains no `.sql` files.
+#[doc(hidden)]
+pub fn __read_migrations(file: &str, manifest_dir: &str, dir: &str) -> Vec<String> {
+    let file = std::path::Path::new(file);
+
+    let source = match file.is_absolute() {
+        true => file.to_path_buf(),
+        false => std::path::Path::new(manifest_dir)
+            .ancestors()
+            .map(|ancestor| ancestor.join(file))
+            .find(|path| path.is_file())
+            .unwrap_or_else(|| panic!("Failed to locate the source file ({}) declaring the migrations directory.", file.display())),
+    };
+
+    let resolved = source
+        .parent()
+        .unwrap_or(std::path::Path::new(""))
+        .join(dir);
+
+    let mut paths: Vec<_> = std::fs::read_dir(&resolved)
+        .unwrap_or_else(|err| panic!("Failed to read the migrations directory ({}): {err}", resolved.display()))
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+
+    assert!(!paths.is_empty(), "The migrations directory ({}) contains no .sql files.", resolved.display());
+
+    paths.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+
+    paths
+        .iter()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .unwrap_or_else(|err| panic!("Failed to read the migration {}: {err}", path.display()))
+        })
+        .collect()
+}
+
+/// Implementation detail of `#[check(..., index = "...")]`.
+/// 
+/// Panics unless the schema declares an index with the provided name on `table`.
+#[doc(hidden)]
+pub fn __check_index(conn: &Connection, table: &str, index: &str) {
+    let exists: bool = conn
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'index' AND name = ? AND tbl_name = ?)",
+            [index, table],
+            |row| row.get(0)
+        )
+        .expect("Failed to query indexes from sqlite_master.");
+
+    assert!(exists, "The model expects an index ({index}) on the schema's table ({table}), but it doesn't exist.");
+}
+
+/// Run `f` within a savepoint with the provided name, rolling back everything it did if it fails.
+fn with_savepoint<T>(conn: &Connection, name: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    conn.execute_batch(&format!("SAVEPOINT {name};"))?;
+
+    match f() {
+        Ok(value) => {
+            conn.execute_batch(&format!("RELEASE {name};"))?;
+            Ok(value)
+        },
+        Err(err) => {
+            conn.execute_batch(&format!("ROLLBACK TO {name}; RELEASE {name};"))?;
+            Err(err)
+        }
+    }
+}
+
+/// Implementation detail of [`Model::insert_sparse`].
+#[doc(hidden)]
+pub fn __insert_sparse(conn: &Connection, model: &dyn Model, strategy: OnConflict, present: &[bool]) -> Result<()> {
+    let meta = model.metadata_dyn();
+    let params = model.to_params()?;
+
+    let (columns, params): (Vec<_>, Vec<_>) = meta
+        .columns
+        .iter()
+        .zip(params.iter())
+        .zip(present)
+        .filter(|(_, present)| **present)
+        .map(|((column, (name, param)), _)| (*column, (*name, param as &dyn ToSql)))
+        .unzip();
+
+    let or = strategy
+        .as_sql_keyword()
+        .map(|keyword| format!(" OR {keyword}"))
+        .unwrap_or_default();
+
+    let sql = if columns.is_empty() {
+        format!("INSERT{or} INTO {} DEFAULT VALUES;", meta.table)
+    }
+    else {
+        let names: Vec<_> = params
+            .iter()
+            .map(|(name, _)| *name)
+            .collect();
+
+        format!(
+            "INSERT{or} INTO {} ({}) VALUES({});",
+            meta.table,
+            columns.join(", "),
+            names.join(", ")
+        )
+    };
+
+    conn.prepare_cached(&sql)?.execute(params.as_slice())?;
+
+    Ok(())
+}
+
+/// Implementation detail of [`Model::from_row`] and [`Model::read_into`].
+/// 
+/// Resolves the index of the first of `names` (a column name and its aliases) present in the row. If none are,
+/// falls back to `ordinal` (when `#[positional_fallback]` is enabled) provided the row is wide enough, and otherwise returns the error for the primary (first) name.
+#[doc(hidden)]
+pub fn __column_index(row: &Row, names: &[&str], ordinal: Option<usize>) -> Result<usize> {
+    __statement_column_index(row.as_ref(), names, ordinal)
+}
+
+/// Implementation detail of [`Model::column_indices`].
+/// 
+/// Resolves a column's index in the output of a statement, exactly like [`__column_index`] does for a row.
+#[doc(hidden)]
+pub fn __statement_column_index(stmt: &Statement, names: &[&str], ordinal: Option<usize>) -> Result<usize> {
+    match stmt.column_index(names[0]) {
+        Err(err) => names[1..]
+            .iter()
+            .find_map(|name| stmt.column_index(name).ok())
+            .or_else(|| ordinal.filter(|ordinal| *ordinal < stmt.column_count()))
+            .ok_or(err),
+        index => index
+    }
+}
+
+/// Implementation detail of [`Model::from_value_map`].
+/// 
+/// Retrieves the value of the first of `names` (a column name and its aliases) present in the map, or returns an error for the primary (first) name.
+#[doc(hidden)]
+pub fn __map_value<'a>(map: &'a HashMap<String, Value>, names: &[&str]) -> Result<ValueRef<'a>> {
+    names
+        .iter()
+        .find_map(|name| map.get(*name))
+        .map(ValueRef::from)
+        .ok_or_else(|| rusqlite::Error::InvalidColumnName(names[0].to_owned()))
+}
+
+/// Implementation detail of writing fields typed `Result<T, E>`.
+/// 
+/// Borrows the `Ok` value, or errors if the field holds a (previously captured) error - as there is nothing to write.
+#[doc(hidden)]
+pub fn __captured<'a, T, E>(value: &'a std::result::Result<T, E>, field: &str) -> Result<&'a T>
+where
+    E: std::fmt::Display
+{
+    value.as_ref().map_err(|err| {
+        rusqlite::Error::ToSqlConversionFailure(
+            format!("the field `{field}` holds an error and cannot be written: {err}").into()
+        )
+    })
+}
+
+/// Implementation detail of binding fields without a `#[bind]` function.
+/// 
+/// Dispatches to [`ToSql`] if the field type implements it, and to [`SqlConvert`] otherwise - see [`__BindToSql`] and [`__BindConvert`].
+#[doc(hidden)]
+pub struct __Bind<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized> Clone for __Bind<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for __Bind<'_, T> {}
+
+/// Implementation detail of [`__Bind`].
+/// 
+/// Implemented for `&__Bind`, so that method resolution prefers it over [`__BindConvert`] (implemented for `__Bind`.)
+#[doc(hidden)]
+pub trait __BindToSql<'a> {
+    fn __bind(self) -> Result<Parameter<'a>>;
+}
+
+impl<'a, T> __BindToSql<'a> for &__Bind<'a, T>
+where
+    T: ToSql
+{
+    fn __bind(self) -> Result<Parameter<'a>> {
+        Ok(Parameter::Borrowed(self.0))
+    }
+}
+
+/// Implementation detail of [`__Bind`].
+/// 
+/// Implemented for every `__Bind`, so that a field type with neither [`ToSql`] nor [`SqlConvert`] ends up here - and fails
+/// on the method's `T: SqlConvert` bound, reporting [`SqlConvert`]'s diagnostic rather than a bare "method not found."
+#[doc(hidden)]
+pub trait __BindConvert<'a, T: ?Sized> {
+    fn __bind(self) -> Result<Parameter<'a>>
+    where
+        T: SqlConvert;
+}
+
+impl<'a, T: ?Sized> __BindConvert<'a, T> for __Bind<'a, T> {
+    fn __bind(self) -> Result<Parameter<'a>>
+    where
+        T: SqlConvert
+    {
+        Ok(Parameter::Boxed(Box::new(self.0.bind()?)))
+    }
+}
+
+/// Implementation detail of extracting fields without an `#[extr]` function.
+/// 
+/// Dispatches to [`FromSql`] if the field type implements it, and to [`SqlConvert`] otherwise - see [`__ExtrFromSql`] and [`__ExtrConvert`].
+#[doc(hidden)]
+pub struct __Extr<T>(PhantomData<fn() -> T>);
+
+impl<T> __Extr<T> {
+    pub const NEW: Self = Self(PhantomData);
+}
+
+impl<T> Clone for __Extr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for __Extr<T> {}
+
+/// Implementation detail of [`__Extr`].
+/// 
+/// Implemented for `&__Extr`, so that method resolution prefers it over [`__ExtrConvert`] (implemented for `__Extr`.)
+#[doc(hidden)]
+pub trait __ExtrFromSql<T> {
+    fn __extr_row(self, row: &Row, idx: usize) -> Result<T>;
+
+    fn __extr_value(self, value: ValueRef) -> FromSqlResult<T>;
+}
+
+impl<T> __ExtrFromSql<T> for &__Extr<T>
+where
+    T: FromSql
+{
+    fn __extr_row(self, row: &Row, idx: usize) -> Result<T> {
+        row.get(idx)
+    }
+
+    fn __extr_value(self, value: ValueRef) -> FromSqlResult<T> {
+        T::column_result(value)
+    }
+}
+
+/// Implementation detail of [`__Extr`].
+/// 
+/// Implemented for every `__Extr`, for the same reason as [`__BindConvert`].
+#[doc(hidden)]
+pub trait __ExtrConvert<T> {
+    fn __extr_row(self, row: &Row, idx: usize) -> Result<T>
+    where
+        T: SqlConvert;
+
+    fn __extr_value(self, value: ValueRef) -> FromSqlResult<T>
+    where
+        T: SqlConvert;
+}
+
+impl<T> __ExtrConvert<T> for __Extr<T> {
+    fn __extr_row(self, row: &Row, idx: usize) -> Result<T>
+    where
+        T: SqlConvert
+    {
+        Ok(self.__extr_value(row.get_ref(idx)?)?)
+    }
+
+    fn __extr_value(self, value: ValueRef) -> FromSqlResult<T>
+    where
+        T: SqlConvert
+    {
+        T::extr(&value)
+    }
+}
+
+/// Implementation detail of the filter builders generated by [`Model`](macro@crate::Model).
+/// 
+/// Accumulates `column op ?` predicates (joined with `AND`) along with their owned values. Any error encountered while
+/// binding a value is held until the filter is executed, so that the builder methods can be chained.
+#[doc(hidden)]
+pub struct __Filter<M> {
+    predicates: Vec<(&'static str, &'static str, Value)>,
+    error: Option<rusqlite::Error>,
+    _model: PhantomData<fn() -> M>,
+}
+
+impl<M> Default for __Filter<M> {
+    fn default() -> Self {
+        Self {
+            predicates: Vec::new(),
+            error: None,
+            _model: PhantomData
+        }
+    }
+}
+
+impl<M> __Filter<M>
+where
+    M: Model
+{
+    pub fn push(&mut self, column: &'static str, op: &'static str, value: Result<impl ToSql>) {
+        if self.error.is_some() {
+            return
+        }
+
+        match value.and_then(|value| to_owned_value(value.to_sql()?)) {
+            Ok(value) => self.predicates.push((column, op, value)),
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    pub fn sql(&self) -> String {
+        let meta = M::metadata();
+
+        let mut sql = format!(
+            "SELECT {} FROM {}",
+            select_list(&meta),
+            meta.table
+        );
+
+        let predicates = self
+            .predicates
+            .iter()
+            .map(|(column, op, _)| format!("{column} {op} ?"))
+            .collect::<Vec<_>>();
+
+        if !predicates.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&predicates.join(" AND "));
+        }
+
+        sql.push(';');
+        sql
+    }
+
+    pub fn fetch(self, conn: &Connection) -> Result<Vec<M>> {
+        if let Some(err) = self.error {
+            return Err(err)
+        }
+
+        let values = self
+            .predicates
+            .iter()
+            .map(|(_, _, value)| value);
+
+        conn.prepare_cached(&self.sql())?
+            .query_and_then(rusqlite::params_from_iter(values), M::from_row)?
+            .collect()
+    }
+}
+
+/// Generate the `SELECT` list read by [`Model::from_row`] - every column, followed by any `#[select_expr]` fields.
+fn select_list(meta: &ModelMeta) -> String {
+    meta
+        .columns
+        .iter()
+        .map(|column| column.to_string())
+        .chain(meta.computed.iter().map(|(column, expr)| format!("{expr} AS {column}")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The error returned by primary key-driven methods when the model's `#[primary_key]` doesn't have the shape they need.
+pub(crate) fn unsupported_primary_key(meta: &ModelMeta, requirement: &str) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_MISUSE),
+        Some(format!("the model `{}` {requirement}", meta.model))
+    )
+}
+
+/// Generate a `WHERE` predicate matching the model's primary key columns against their named parameters.
+fn primary_key_predicate(meta: &ModelMeta) -> String {
+    meta
+        .primary_key
+        .iter()
+        .map(|col| format!("{col} = :{col}"))
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+/// Implementation detail of [`sql_enum`]'s text storage mode.
+#[doc(hidden)]
+pub fn __sql_enum_matches(value: &str, variant: &str, case_insensitive: bool) -> bool {
+    match case_insensitive {
+        true => value.eq_ignore_ascii_case(variant),
+        false => value == variant,
+    }
+}
+
+/// Implementation detail of `#[extr]` and `#[extr_named]`.
+/// 
+/// Boxes an arbitrary extractor error into a [`FromSqlError`]. Errors that already *are* a [`FromSqlError`]
+/// are passed through untouched, so that variants like [`FromSqlError::InvalidType`] survive the round trip.
+#[doc(hidden)]
+pub fn __extr_error<E>(err: E) -> FromSqlError
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>
+{
+    match err.into().downcast::<FromSqlError>() {
+        Ok(err) => *err,
+        Err(err) => FromSqlError::Other(err),
+    }
+}
+
+/// Implementation detail of [`Model::read_into`].
+/// 
+/// Reads a `TEXT` column into an existing [`String`], reusing its allocation. Errors carry the same column context as [`Row::get`].
+#[doc(hidden)]
+pub fn __read_string_into(row: &Row, idx: usize, buf: &mut String) -> Result<()> {
+    let value = row.get_ref(idx)?;
+    let str = value
+        .as_str()
+        .map_err(|err| column_error(row, idx, value, err))?;
+
+    buf.clear();
+    buf.push_str(str);
+
+    Ok(())
+}
+
+/// Implementation detail of [`Model::read_into`].
+/// 
+/// Reads a `BLOB` column into an existing [`Vec<u8>`], reusing its allocation. Errors carry the same column context as [`Row::get`].
+#[doc(hidden)]
+pub fn __read_blob_into(row: &Row, idx: usize, buf: &mut Vec<u8>) -> Result<()> {
+    let value = row.get_ref(idx)?;
+    let blob = value
+        .as_blob()
+        .map_err(|err| column_error(row, idx, value, err))?;
+
+    buf.clear();
+    buf.extend_from_slice(blob);
+
+    Ok(())
+}
+
+/// Attach a column's index, name and type to an error from reading it, exactly like [`Row::get`] does.
+fn column_error(row: &Row, idx: usize, value: ValueRef, err: FromSqlError) -> rusqlite::Error {
+    match err {
+        FromSqlError::InvalidType => rusqlite::Error::InvalidColumnType(
+            idx,
+            row.as_ref().column_name(idx).unwrap_or_default().to_owned(),
+            value.data_type()
+        ),
+        FromSqlError::Other(err) => rusqlite::Error::FromSqlConversionFailure(idx, value.data_type(), err),
+        err => rusqlite::Error::FromSqlConversionFailure(idx, value.data_type(), Box::new(err)),
+    }
+}
+
+/// Convert the output of a [`ToSql`] implementation into an owned [`Value`].
+fn to_owned_value(output: ToSqlOutput<'_>) -> Result<Value> {
+    match output {
+        ToSqlOutput::Borrowed(value) => Ok(value.into()),
+        ToSqlOutput::Owned(value) => Ok(value),
+        _ => Err(rusqlite::Error::ToSqlConversionFailure(
+            "unsupported ToSqlOutput variant; only borrowed and owned values can be converted".into()
+        ))
+    }
+}
+
+/// Render a [`Value`] as an SQL literal.
+fn sql_literal(value: &Value) -> String {
+    use std::fmt::Write;
+
+    match value {
+        Value::Null => "NULL".to_owned(),
+        Value::Integer(i) => i.to_string(),
+        Value::Real(f) if f.is_nan() => "NULL".to_owned(),
+        Value::Real(f) if f.is_infinite() => {
+            if f.is_sign_positive() { "9e999" } else { "-9e999" }.to_owned()
+        },
+        Value::Real(f) => format!("{f:?}"),
+        Value::Text(s) => format!("'{}'", s.replace('\'', "''")),
+        Value::Blob(b) => {
+            let mut literal = String::with_capacity(b.len() * 2 + 3);
+            literal.push_str("x'");
+            for byte in b {
+                let _ = write!(literal, "{byte:02x}");
+            }
+            literal.push('\'');
+            literal
+        }
+    }
+}
+
+/// Append a value to a CSV record, quoting and escaping it as described by RFC 4180.
+fn csv_field(record: &mut String, value: ValueRef) {
+    use std::fmt::Write;
+
+    match value {
+        ValueRef::Null => {},
+        ValueRef::Integer(i) => {
+            let _ = write!(record, "{i}");
+        },
+        ValueRef::Real(f) => {
+            let _ = write!(record, "{f:?}");
+        },
+        ValueRef::Text(s) => {
+            let text = String::from_utf8_lossy(s);
+
+            if text.contains([',', '"', '\r', '\n']) {
+                record.push('"');
+                record.push_str(&text.replace('"', "\"\""));
+                record.push('"');
+            }
+            else {
+                record.push_str(&text);
+            }
+        },
+        ValueRef::Blob(b) => {
+            for byte in b {
+                let _ = write!(record, "{byte:02x}");
+            }
+        }
+    }
+}
+
+/// Metadata about a [`Model`] implementor.
+/// 
+/// Can be retrieved via the [`Model::metadata`] and [`Model::metadata_dyn`] methods.
+/// 
+/// This struct is `#[non_exhaustive]`, so that more metadata can be added without breaking changes; it can only be constructed by the derive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ModelMeta {
+    /// The name of the model type.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo { 
+    ///     pub field: String 
+    /// }
+    /// 
+    /// assert_eq!("Foo", Foo::metadata().model)
+    /// ```
+    pub model: &'static str,
+    /// The name of the model table.
+    /// 
+    /// # Example
     /// ```rust
     /// # use exemplar::*;
     /// #[derive(Model)]
@@ -377,6 +3032,9 @@ pub struct ModelMeta {
     pub table: &'static str,
     /// The field names of the model type, in order of their definition.
     /// 
+    /// Fields marked with `#[skip]` are not included. A field split across several columns with [`#[bind_multi]`](crate::macros::Model#bind_multiextr_multi)
+    /// is listed once per column, so this always lines up with [`ModelMeta::columns`].
+    /// 
     /// # Example
     /// ```rust
     /// # use exemplar::*;
@@ -392,6 +3050,11 @@ pub struct ModelMeta {
     pub fields: &'static [&'static str],
     /// The columns of the model table.
     /// 
+    /// Columns are always listed in the order their fields are defined (ignoring `#[skip]`ped fields), and this order is guaranteed to be shared by
+    /// [`ModelMeta::fields`], [`ModelMeta::insert_sql`], [`ModelMeta::create_table_sql_untyped`], [`Model::select_all`], [`Model::to_params`] and the
+    /// (opt-in) [positional fallback](Model#column-resolution) of [`Model::from_row`]. Reordering a model's fields is therefore a breaking change for any code
+    /// (or tables) that depend on column positions.
+    /// 
     /// # Example
     /// ```rust
     /// # use exemplar::*;
@@ -406,4 +3069,243 @@ pub struct ModelMeta {
     /// assert_eq!(&["bar", "baz"], Foo::metadata().columns)
     /// ```
     pub columns: &'static [&'static str],
-}
\ No newline at end of file
+    /// The columns that make up the model's primary key, as marked with [`#[primary_key]`](crate::macros::Model#primary_key), in order of definition.
+    /// 
+    /// This is empty if no primary key is declared, and has more than one element only for [composite keys](crate::macros::Model#composite_key).
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo { 
+    ///     #[primary_key]
+    ///     #[column("id")]
+    ///     pub bar: i64,
+    ///     pub qux: String,
+    /// }
+    /// 
+    /// assert_eq!(&["id"], Foo::metadata().primary_key)
+    /// ```
+    pub primary_key: &'static [&'static str],
+    /// Whether each column is written through a conversion function (`#[bind]`, `#[bind_multi]`, `#[as_text]` or a `#[convert]` rule),
+    /// rather than the field's own [`ToSql`] implementation. Listed in the same order as [`ModelMeta::columns`].
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo { 
+    ///     pub bar: String,
+    ///     #[as_text]
+    ///     pub qux: u32,
+    /// }
+    /// 
+    /// assert_eq!(&[false, true], Foo::metadata().bound)
+    /// ```
+    pub bound: &'static [bool],
+    /// The `CHECK` constraints declared with [`#[sql_check]`](crate::macros::Model#sql_check), as `(column, expression)` pairs.
+    /// 
+    /// Listed in column order, and then in order of definition for columns with several constraints.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo { 
+    ///     pub bar: String,
+    ///     #[sql_check("qux >= 0")]
+    ///     pub qux: i64,
+    /// }
+    /// 
+    /// assert_eq!(&[("qux", "qux >= 0")], Foo::metadata().checks)
+    /// ```
+    pub checks: &'static [(&'static str, &'static str)],
+    /// The read-only fields computed with [`#[select_expr]`](crate::macros::Model#select_expr), as `(column, expression)` pairs in order of definition.
+    /// 
+    /// These are not part of [`ModelMeta::columns`] (or any other per-column list), as they don't correspond to columns of the model table.
+    /// Instead, they're selected as `<expression> AS <column>` after every stored column.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo { 
+    ///     pub bar: String,
+    ///     #[select_expr("LENGTH(bar)")]
+    ///     pub bar_len: i64,
+    /// }
+    /// 
+    /// assert_eq!(&["bar"], Foo::metadata().columns);
+    /// assert_eq!(&[("bar_len", "LENGTH(bar)")], Foo::metadata().computed)
+    /// ```
+    pub computed: &'static [(&'static str, &'static str)],
+    /// The `INSERT` statements used by [`Model::insert_or`], in the same order as the variants of [`OnConflict`] are declared.
+    /// 
+    /// Retrieved with [`ModelMeta::insert_sql`].
+    insert_sql: [&'static str; 5],
+}
+
+impl ModelMeta {
+    /// Implementation detail of [`Model`](macro@crate::Model).
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn __new(
+        model: &'static str,
+        table: &'static str,
+        fields: &'static [&'static str],
+        columns: &'static [&'static str],
+        primary_key: &'static [&'static str],
+        bound: &'static [bool],
+        checks: &'static [(&'static str, &'static str)],
+        computed: &'static [(&'static str, &'static str)],
+        insert_sql: [&'static str; 5],
+    ) -> Self {
+        Self {
+            model,
+            table,
+            fields,
+            columns,
+            primary_key,
+            bound,
+            checks,
+            computed,
+            insert_sql
+        }
+    }
+
+    /// Retrieve the `INSERT` statement used by [`Model::insert_or`] for the provided [conflict resolution strategy](OnConflict).
+    /// 
+    /// Fields are bound as named parameters, exactly as in [`Model::insert_with`] - so the returned SQL can be prepared and used with it (or [`Model::to_params`])
+    /// to perform insertions from contexts that only have a [`dyn Model`](Model).
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo { 
+    ///     pub bar: String,
+    /// }
+    /// 
+    /// assert_eq!(
+    ///     "INSERT OR IGNORE INTO foos (bar) VALUES(:bar);",
+    ///     Foo::metadata().insert_sql(OnConflict::Ignore)
+    /// )
+    /// ```
+    #[must_use]
+    pub fn insert_sql(&self, strategy: OnConflict) -> &'static str {
+        let idx = match strategy {
+            OnConflict::Abort => 0,
+            OnConflict::Fail => 1,
+            OnConflict::Ignore => 2,
+            OnConflict::Replace => 3,
+            OnConflict::Rollback => 4,
+        };
+
+        self.insert_sql[idx]
+    }
+
+    /// Render an untyped `CREATE TABLE` statement for the model table.
+    /// 
+    /// SQLite permits columns without a declared type, so the output is immediately usable - this is handy
+    /// for quick prototyping and tests, but you should prefer a hand-written schema for anything serious.
+    /// 
+    /// Any [`#[sql_check]`](crate::macros::Model#sql_check) constraints are attached to their columns.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo { 
+    ///     pub bar: String,
+    ///     #[column("baz")]
+    ///     pub qux: String,
+    /// }
+    /// 
+    /// assert_eq!(
+    ///     "CREATE TABLE foos (bar, baz);",
+    ///     Foo::metadata().create_table_sql_untyped()
+    /// )
+    /// ```
+    #[must_use]
+    pub fn create_table_sql_untyped(&self) -> String {
+        let columns: Vec<_> = self
+            .columns
+            .iter()
+            .map(|column| {
+                self.checks
+                    .iter()
+                    .filter(|(checked, _)| checked == column)
+                    .fold(column.to_string(), |column, (_, expr)| format!("{column} CHECK ({expr})"))
+            })
+            .collect();
+
+        format!(
+            "CREATE TABLE {} ({});",
+            self.table,
+            columns.join(", ")
+        )
+    }
+
+    /// Describe the model as a (pretty-printed) JSON document, for generating external documentation or diffing model definitions.
+    /// 
+    /// The document contains the model name, its table and one entry per column, in column order. Object keys are sorted,
+    /// so the output is stable for a given model definition.
+    /// 
+    /// # Example
+    /// ```rust
+    /// # use exemplar::*;
+    /// #[derive(Model)]
+    /// #[table("foos")]
+    /// pub struct Foo { 
+    ///     #[as_text]
+    ///     #[column("baz")]
+    ///     pub qux: u32,
+    /// }
+    /// 
+    /// let expected = r#"{
+    ///   "columns": [
+    ///     {
+    ///       "column": "baz",
+    ///       "field": "qux",
+    ///       "has_bind": true
+    ///     }
+    ///   ],
+    ///   "model": "Foo",
+    ///   "table": "foos"
+    /// }"#;
+    /// 
+    /// assert_eq!(expected, Foo::metadata().describe())
+    /// ```
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let columns: Vec<_> = self
+            .fields
+            .iter()
+            .zip(self.columns)
+            .zip(self.bound)
+            .map(|((field, column), bound)| {
+                serde_json::json!({
+                    "field": field,
+                    "column": column,
+                    "has_bind": bound,
+                })
+            })
+            .collect();
+
+        let description = serde_json::json!({
+            "model": self.model,
+            "table": self.table,
+            "columns": columns,
+        });
+
+        serde_json::to_string_pretty(&description)
+            .expect("A JSON value should always serialize.")
+    }
+}
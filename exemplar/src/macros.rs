@@ -17,8 +17,10 @@
 /// 
 /// All fields in a [`Model`](crate::Model) derivee must either:
 /// - Implement [`ToSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.ToSql.html) and [`FromSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.FromSql.html). Most common types will meet this requirement.
+///   - This includes fixed-size byte arrays like `[u8; 32]` (common for hashes and keys), which are stored as `BLOB`s. Reading a blob of any other length fails.
 /// - Have `#[bind]` and `#[extr]` [attributes](Model#attributes) on fields that do not meet the first requirement. 
 ///   - This escape hatch is designed to enable compatibility with certain `std` types like [`PathBuf`](std::path::PathBuf) and third-party crate types.
+/// - Implement [`SqlConvert`](crate::SqlConvert), which is picked up automatically. This is the attribute-free alternative for types you own.
 /// 
 /// # Usage
 /// Most of the time, deriving [`Model`](crate::Model) is easy. The only thing you need to specify is the table name:
@@ -31,7 +33,7 @@
 ///     pub age: u16,
 /// }
 /// ```
-///
+/// 
 /// For more complicated types and schemas, you may need to make use of some of the [attributes](Model#attributes) recognized by the macro:
 /// ```rust
 /// # use std::path::{PathBuf, Path};
@@ -54,6 +56,60 @@
 /// # Attributes
 /// The [`Model`](crate::Model) derive macro recognizes several attributes.
 /// 
+/// ### `#[table]`
+/// Usage:
+/// ```ignore
+/// #[table("name")]
+/// #[table(infer = "rule")]
+/// pub struct T { ... }
+/// ```
+/// 
+/// The `table` attribute (which is required) specifies the name of the SQL table your type maps to. You can also opt into inferring
+/// the name from the struct's identifier, using one of the following rules:
+/// 
+/// - `snake_case` - `UserAccount` maps to `user_account`.
+/// - `snake_case_plural` - `UserAccount` maps to `user_accounts`.
+/// 
+/// Pluralization is deliberately naive: a trailing consonant + `y` becomes `ies` (`Category` maps to `categories`), 
+/// words ending in `s`, `x`, `z`, `ch` or `sh` get `es`, and everything else gets an `s`. Irregular nouns (`Person`, `Mouse`) are not handled - 
+/// use an explicit name for those.
+/// 
+/// ### `#[resolve_table]`
+/// Usage:
+/// ```ignore
+/// #[table("name")]
+/// #[resolve_table(path::to::fn)]
+/// pub struct T { ... }
+/// ```
+/// 
+/// The `resolve_table` attribute defers the table name to a function with the signature `fn() -> &'static str`, which
+/// becomes the model's [`Model::resolve_table`](crate::Model::resolve_table) implementation. This is useful when the same type
+/// maps to different tables depending on deployment - like a per-tenant prefix read from configuration at startup.
+/// 
+/// `#[table]` is still required, and is used by [`#[check]`](#check) and as documentation of the canonical name.
+/// 
+/// Generated SQL is built the first time it's needed and then cached, so the function should return the same name every time it's called.
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// # use std::sync::OnceLock;
+/// static TENANT_TABLE: OnceLock<String> = OnceLock::new();
+/// 
+/// fn tenant_table() -> &'static str {
+///     TENANT_TABLE.get_or_init(|| "acme_people".to_owned())
+/// }
+/// 
+/// #[derive(Model)]
+/// #[table("people")]
+/// #[resolve_table(tenant_table)]
+/// pub struct Person {
+///     name: String,
+/// }
+/// 
+/// assert_eq!(Person::resolve_table(), "acme_people");
+/// assert_eq!(Person::metadata().table, "acme_people");
+/// ```
+/// 
 /// ### `#[check]`
 /// Usage:
 /// ```ignore
@@ -61,71 +117,773 @@
 /// pub struct T { ... }
 /// ```
 /// 
-/// The `check` attribute automatically generates a test that checks the derived [`Model`](crate::Model) implementation against a provided schema.
+/// The `check` attribute automatically generates a test that checks the derived [`Model`](crate::Model) implementation against a provided schema.
+/// 
+/// More specifically, the generated test verifies that:
+/// - The specified table exists.
+/// - The columns in the schema match up with those specified by the model type. If the model has fields not present in the schema (or vice versa) the test will fail.
+/// 
+/// It does *not* verify the validity of column types, nor does it test actual insertion/retrieval.
+/// 
+/// For small tables, the schema can also be provided inline instead of as a path:
+/// ```ignore
+/// #[check(inline = "CREATE TABLE users (username, home_dir, pwd);")]
+/// pub struct T { ... }
+/// ```
+/// 
+/// If the model is meant for a `WITHOUT ROWID` table, add the `without_rowid` flag; the test will then also fail if the schema
+/// declares an ordinary (rowid) table. (This relies on `pragma_table_list`, so it requires SQLite 3.37.0 or later.)
+/// ```ignore
+/// #[check("path_to_schema", without_rowid)]
+/// pub struct T { ... }
+/// ```
+/// 
+/// If the model relies on an index (such as a unique index targeted by upserts), name it with `index`; the test will then also fail
+/// if the schema doesn't declare an index of that name on the table. This can be repeated for several indexes.
+/// ```ignore
+/// #[check("path_to_schema", index = "idx_users_email", index = "idx_users_name")]
+/// pub struct T { ... }
+/// ```
+/// 
+/// For projects that keep their schema as a folder of migrations, point `migrations` at the directory instead. Every `.sql` file in it is applied
+/// in lexicographic order of file name (so number them, like `0001_init.sql`) before the model is checked. As with schema paths, the directory is
+/// resolved relative to the current file.
+/// ```ignore
+/// #[check(migrations = "../migrations")]
+/// pub struct T { ... }
+/// ```
+/// 
+/// The directory is read when the generated test runs, so newly added migrations are always picked up.
+/// 
+/// ### `#[on_conflict]`
+/// Usage:
+/// ```ignore
+/// #[on_conflict("ignore")]
+/// pub struct T { ... }
+/// ```
+/// 
+/// The `on_conflict` attribute sets the [conflict resolution strategy](crate::OnConflict) used by [`insert`](crate::Model::insert) (and [`insert_uncached`](crate::Model::insert_uncached)),
+/// baking the policy into the type. This is useful for tables like append-only logs, where duplicates should always be ignored.
+/// 
+/// Valid strategies are `abort` (the default), `fail`, `ignore`, `replace` and `rollback`. [`insert_or`](crate::Model::insert_or) is unaffected.
+/// 
+/// ### `#[convert]`
+/// Usage:
+/// ```ignore
+/// #[convert(Type => path::to::bind, path::to::extr)]
+/// pub struct T { ... }
+/// ```
+/// 
+/// The `convert` attribute applies a pair of [`#[bind]`/`#[extr]`](#bindextr) functions to every field of the given type, which saves
+/// repeating them on conversion-heavy models. It can be specified multiple times (once per type.) Per-field `#[bind]`, `#[extr]`, `#[extr_named]`
+/// and `#[as_text]` attributes take precedence over the rule.
+/// 
+/// Types are matched *syntactically* - a rule for `PathBuf` won't apply to a field declared as `std::path::PathBuf`, and vice versa.
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// # use std::path::PathBuf;
+/// #[derive(Model)]
+/// #[table("projects")]
+/// #[convert(PathBuf => exemplar::convert::bind_path, exemplar::convert::extr_path)]
+/// pub struct Project {
+///     pub root: PathBuf,
+///     pub config: PathBuf,
+///     pub output: PathBuf,
+/// }
+/// ```
+/// 
+/// ### `#[composite_key]`
+/// Usage:
+/// ```ignore
+/// #[composite_key]
+/// ```
+/// 
+/// The `composite_key` attribute allows more than one field to be marked with [`#[primary_key]`](#primary_key), for tables whose primary key spans several columns.
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("memberships")]
+/// #[composite_key]
+/// pub struct Membership {
+///     #[primary_key]
+///     pub user: i64,
+///     #[primary_key]
+///     pub team: i64,
+///     pub role: String,
+/// }
+/// ```
+/// 
+/// ### `#[hash_by_pk]`
+/// Usage:
+/// ```ignore
+/// #[hash_by_pk]
+/// ```
+/// 
+/// The `hash_by_pk` attribute implements [`PartialEq`], [`Eq`] and [`Hash`](std::hash::Hash) for the model, considering *only* its [`#[primary_key]`](#primary_key)
+/// field(s) - so two instances are equal if they refer to the same row, whatever their other fields hold. This is useful for keeping models in hash maps and sets by identity.
+/// 
+/// The model must have a primary key, whose field types implement the three traits. As the implementations are generated, don't also derive them.
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// # use std::collections::HashSet;
+/// #[derive(Model)]
+/// #[table("users")]
+/// #[hash_by_pk]
+/// pub struct User {
+///     #[primary_key]
+///     pub id: i64,
+///     pub name: String,
+/// }
+/// 
+/// let old = User { id: 1, name: "Alice".to_owned() };
+/// let new = User { id: 1, name: "Alicia".to_owned() };
+/// 
+/// assert!(old == new);
+/// assert_eq!(HashSet::from([old, new]).len(), 1);
+/// ```
+/// 
+/// ### `#[order_by_pk]`
+/// Usage:
+/// ```ignore
+/// #[hash_by_pk]
+/// #[order_by_pk]
+/// ```
+/// 
+/// The `order_by_pk` attribute implements [`PartialOrd`] and [`Ord`] for the model, comparing *only* its [`#[primary_key]`](#primary_key) field(s)
+/// (in order of definition, for composite keys.) This is useful for sorting collections of models by identity.
+/// 
+/// As [`Ord`] must agree with [`PartialEq`], `order_by_pk` requires [`#[hash_by_pk]`](#hash_by_pk) - and the key's field types must implement [`Ord`].
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("users")]
+/// #[hash_by_pk]
+/// #[order_by_pk]
+/// pub struct User {
+///     #[primary_key]
+///     pub id: i64,
+///     pub name: String,
+/// }
+/// 
+/// let mut users = vec![
+///     User { id: 2, name: "Alice".to_owned() },
+///     User { id: 1, name: "Bob".to_owned() },
+/// ];
+/// 
+/// users.sort();
+/// 
+/// assert_eq!(users[0].name, "Bob");
+/// ```
+/// 
+/// ### `#[positional_fallback]`
+/// Usage:
+/// ```ignore
+/// #[positional_fallback]
+/// pub struct T { ... }
+/// ```
+/// 
+/// By default, [`from_row`](crate::Model::from_row) (and the other row-reading methods) look up every field by its column name, and fail with
+/// [`InvalidColumnName`](rusqlite::Error::InvalidColumnName) if it is missing. The `positional_fallback` attribute makes a missing column fall back to
+/// the one at the field's position instead - the `n`th field (ignoring [skipped](#skip) fields) is read from the `n`th column.
+/// 
+/// This lets the model be read from queries whose columns lack usable names, such as aggregates:
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// # use rusqlite::Connection;
+/// #[derive(Model)]
+/// #[table("people")]
+/// #[positional_fallback]
+/// pub struct Person {
+///     pub name: String,
+///     pub age: u16,
+/// }
+/// 
+/// # fn main() -> rusqlite::Result<()> {
+/// # let conn = Connection::open_in_memory()?;
+/// # conn.execute_batch("
+/// #     CREATE TABLE people (name, age);
+/// #     INSERT INTO people VALUES ('Alice', 21), ('Bob', 90);
+/// # ")?;
+/// let oldest = conn.query_row("SELECT max(name), max(age) FROM people", [], Person::from_row)?;
+/// 
+/// assert_eq!(oldest.age, 90);
+/// # Ok(())
+/// # }
+/// ```
+/// 
+/// Be careful: if a query renames or omits a column, the field silently reads whatever column happens to sit at its position.
+/// Only opt in for models that are actually read from such queries.
+/// 
+/// ### `#[bind]`/`#[extr]`
+/// Usage:
+/// ```ignore
+/// #[bind(path::to::fn)]
+/// #[extr(path::to::fn)]
+/// field: T,
+/// ```
+/// 
+/// The `bind` and `extr` attributes specify functions used to convert the annotated field to and from an SQL-friendly representation.
+/// This is primarily intended as an escape hatch for when you can't implement [`ToSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.ToSql.html) and [`FromSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.FromSql.html)
+/// yourself.
+/// 
+/// Both attributes take as their argument a path to a free function used to do the conversion.
+/// - For `bind`, the signature should be [`fn(&T) -> BindResult`](crate::BindResult).
+/// - For `extr`, the signature should be [`fn(&ValueRef) -> ExtrResult<T>`](crate::ExtrResult). 
+/// 
+/// Extractors may also return `Result<T, E>` for any `E: Into<Box<dyn Error + Send + Sync>>`, letting you keep your own domain error types;
+/// such errors are wrapped in [`FromSqlError::Other`](rusqlite::types::FromSqlError::Other).
+/// 
+/// In both cases `T` is the type of the field being annotated. For some types (e.g. `PathBuf`) you may also be able to use a type it derefs to, like `Path`.
+/// 
+/// Binders may also return `rusqlite::Result<U>` for any `U: ToSql`, including types that borrow from the field. This is useful for blobs:
+/// returning a `Cow<'_, [u8]>` lets [`to_params`](crate::Model::to_params) and the insertion methods bind the bytes without copying them
+/// whenever the field already holds them.
+/// 
+/// ```rust
+/// # use exemplar::*;
+/// # use std::borrow::Cow;
+/// pub enum Payload {
+///     Bytes(Vec<u8>),
+///     Empty,
+/// }
+/// 
+/// pub fn bind_payload(value: &Payload) -> rusqlite::Result<Cow<'_, [u8]>> {
+///     match value {
+///         Payload::Bytes(bytes) => Ok(Cow::Borrowed(bytes)),
+///         Payload::Empty => Ok(Cow::Owned(Vec::new())),
+///     }
+/// }
+/// ```
+/// 
+/// To tolerate malformed values, give `extr` a fallback with `default`. If the extraction function fails, the fallback (a `fn() -> T`) is called
+/// and its value used instead, rather than propagating the error. This is distinct from `NULL` handling - for `Option<T>` fields, `NULL` is still
+/// extracted as `None`, and the fallback only replaces values that are present but fail to convert.
+/// 
+/// ```ignore
+/// #[extr(exemplar::convert::extr_from_str, default = default_port)]
+/// port: u16,
+/// ```
+/// 
+/// For an `Option<T>` field, the functions normally operate on the `Option` itself - taking an `&Option<T>` and returning an `Option<T>`.
+/// To reuse functions written for `T` instead, add the `lift` flag to lift them over the `Option`: `None` is then bound as `NULL` without calling
+/// the bind function, and `NULL` is extracted as `None` without calling the extraction function.
+/// 
+/// ```ignore
+/// #[bind(bind_path, lift)]
+/// #[extr(extr_path, lift)]
+/// backup_dir: Option<PathBuf>,
+/// ```
+/// 
+/// `lift` can be given to `bind`, `extr` (alongside any `default`) and [`extr_named`](#extr_named), and only on `Option<T>` fields.
+/// Fields stored with [`#[as_text]`](#as_text) are always lifted.
+/// 
+/// The functions may also be generic (or take `impl Trait` arguments), so long as their type parameters can be inferred from the field type.
+/// If they can't, you can specify them with a turbofish: `#[extr(extr_from_str::<Ipv4Addr>)]`.
+/// 
+/// Dynamically-typed `Box<dyn ToSql>` fields (optionally with `+ Send`/`+ Sync`) need no `bind` function - the trait object is borrowed and bound directly.
+/// As trait objects can't implement `FromSql`, such fields *must* have an `extr` function, or the derive will fail:
+/// 
+/// ```rust
+/// # use exemplar::*;
+/// # use rusqlite::ToSql;
+/// # use rusqlite::types::{Value, ValueRef};
+/// #[derive(Model)]
+/// #[table("settings")]
+/// pub struct Setting {
+///     pub key: String,
+///     #[extr(extr_dynamic)]
+///     pub value: Box<dyn ToSql>,
+/// }
+/// 
+/// pub fn extr_dynamic(value: &ValueRef) -> ExtrResult<Box<dyn ToSql>> {
+///     Ok(Box::new(Value::from(*value)))
+/// }
+/// ```
+/// 
+/// The [`convert`](crate::convert) module contains ready-made functions for several common types (including `PathBuf`),
+/// which can be used like this: `#[bind(exemplar::convert::bind_path)]`.
+/// 
+/// If you own the type in question, consider implementing [`SqlConvert`](crate::SqlConvert) for it instead - fields of that type
+/// (or `Option`s of it) are then converted automatically, without any attributes.
+/// 
+/// Example implementations for `PathBuf`:
+/// ```rust
+/// # use exemplar::*;
+/// # use std::path::{Path, PathBuf};
+/// # use rusqlite::types::ValueRef;
+/// pub fn bind_path(value: &Path) -> BindResult {
+///     use rusqlite::types::Value;
+///     use rusqlite::types::ToSqlOutput;
+///     
+///     // Depending on your program, it may make more sense
+///     // to error if a lossless conversion isn't possible.
+///     let str = value.to_string_lossy().into_owned();
+/// 
+///     Ok(ToSqlOutput::Owned(
+///         Value::Text(str)
+///     ))
+/// }
+/// 
+/// pub fn extr_path(value: &ValueRef) -> ExtrResult<PathBuf> {
+///     let path = value.as_str()?;
+///     let path = PathBuf::from(path);
+/// 
+///     Ok(path)
+/// }
+/// ```
+/// 
+/// ### `#[extr_named]`
+/// Usage:
+/// ```ignore
+/// #[extr_named(path::to::fn)]
+/// field: T,
+/// ```
+/// 
+/// A variant of `#[extr]` whose function also receives the name of the column being extracted, which can be used to produce more contextual errors.
+/// 
+/// The signature should be `fn(&ValueRef, &str) -> ExtrResult<T>`. `#[extr_named]` and `#[extr]` cannot be used on the same field.
+/// 
+/// ```rust
+/// # use exemplar::*;
+/// # use std::path::PathBuf;
+/// # use rusqlite::types::{FromSqlError, ValueRef};
+/// pub fn extr_path(value: &ValueRef, column: &str) -> ExtrResult<PathBuf> {
+///     let path = value.as_str().map_err(|_| {
+///         FromSqlError::Other(
+///             format!("column `{column}` does not contain a valid path").into()
+///         )
+///     })?;
+/// 
+///     Ok(PathBuf::from(path))
+/// }
+/// ```
+/// 
+/// ### `#[bind_multi]`/`#[extr_multi]`
+/// Usage:
+/// ```ignore
+/// #[bind_multi(["column_a", "column_b"], path::to::fn)]
+/// #[extr_multi(path::to::fn)]
+/// field: T,
+/// ```
+/// 
+/// These attributes split a single field across several columns, named by the list in `#[bind_multi]`. Both must be present on the field,
+/// and neither can be combined with `#[bind]`, `#[extr]`, `#[extr_named]`, `#[as_text]`, `#[column]` or `#[primary_key]`.
+/// 
+/// The `#[bind_multi]` function should have the signature `fn(&T) -> BindMultiResult<N>`, where `N` is the number of columns; its outputs
+/// are bound to the columns in the order they are listed. The `#[extr_multi]` function should have the signature `fn(&[ValueRef; N]) -> ExtrResult<T>`,
+/// and receives the column values in that same order.
+/// 
+/// ```rust
+/// # use exemplar::*;
+/// # use rusqlite::types::{Value, ValueRef, ToSqlOutput};
+/// /// Split "YYYY-MM-DD HH:MM:SS" into a date column and a time column.
+/// pub fn split_datetime(value: &String) -> BindMultiResult<2> {
+///     let (date, time) = value.split_once(' ').unwrap_or((value, ""));
+/// 
+///     Ok([
+///         ToSqlOutput::Owned(Value::Text(date.to_owned())),
+///         ToSqlOutput::Owned(Value::Text(time.to_owned())),
+///     ])
+/// }
+/// 
+/// pub fn join_datetime(values: &[ValueRef; 2]) -> ExtrResult<String> {
+///     let [date, time] = values;
+/// 
+///     Ok(format!("{} {}", date.as_str()?, time.as_str()?))
+/// }
+/// 
+/// #[derive(Model)]
+/// #[table("events")]
+/// pub struct Event {
+///     name: String,
+///     #[bind_multi(["date", "time"], split_datetime)]
+///     #[extr_multi(join_datetime)]
+///     at: String,
+/// }
+/// 
+/// assert_eq!(Event::COLUMN_COUNT, 3);
+/// ```
+/// 
+/// ### `#[as_text]`
+/// Usage:
+/// ```ignore
+/// #[as_text]
+/// field: T,
+/// ```
+/// 
+/// The `as_text` attribute stores the annotated field as `TEXT`, using its [`Display`](std::fmt::Display) implementation to bind
+/// and its [`FromStr`](std::str::FromStr) implementation to extract. This is shorthand for
+/// [`#[bind(exemplar::convert::bind_display)]`](crate::convert::bind_display) and [`#[extr(exemplar::convert::extr_from_str)]`](crate::convert::extr_from_str),
+/// and so cannot be combined with `#[bind]`, `#[extr]` or `#[extr_named]`.
+/// 
+/// It's useful for storing an enum (such as one defined with [`sql_enum`](crate::sql_enum)) as text in one model, without changing how the enum itself implements [`ToSql`](rusqlite::ToSql).
+/// 
+/// ### `#[transparent]`
+/// Usage:
+/// ```ignore
+/// #[transparent]
+/// field: T,
+/// ```
+/// 
+/// The `transparent` attribute treats a newtype field (a single-field tuple struct, like `struct UserId(i64)`) as its inner value - binding through
+/// the inner value's [`ToSql`](rusqlite::ToSql) implementation, and extracting with its [`FromSql`](rusqlite::types::FromSql) implementation before wrapping it back up.
+/// The newtype itself doesn't need to implement either trait, but its inner field must be visible to the model.
+/// 
+/// ```rust
+/// # use exemplar::*;
+/// pub struct UserId(i64);
+/// 
+/// #[derive(Model)]
+/// #[table("users")]
+/// pub struct User {
+///     #[transparent]
+///     pub id: UserId,
+///     pub name: String,
+/// }
+/// ```
+/// 
+/// If you control the newtype, consider deriving [`SqlTransparent`] on it instead - which makes it usable everywhere, not just in one model.
+/// 
+/// `#[transparent]` cannot be combined with `#[bind]`, `#[extr]`, `#[extr_named]`, `#[as_text]`, `#[bind_multi]` or `#[extr_multi]`, and cannot be used on `Option` fields.
+/// 
+/// ### `#[column]`
+/// Usage:
+/// ```ignore
+/// #[column("name")]
+/// field: T,
+/// ```
+/// 
+/// The `column` attribute overrides the column name Exemplar maps the annotated field to. By default, the field name is assumed to directly map to the underlying schema - `#[column]` is how you alter this behavior.
+/// 
+/// During a migration, a column may go by different names in different databases. You can list alternate names with `alias`:
+/// 
+/// ```ignore
+/// #[column("password", alias = "pwd")]
+/// password: Vec<u8>,
+/// ```
+/// 
+/// When reading a row (with [`from_row`](crate::Model::from_row) or [`read_into`](crate::Model::read_into)), the primary name is tried first, followed by each alias in order.
+/// All other generated code - including insertions and [`select_all`](crate::Model::select_all) - only ever uses the primary name.
+/// 
+/// Exemplar does not quote column names in the SQL it generates, so a column that maps to a reserved SQL keyword (like `group` or `order`)
+/// will produce invalid statements. The derive emits a (deprecation-style) warning pointing at any such field; renaming the column with `#[column]`
+/// is the recommended fix.
+/// 
+/// Since identifiers are never quoted, they are also matched case-insensitively by SQLite - `#[column("Title")]` will happily read from and write to a
+/// `title` column. There is no attribute to change this.
+/// 
+/// ```ignore
+/// #[derive(Model)]
+/// #[table("members")]
+/// pub struct Member {
+///     pub name: String,
+///     pub group: String, // warning: the column `group` is a reserved SQL keyword...
+/// }
+/// ```
+/// 
+/// ### `#[columns]`
+/// Usage:
+/// ```ignore
+/// #[columns("column_a", "column_b", ...)]
+/// pub struct T { ... }
+/// ```
+/// 
+/// The `columns` attribute maps the model's fields to the listed column names positionally, which saves annotating every field with
+/// [`#[column]`](#column) when a table's names differ systematically from the field names. There must be exactly one name per field
+/// ([skipped](#skip) fields excluded), or the derive will fail. A field's own `#[column]` still takes precedence over its listed name.
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("legacy_people")]
+/// #[columns("PERSON_NAME", "PERSON_AGE", "PERSON_EMAIL")]
+/// pub struct Person {
+///     pub name: String,
+///     pub age: u16,
+///     #[column("EMAIL_ADDRESS")]
+///     pub email: String,
+/// }
+/// 
+/// assert_eq!(Person::metadata().columns, &["PERSON_NAME", "PERSON_AGE", "EMAIL_ADDRESS"]);
+/// ```
+/// 
+/// `columns` can't be combined with [`#[column_prefix]`](#column_prefix), or used on structs with [`#[bind_multi]`](#bind_multiextr_multi) fields.
+/// 
+/// ```compile_fail
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("legacy_people")]
+/// #[columns("PERSON_NAME")] // error: the struct has 2 non-skipped field(s)
+/// pub struct Person {
+///     pub name: String,
+///     pub age: u16,
+/// }
+/// ```
+/// 
+/// ### `#[column_prefix]`
+/// Usage:
+/// ```ignore
+/// #[column_prefix("prefix")]
+/// pub struct T { ... }
+/// ```
+/// 
+/// The `column_prefix` attribute prepends a prefix to the column name of every field, which is useful when several logical entities
+/// share one table with prefixed columns. Fields with their own [`#[column]`](#column) (or [`#[bind_multi]`](#bind_multiextr_multi)) are left as-is.
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("feed")]
+/// #[column_prefix("post_")]
+/// pub struct Post {
+///     pub title: String,
+///     pub body: String,
+///     #[column("author_id")]
+///     pub author: i64,
+/// }
+/// 
+/// assert_eq!(Post::metadata().columns, &["post_title", "post_body", "author_id"]);
+/// ```
+/// 
+/// ### `#[primary_key]`
+/// Usage:
+/// ```ignore
+/// #[primary_key]
+/// field: T,
+/// ```
+/// 
+/// The `primary_key` attribute marks the annotated field as (part of) the table's primary key, which is recorded in [`ModelMeta::primary_key`](crate::ModelMeta::primary_key).
+/// 
+/// At most one field may be marked, as methods keyed on the primary key would otherwise be ambiguous. If the table has a composite primary key, 
+/// opt in with [`#[composite_key]`](#composite_key) - without it, marking two fields is a compile-time error:
+/// 
+/// ```compile_fail
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("memberships")]
+/// pub struct Membership {
+///     #[primary_key]
+///     pub user: i64,
+///     #[primary_key] // error: only one field can be marked with #[primary_key]
+///     pub team: i64,
+/// }
+/// ```
+/// 
+/// ### `#[skip]`
+/// Usage:
+/// ```ignore
+/// #[skip]
+/// field: T,
+/// ```
+/// 
+/// The `skip` attribute excludes the annotated field from the model entirely - it has no corresponding column, is never written, and is initialized with [`Default::default`] when reading a row.
+/// 
+/// The type of a skipped field must therefore implement [`Default`], but does not need to implement [`ToSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.ToSql.html) or [`FromSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.FromSql.html).
+/// 
+/// Marker fields typed [`PhantomData`](std::marker::PhantomData) carry no data, so they're skipped automatically - no attribute required.
+/// 
+/// ### `#[sql_check]`
+/// Usage:
+/// ```ignore
+/// #[sql_check("expression")]
+/// field: T,
+/// ```
+/// 
+/// The `sql_check` attribute declares a `CHECK` constraint on the annotated field's column, keeping the invariant next to the field it governs.
+/// It can be specified multiple times, and can't be used on [`#[bind_multi]`](#bind_multiextr_multi) fields.
 /// 
-/// More specifically, the generated test verifies that:
-/// - The specified table exists.
-/// - The columns in the schema match up with those specified by the model type. If the model has fields not present in the schema (or vice versa) the test will fail.
+/// Constraints are recorded in [`ModelMeta::checks`](crate::ModelMeta::checks) and emitted by [`ModelMeta::create_table_sql_untyped`](crate::ModelMeta::create_table_sql_untyped).
+/// If the model also has a [`#[check]`](#check) attribute, the generated test verifies that the schema declares each constraint (comparing expressions
+/// textually, ignoring whitespace and case.)
 /// 
-/// It does *not* verify the validity of column types, nor does it test actual insertion/retrieval.
+/// ```rust
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("people")]
+/// #[check(inline = "CREATE TABLE people (name, age CHECK (age >= 0));")]
+/// pub struct Person {
+///     pub name: String,
+///     #[sql_check("age >= 0")]
+///     pub age: i64,
+/// }
 /// 
-/// ### `#[bind]`/`#[extr]`
+/// assert_eq!(
+///     Person::metadata().create_table_sql_untyped(),
+///     "CREATE TABLE people (name, age CHECK (age >= 0));"
+/// );
+/// ```
+/// 
+/// ### `#[select_expr]`
 /// Usage:
 /// ```ignore
-/// #[bind(path::to::fn)]
-/// #[extr(path::to::fn)]
+/// #[select_expr("expression")]
 /// field: T,
 /// ```
 /// 
-/// The `bind` and `extr` attributes specify functions used to convert the annotated field to and from an SQL-friendly representation.
-/// This is primarily intended as an escape hatch for when you can't implement [`ToSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.ToSql.html) and [`FromSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.FromSql.html)
-/// yourself.
+/// The `select_expr` attribute makes the annotated field *computed* - rather than mapping to a stored column, it's populated from an SQL expression
+/// when reading, and is never written. This lets a model represent a projection, like the length of a blob or a value derived from several columns.
 /// 
-/// Both attributes take as their argument a path to a free function used to do the conversion.
-/// - For `bind`, the signature should be [`fn(&T) -> BindResult`](crate::BindResult).
-/// - For `extr`, the signature should be [`fn(&ValueRef) -> ExtrResult<T>`](crate::ExtrResult). 
+/// The expression is selected as `<expression> AS <column>` (where the column name is the field name, or the name given by [`#[column]`](#column)),
+/// after every stored column - this applies to [`select_all`](crate::Model::select_all) and the other generated queries, as well as
+/// [`columns_for_select`](crate::Model::columns_for_select). When reading rows from your own queries, include the expression under the same name.
 /// 
-/// In both cases `T` is the type of the field being annotated. For some types (e.g. `PathBuf`) you may also be able to use a type it derefs to, like `Path`.
+/// Computed fields are otherwise read like any other (so `#[extr]` and friends work as usual), but are excluded from everything else:
+/// they aren't part of [`ModelMeta::columns`](crate::ModelMeta::columns) or `COLUMN_COUNT`, aren't bound by [`to_params`](crate::Model::to_params)
+/// or any insertion method, and aren't expected in the schema by [`#[check]`](#check). They're recorded in [`ModelMeta::computed`](crate::ModelMeta::computed) instead.
+/// The derived `TryFrom<Vec<Value>>` implementation expects their values after those of the stored columns, mirroring `select_all`.
 /// 
-/// Example implementations for `PathBuf`:
 /// ```rust
-/// # use exemplar::*;
-/// # use std::path::{Path, PathBuf};
-/// # use rusqlite::types::ValueRef;
-/// pub fn bind_path(value: &Path) -> BindResult {
-///     use rusqlite::types::Value;
-///     use rusqlite::types::ToSqlOutput;
-///     
-///     // Depending on your program, it may make more sense
-///     // to error if a lossless conversion isn't possible.
-///     let str = value.to_string_lossy().into_owned();
-/// 
-///     Ok(ToSqlOutput::Owned(
-///         Value::Text(str)
-///     ))
+/// # use exemplar::Model;
+/// # use rusqlite::Connection;
+/// #[derive(Model)]
+/// #[table("users")]
+/// pub struct User {
+///     pub username: String,
+///     pub pwd: Vec<u8>,
+///     #[select_expr("LENGTH(pwd)")]
+///     pub pwd_len: i64,
 /// }
 /// 
-/// pub fn extr_path(value: &ValueRef) -> ExtrResult<PathBuf> {
-///     let path = value.as_str()?;
-///     let path = PathBuf::from(path);
+/// # fn main() -> rusqlite::Result<()> {
+/// let conn = Connection::open_in_memory()?;
+/// conn.execute_batch("CREATE TABLE users (username, pwd);")?;
 /// 
-///     Ok(path)
+/// User { username: "alice".to_owned(), pwd: b"hunter2".to_vec(), pwd_len: 0 }.insert(&conn)?;
+/// 
+/// assert_eq!(User::select_all(&conn)?[0].pwd_len, 7);
+/// # Ok(())
+/// # }
+/// ```
+/// 
+/// # Filters
+/// Marking a model with `#[filter]` generates a filter builder named after it (e.g. `PersonFilter` for `Person`), with the same visibility,
+/// created by an inherent `filter()` function. Without the attribute, neither is generated. For each field, the builder has one method per comparison: `<field>_eq`, `<field>_ne`,
+/// `<field>_lt`, `<field>_le`, `<field>_gt` and `<field>_ge`. Predicates are joined with `AND`, and `fetch` runs the resulting query:
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// # use rusqlite::Connection;
+/// #[derive(Model)]
+/// #[table("users")]
+/// #[filter]
+/// pub struct User {
+///     pub username: String,
+///     pub age: u16,
 /// }
+/// 
+/// # fn main() -> rusqlite::Result<()> {
+/// # let conn = Connection::open_in_memory()?;
+/// # conn.execute_batch("
+/// #     CREATE TABLE users (username, age);
+/// #     INSERT INTO users VALUES ('alice', 21), ('alice', 16), ('bob', 30);
+/// # ")?;
+/// let users = User::filter()
+///     .username_eq("alice")
+///     .age_gt(18)
+///     .fetch(&conn)?;
+/// 
+/// assert_eq!(users.len(), 1);
+/// # Ok(())
+/// # }
 /// ```
 /// 
-/// ### `#[column]`
-/// Usage:
-/// ```ignore
-/// #[column("name")]
-/// field: T,
+/// Values are bound as parameters, never interpolated. Methods take any [`ToSql`](rusqlite::ToSql) value, except for fields with a
+/// [`#[bind]`](#bindextr) function (or [`#[convert]`](#convert) rule) - these take a reference to the field's type, and apply the function.
+/// Equality is tested with `IS` rather than `=`, so `_eq` and `_ne` also match `NULL` correctly. Fields with [`#[bind_multi]`](#bind_multiextr_multi)
+/// and [`#[select_expr]`](#select_expr) fields have no filter methods.
+/// 
+/// The builder's `sql` method returns the generated statement, for debugging.
+/// 
+/// # Captured Errors
+/// A field typed `Result<T, E>` (including aliases like `rusqlite::Result<T>`) *captures* any error encountered while reading it, rather than failing the whole row.
+/// This enables "load what you can" tooling for potentially corrupt data:
+/// 
+/// ```rust
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("samples")]
+/// pub struct Sample {
+///     pub name: String,
+///     pub count: Result<i64, rusqlite::Error>,
+/// }
 /// ```
 /// 
-/// The `column` attribute overrides the column name Exemplar maps the annotated field to. By default, the field name is assumed to directly map to the underlying schema - `#[column]` is how you alter this behavior.
+/// The semantics are as follows:
+/// - The field is read as a `T` - including any `#[extr]` function or `#[convert]` rule, which should operate on `T`.
+/// - *Any* error reading the field (a missing column, a type mismatch or an extraction error) is converted with [`Into`] and stored as `Err`.
+///   `E` must therefore implement `From<rusqlite::Error>`.
+/// - Errors in other (non-`Result`) fields are still propagated as usual.
+/// - When writing, `Ok` values are bound as `T` (applying any `#[bind]` function.) An `Err` has nothing to write, so attempting it returns
+///   [`ToSqlConversionFailure`](rusqlite::Error::ToSqlConversionFailure), describing the captured error - `E` must implement [`Display`](std::fmt::Display).
 /// 
 /// # Notes
 /// Any type that derives [`Model`](crate::Model) also has an implementation of [`TryFrom<Row>`] derived, making models usable in some generic contexts.
+/// 
+/// [`TryFrom<Vec<Value>>`](https://docs.rs/rusqlite/latest/rusqlite/types/enum.Value.html) is also derived, which reconstructs a model from
+/// a vector of owned values in column order (applying any `#[extr]` functions.) This is useful for round-tripping models through non-SQLite
+/// intermediates, like a cache or wire format. If the length of the vector does not match the number of columns in the model, 
+/// [`Error::InvalidParameterCount`](https://docs.rs/rusqlite/latest/rusqlite/enum.Error.html#variant.InvalidParameterCount) is returned
+/// with the actual and expected lengths.
+/// 
+/// Additionally, the number of columns a model maps to is exposed as an inherent associated constant, `COLUMN_COUNT`, for use in `const` contexts:
+/// ```rust
+/// # use exemplar::Model;
+/// #[derive(Model)]
+/// #[table("people")]
+/// pub struct Person {
+///     pub name: String,
+///     pub age: u16,
+///     #[skip]
+///     pub cached_greeting: Option<String>,
+/// }
+/// 
+/// let buf = [0_u8; Person::COLUMN_COUNT];
+/// 
+/// assert_eq!(buf.len(), 2);
+/// ```
 pub use exemplar_proc_macro::Model;
 
+/// Derive macro for [`ToSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.ToSql.html) and [`FromSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.FromSql.html)
+/// on newtypes.
+/// 
+/// `SqlTransparent` can only be derived for tuple structs with exactly one field. The generated implementations delegate to that field,
+/// so the newtype is stored exactly like its inner value - and can be used anywhere a [`Model`](crate::Model) field is expected (including as a [`#[primary_key]`](Model#primary_key))
+/// or as a query parameter.
+/// 
+/// # Example
+/// ```rust
+/// # use exemplar::*;
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, SqlTransparent)]
+/// pub struct UserId(i64);
+/// 
+/// #[derive(Model)]
+/// #[table("users")]
+/// pub struct User {
+///     #[primary_key]
+///     pub id: UserId,
+///     pub name: String,
+/// }
+/// 
+/// # fn main() -> rusqlite::Result<()> {
+/// # let conn = rusqlite::Connection::open_in_memory()?;
+/// # conn.execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name);")?;
+/// User { id: UserId(1), name: "Alice".to_owned() }.insert(&conn)?;
+/// 
+/// let name: String = conn.query_row("SELECT name FROM users WHERE id = ?", [UserId(1)], |row| row.get(0))?;
+/// # assert_eq!(name, "Alice");
+/// # Ok(())
+/// # }
+/// ```
+/// 
+/// Generic newtypes are supported, provided the inner type implements both traits.
+/// For newtypes you don't control (and so can't derive on), see the [`#[transparent]`](Model#transparent) field attribute.
+pub use exemplar_proc_macro::SqlTransparent;
+
 /// Generate an "anonymous" record `struct` that implements `from_row`.
 /// 
 /// This is best used for deserializing rows from an ad-hoc query in a strongly typed manner. 
@@ -167,8 +925,46 @@ pub use exemplar_proc_macro::Model;
 ///     .map(|age| ...);
 /// ```
 /// 
+/// # Nested Records
+/// A field can hold another record, marked with `[nested]` after its type. Nested records are populated with their own `from_row`, 
+/// which makes it possible to compose record types for joined queries:
+/// 
+/// ```ignore
+/// record! {
+///     Name => Author,
+///     name => String,
+/// }
+/// 
+/// record! {
+///     Name => Post,
+///     title  => String,
+///     author => Author [nested],
+/// }
+/// 
+/// let mut get_posts = conn.prepare("SELECT posts.title, users.name FROM posts JOIN users ON posts.author = users.id")?;
+/// 
+/// get_posts
+///     .query_and_then([], Post::from_row)?
+///     .map(|post| ...);
+/// ```
+/// 
+/// When the joined tables share column names, look the columns up *by position* instead with `from_row_offset(row, offset)`, 
+/// which reads `COLUMN_COUNT` columns (in field order, recursing into nested records) starting at `offset`. 
+/// `Post::from_row_offset(row, 0)` would read `title` from column 0 and the author's `name` from column 1.
+/// 
 /// # Notes
 /// 
+/// Fields can be of any type implementing [`FromSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.FromSql.html) or [`SqlConvert`](crate::SqlConvert).
+/// That includes `Cow<'static, str>` (always extracting an owned string), which is handy for records used as keys in a `HashMap<Cow<str>, _>`:
+/// ```rust
+/// # use exemplar::*;
+/// # use std::borrow::Cow;
+/// record! {
+///     Name => Key,
+///     name => Cow<'static, str>,
+/// }
+/// ```
+/// 
 /// Doc comments (and other attributes) are supported:
 /// ```rust
 /// # use exemplar::*;
@@ -205,7 +1001,32 @@ pub use exemplar_proc_macro::Model;
 /// ```
 #[macro_export]
 macro_rules! record {
-    ($(#[$struct_doc:meta])* Name => $name:ident, $($(#[$field_doc:meta])* $fname:ident => $ftype:ty),* $(,)?) => {
+    (@read $row:ident, $idx:expr, $ftype:ty) => {{
+        #[allow(unused_imports)]
+        use $crate::{__ExtrFromSql as _, __ExtrConvert as _};
+
+        let idx = ::rusqlite::RowIndex::idx(&$idx, $row.as_ref())?;
+        (&$crate::__Extr::<$ftype>::NEW).__extr_row($row, idx)?
+    }};
+    (@get $row:ident, $fname:ident, $ftype:ty) => {
+        $crate::record!(@read $row, stringify!($fname), $ftype)
+    };
+    (@get $row:ident, $fname:ident, $ftype:ty, nested) => {
+        <$ftype>::from_row($row)?
+    };
+    (@get_at $row:ident, $offset:ident, $ftype:ty) => {{
+        let value = $crate::record!(@read $row, $offset, $ftype);
+        $offset += 1;
+        value
+    }};
+    (@get_at $row:ident, $offset:ident, $ftype:ty, nested) => {{
+        let value = <$ftype>::from_row_offset($row, $offset)?;
+        $offset += <$ftype>::COLUMN_COUNT;
+        value
+    }};
+    (@width $ftype:ty) => { 1 };
+    (@width $ftype:ty, nested) => { <$ftype>::COLUMN_COUNT };
+    ($(#[$struct_doc:meta])* Name => $name:ident, $($(#[$field_doc:meta])* $fname:ident => $ftype:ty $([$nested:ident])?),* $(,)?) => {
         $(#[$struct_doc])*
         /// 
         /// Automatically generated record type for storing query results.
@@ -214,9 +1035,21 @@ macro_rules! record {
         }
         
         impl $name {
+            /// The number of columns this record reads, including those of any nested records.
+            pub const COLUMN_COUNT: usize = 0 $(+ $crate::record!(@width $ftype $(, $nested)?))*;
+
             fn from_row(row: &::rusqlite::Row) -> ::rusqlite::Result<Self> {
                 Ok(Self {
-                    $($fname : row.get(stringify!($fname))?),*
+                    $($fname : $crate::record!(@get row, $fname, $ftype $(, $nested)?)),*
+                })
+            }
+
+            /// Attempt to extract an instance of `Self` from the provided [`Row`](::rusqlite::Row) *by position*,
+            /// reading [`COLUMN_COUNT`](Self::COLUMN_COUNT) columns starting at `offset`.
+            #[allow(unused_mut, unused_assignments)]
+            pub fn from_row_offset(row: &::rusqlite::Row, mut offset: usize) -> ::rusqlite::Result<Self> {
+                Ok(Self {
+                    $($fname : $crate::record!(@get_at row, offset, $ftype $(, $nested)?)),*
                 })
             }
         }
@@ -229,14 +1062,14 @@ macro_rules! record {
             }
         }
     };
-    ($($(#[$field_doc:meta])* $fname:ident => $ftype:ty),* $(,)?) => {
-        record!(Name => Record, $($(#[$field_doc])* $fname => $ftype),*);
+    ($($(#[$field_doc:meta])* $fname:ident => $ftype:ty $([$nested:ident])?),* $(,)?) => {
+        record!(Name => Record, $($(#[$field_doc])* $fname => $ftype $([$nested])?),*);
     };
 }
 
 /// Generate an SQL-compatible field-less `enum`.
 /// 
-/// SQL compatible means:
+/// SQL compatible means (by default, see [text storage](#text-storage)):
 /// - `#[repr(i*/u*)]`
 /// - Implements [`TryFrom<i64>`].
 /// - Implements [`ToSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.ToSql.html) and [`FromSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.FromSql.html).
@@ -269,24 +1102,73 @@ macro_rules! record {
 /// };
 /// ```
 /// 
-/// # Notes
-/// Explicit discriminants are *not* supported. Variants will always be implicitly numbered, in order of definition, from zero. 
+/// ## Text Storage
+/// Passing `Text` as the `Type` stores variants as their names (`TEXT`) instead of integers:
+/// 
+/// ```rust
+/// # use exemplar::sql_enum;
+/// sql_enum! {
+///     Name => Color,
+///     Type => Text,
+///     Red,
+///     Green,
+///     Blue,
+/// };
+/// 
+/// assert_eq!(Color::Red.to_string(), "Red");
+/// assert_eq!("Blue".parse::<Color>().unwrap(), Color::Blue);
+/// ```
+/// 
+/// In this mode, [`Display`](std::fmt::Display) and [`FromStr`](std::str::FromStr) are implemented instead of [`TryFrom<i64>`], along with
+/// an `as_str` method. Values must match a variant name exactly, unless the `CaseInsensitive` flag is set - in which case ASCII letters
+/// are compared case-insensitively (like SQLite's own case folding), so `"red"`, `"RED"` and `"Red"` are all accepted. (Values are always *written* using the variant name.)
+/// 
+/// ```rust
+/// # use exemplar::sql_enum;
+/// sql_enum! {
+///     Name => Color,
+///     Type => Text(CaseInsensitive),
+///     Red,
+///     Green,
+///     Blue,
+/// };
+/// 
+/// assert_eq!("rED".parse::<Color>().unwrap(), Color::Red);
+/// ```
+/// 
+/// ## Discriminants
+/// By default, variants are implicitly numbered in order of definition, from zero. Explicit discriminants are also supported
+/// (in integer mode), including negative ones when the `Type` is signed:
+/// 
+/// ```rust
+/// # use exemplar::sql_enum;
+/// sql_enum! {
+///     Name => Priority,
+///     Type => i8,
+///     Low = -1,
+///     Normal = 0,
+///     High = 1,
+/// };
+/// 
+/// assert_eq!(Priority::try_from(-1).unwrap(), Priority::Low);
+/// assert!(Priority::try_from(-2).is_err());
+/// ```
 /// 
-/// Concretely, this means that:
+/// Values are always stored as the discriminant widened to an `i64`, so negative discriminants round-trip unchanged.
+/// As with any Rust enum, a negative discriminant with an unsigned `Type` is rejected by the compiler:
 /// ```compile_fail
 /// # use exemplar::*;
 /// sql_enum! {
 ///     Name => Color,
-///     Red = 1,
-///     Green = 2,
-///     Blue = 3
+///     Type => u8,
+///     Red = -1,
+///     Green,
+///     Blue
 /// }
 /// ```
-/// ...will not compile.
 /// 
-/// <hr>
-/// 
-/// Doc comments (and other attributes, like derives) *are* supported:
+/// # Notes
+/// Doc comments (and other attributes, like derives) are supported:
 /// ```rust
 /// # use exemplar::sql_enum;
 /// sql_enum! {
@@ -304,14 +1186,81 @@ macro_rules! record {
 /// ```
 #[macro_export]
 macro_rules! sql_enum {
-    ($(#[$enum_doc:meta])* Name => $name:ident, Type => $disc:ty, $($(#[$variant_doc:meta])* $vname:ident),* $(,)?) => {
+    (@text $case_insensitive:literal, $(#[$enum_doc:meta])* Name => $name:ident, $($(#[$variant_doc:meta])* $vname:ident),* $(,)?) => {
         $(#[$enum_doc])*
-        #[repr($disc)]
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum $name {
             $($(#[$variant_doc])* $vname),*
         }
 
+        impl $name {
+            /// The name of this variant, as stored in the database.
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$vname => stringify!($vname)),*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::str::FromStr for $name {
+            type Err = ::rusqlite::types::FromSqlError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                $(
+                    if $crate::__sql_enum_matches(value, stringify!($vname), $case_insensitive) {
+                        return Ok(Self::$vname)
+                    }
+                )*
+
+                let msg = format!(
+                    "No variant in enum `{}` matches the value `{value}`",
+                    stringify!($name)
+                );
+
+                Err(::rusqlite::types::FromSqlError::Other(
+                    msg.into()
+                ))
+            }
+        }
+
+        #[automatically_derived]
+        impl ::rusqlite::ToSql for $name {
+            fn to_sql(&self) -> ::rusqlite::Result<::rusqlite::types::ToSqlOutput<'_>> {
+                let value = ::rusqlite::types::ValueRef::Text(self.as_str().as_bytes());
+                let value = ::rusqlite::types::ToSqlOutput::Borrowed(value);
+                Ok(value)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::rusqlite::types::FromSql for $name {
+            fn column_result(value: ::rusqlite::types::ValueRef<'_>) -> ::rusqlite::types::FromSqlResult<Self> {
+                value.as_str()?.parse()
+            }
+        }
+    };
+    ($(#[$enum_doc:meta])* Name => $name:ident, Type => Text, $($(#[$variant_doc:meta])* $vname:ident),* $(,)?) => {
+        sql_enum!(@text false, $(#[$enum_doc])* Name => $name, $($(#[$variant_doc])* $vname),*);
+    };
+    ($(#[$enum_doc:meta])* Name => $name:ident, Type => Text(CaseInsensitive), $($(#[$variant_doc:meta])* $vname:ident),* $(,)?) => {
+        sql_enum!(@text true, $(#[$enum_doc])* Name => $name, $($(#[$variant_doc])* $vname),*);
+    };
+    ($(#[$enum_doc:meta])* Name => $name:ident, Type => $disc:ty, $($(#[$variant_doc:meta])* $vname:ident $(= $vdisc:expr)?),* $(,)?) => {
+        $(#[$enum_doc])*
+        #[repr($disc)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_doc])* $vname $(= $vdisc)?),*
+        }
+
         #[automatically_derived]
         impl ::rusqlite::ToSql for $name {
             fn to_sql(&self) -> ::rusqlite::Result<::rusqlite::types::ToSqlOutput<'_>> {
@@ -353,11 +1302,51 @@ macro_rules! sql_enum {
             }
         }
     };
-    ($(#[$enum_doc:meta])* Name => $name:ident, $($(#[$variant_doc:meta])* $vname:ident),* $(,)?) => {
-        sql_enum!($(#[$enum_doc])* Name => $name, Type => i64, $($(#[$variant_doc])* $vname),*);
+    ($(#[$enum_doc:meta])* Name => $name:ident, $($(#[$variant_doc:meta])* $vname:ident $(= $vdisc:expr)?),* $(,)?) => {
+        sql_enum!($(#[$enum_doc])* Name => $name, Type => i64, $($(#[$variant_doc])* $vname $(= $vdisc)?),*);
     }
 }
 
+/// Create the tables for several [`Model`](crate::Model) types at once.
+/// 
+/// Each type's [`create_table_sql_untyped`](crate::ModelMeta::create_table_sql_untyped) statement is executed, in order, inside
+/// a savepoint - so either every table is created or none are. Evaluates to a [`rusqlite::Result<()>`](https://docs.rs/rusqlite/latest/rusqlite/type.Result.html).
+/// 
+/// Because a savepoint is used instead of a transaction, this works with plain connections and open transactions alike.
+/// 
+/// # Example
+/// ```rust
+/// # use exemplar::*;
+/// # use rusqlite::Connection;
+/// #[derive(Model)]
+/// #[table("users")]
+/// pub struct User {
+///     pub username: String,
+/// }
+/// 
+/// #[derive(Model)]
+/// #[table("posts")]
+/// pub struct Post {
+///     pub author: String,
+///     pub body: String,
+/// }
+/// 
+/// # fn main() -> rusqlite::Result<()> {
+/// let conn = Connection::open_in_memory()?;
+/// 
+/// create_tables!(&conn, User, Post)?;
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! create_tables {
+    ($conn:expr, $($model:ty),+ $(,)?) => {
+        $crate::__create_tables($conn, &[
+            $(<$model as $crate::Model>::metadata()),+
+        ])
+    };
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -409,4 +1398,88 @@ mod tests {
             Color::try_from(3).is_err()
         );
     }
-}
\ No newline at end of file
+
+    sql_enum! {
+        Name => Delta,
+        Type => i32,
+        Down = -1,
+        Flat = 0,
+        Up = 1,
+    }
+
+    #[test]
+    fn negative_discriminants() {
+        use rusqlite::types::{FromSql, ToSqlOutput, Value, ValueRef};
+        use rusqlite::ToSql;
+
+        assert_eq!(-1, Delta::Down as i64);
+
+        assert!(matches!(
+            Delta::Down.to_sql().unwrap(),
+            ToSqlOutput::Owned(Value::Integer(-1))
+        ));
+
+        assert_eq!(
+            Delta::Down,
+            Delta::column_result(ValueRef::Integer(-1)).unwrap()
+        );
+
+        assert_eq!(
+            Delta::Up,
+            Delta::try_from(1).unwrap()
+        );
+
+        assert!(Delta::try_from(-2).is_err());
+        assert!(Delta::try_from(i64::MIN).is_err());
+        assert!(Delta::try_from(u32::MAX as i64).is_err());
+        assert!(Delta::column_result(ValueRef::Integer(-2)).is_err());
+    }
+
+    sql_enum! {
+        Name => Shade,
+        Type => Text,
+        Light,
+        Dark,
+    }
+
+    sql_enum! {
+        Name => Hue,
+        Type => Text(CaseInsensitive),
+        Red,
+        Green,
+    }
+
+    #[test]
+    fn text() {
+        use rusqlite::types::{FromSql, ToSqlOutput, ValueRef};
+        use rusqlite::ToSql;
+
+        assert!(matches!(
+            Shade::Dark.to_sql().unwrap(),
+            ToSqlOutput::Borrowed(ValueRef::Text(b"Dark"))
+        ));
+
+        assert_eq!(
+            Shade::Light,
+            Shade::column_result(ValueRef::Text(b"Light")).unwrap()
+        );
+
+        assert!(Shade::column_result(ValueRef::Text(b"light")).is_err());
+        assert!(Shade::column_result(ValueRef::Integer(0)).is_err());
+    }
+
+    #[test]
+    fn text_case_insensitive() {
+        use rusqlite::types::{FromSql, ValueRef};
+
+        for value in ["red", "RED", "Red"] {
+            assert_eq!(
+                Hue::Red,
+                Hue::column_result(ValueRef::Text(value.as_bytes())).unwrap()
+            );
+        }
+
+        assert_eq!(Hue::Green.to_string(), "Green");
+        assert!("blue".parse::<Hue>().is_err());
+    }
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "array")]
+
+use anyhow::Result;
+
+use exemplar::Model;
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("users")]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[test]
+fn test_select_in() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    rusqlite::vtab::array::load_module(&conn)?;
+
+    conn.execute_batch("
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name);
+        INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob'), (3, 'Carol'), (4, 'Dave');
+    ")?;
+
+    let mut users = User::select_in(&conn, "id", &[4, 2, 7])?;
+    users.sort_by_key(|user| user.id);
+
+    assert_eq!(users, vec![
+        User { id: 2, name: "Bob".to_owned() },
+        User { id: 4, name: "Dave".to_owned() },
+    ]);
+
+    let ids: Vec<i64> = (1..=1000).collect();
+
+    assert_eq!(User::select_in(&conn, "id", &ids)?.len(), 4);
+    assert!(User::select_in::<i64>(&conn, "id", &[])?.is_empty());
+
+    assert!(matches!(
+        User::select_in(&conn, "id) OR (1", &[1]),
+        Err(rusqlite::Error::InvalidColumnName(_))
+    ));
+
+    Ok(())
+}
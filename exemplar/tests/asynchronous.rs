@@ -0,0 +1,145 @@
+#![cfg(feature = "blocking")]
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use exemplar::Model;
+use exemplar::asynchronous::{
+    AsyncConnection,
+    AsyncModel,
+    Offload,
+};
+
+use futures_lite::future::block_on;
+
+#[derive(Debug, Clone, PartialEq, Eq, Model)]
+#[table("people")]
+struct Person {
+    name: String,
+    age: u16,
+}
+
+#[test]
+fn test_insert_async() -> Result<()> {
+    use rusqlite::Connection;
+
+    block_on(async {
+        let conn = AsyncConnection::new(
+            Connection::open_in_memory()?
+        );
+
+        conn.call(|conn| conn.execute_batch("CREATE TABLE people (name, age);")).await?;
+
+        let alice = Person {
+            name: "Alice".to_owned(),
+            age: 21,
+        };
+
+        let bob = Person {
+            name: "Bob".to_owned(),
+            age: 90,
+        };
+
+        alice.clone().insert_async(&conn).await?;
+        bob.clone().insert_async(&conn.clone()).await?;
+
+        assert_eq!(
+            Person::select_all_async(&conn).await?,
+            vec![alice, bob]
+        );
+
+        Ok(())
+    })
+}
+
+/// Runs work inline, counting how many times it was asked to.
+#[derive(Default)]
+struct Inline(Arc<AtomicUsize>);
+
+impl Offload for Inline {
+    fn offload<F, T>(&self, f: F) -> impl Future<Output = T> + Send
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static
+    {
+        self.0.fetch_add(1, Ordering::Relaxed);
+
+        async move { f() }
+    }
+}
+
+#[test]
+fn test_custom_offload() -> Result<()> {
+    use rusqlite::Connection;
+
+    block_on(async {
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let conn = AsyncConnection::with_offload(
+            Connection::open_in_memory()?,
+            Inline(Arc::clone(&calls))
+        );
+
+        conn.call(|conn| conn.execute_batch("CREATE TABLE people (name, age);")).await?;
+
+        Person { name: "Alice".to_owned(), age: 21 }
+            .insert_async(&conn)
+            .await?;
+
+        assert_eq!(Person::select_all_async(&conn).await?.len(), 1);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        Ok(())
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Model)]
+#[table("pets")]
+struct Pet {
+    name: String,
+    species: String,
+}
+
+#[test]
+fn test_dyn_insert_async() -> Result<()> {
+    use exemplar::asynchronous::DynModelExt;
+    use rusqlite::Connection;
+
+    block_on(async {
+        let conn = AsyncConnection::new(
+            Connection::open_in_memory()?
+        );
+
+        conn.call(|conn| conn.execute_batch("
+            CREATE TABLE people (name, age);
+            CREATE TABLE pets (name, species);
+        ")).await?;
+
+        let alice = Person { name: "Alice".to_owned(), age: 21 };
+        let rex = Pet { name: "Rex".to_owned(), species: "Dog".to_owned() };
+
+        let models: Vec<Box<dyn Model + Send>> = vec![
+            Box::new(alice.clone()),
+            Box::new(rex.clone()),
+        ];
+
+        let tables: Vec<_> = models
+            .iter()
+            .map(|model| model.metadata_dyn().table)
+            .collect();
+
+        assert_eq!(tables, ["people", "pets"]);
+
+        for model in models {
+            model.insert_async(&conn).await?;
+        }
+
+        assert_eq!(Person::select_all_async(&conn).await?, vec![alice]);
+        assert_eq!(Pet::select_all_async(&conn).await?, vec![rex]);
+
+        Ok(())
+    })
+}
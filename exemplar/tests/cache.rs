@@ -0,0 +1,95 @@
+use anyhow::Result;
+
+use rusqlite::Connection;
+
+use exemplar::Model;
+use exemplar::cache::ModelCache;
+
+#[derive(Debug, Clone, PartialEq, Eq, Model)]
+#[table("users")]
+struct User {
+    #[primary_key]
+    id: i64,
+    name: String,
+}
+
+#[derive(Debug, Model)]
+#[table("memberships")]
+#[composite_key]
+struct Membership {
+    #[primary_key]
+    user: i64,
+    #[primary_key]
+    team: String,
+}
+
+fn setup() -> Result<Connection> {
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name);
+        INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob'), (3, 'Carol');
+    ")?;
+
+    Ok(conn)
+}
+
+#[test]
+fn test_cache_hit_miss() -> Result<()> {
+    let conn = setup()?;
+    let mut cache = ModelCache::<User, i64>::new(8);
+
+    // Miss - loaded from the database.
+    assert_eq!(cache.get(&conn, 1)?.unwrap().name, "Alice");
+    assert!(cache.contains(&1));
+
+    conn.execute("UPDATE users SET name = 'Alicia' WHERE id = 1", [])?;
+
+    // Hit - served from memory, so the update isn't visible...
+    assert_eq!(cache.get(&conn, 1)?.unwrap().name, "Alice");
+
+    // ...until the entry is invalidated.
+    assert_eq!(cache.invalidate(&1).unwrap().name, "Alice");
+    assert_eq!(cache.get(&conn, 1)?.unwrap().name, "Alicia");
+
+    // Missing rows aren't cached.
+    assert!(cache.get(&conn, 4)?.is_none());
+    assert_eq!(cache.len(), 1);
+
+    cache.clear();
+    assert!(cache.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_eviction() -> Result<()> {
+    let conn = setup()?;
+    let mut cache = ModelCache::<User, i64>::new(2);
+
+    cache.get(&conn, 1)?;
+    cache.get(&conn, 2)?;
+
+    // Using 1 again makes 2 the least recently used...
+    cache.get(&conn, 1)?;
+
+    // ...so it's evicted to make room for 3.
+    cache.get(&conn, 3)?;
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains(&1));
+    assert!(!cache.contains(&2));
+    assert!(cache.contains(&3));
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_composite_key() -> Result<()> {
+    let conn = setup()?;
+    let mut cache = ModelCache::<Membership, i64>::new(8);
+
+    assert!(cache.get(&conn, 1).is_err());
+
+    Ok(())
+}
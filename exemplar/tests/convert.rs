@@ -0,0 +1,432 @@
+use std::path::PathBuf;
+use std::time::{
+    Duration,
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use anyhow::Result;
+
+use exemplar::Model;
+
+use rusqlite::Connection;
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("flags")]
+struct Flag {
+    name: String,
+    #[bind(exemplar::convert::bind_strict_bool)]
+    #[extr(exemplar::convert::extr_strict_bool)]
+    enabled: bool,
+}
+
+#[test]
+fn test_strict_bool() -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE flags (name, enabled);
+    ")?;
+
+    let on = Flag {
+        name: "on".to_owned(),
+        enabled: true,
+    };
+
+    let off = Flag {
+        name: "off".to_owned(),
+        enabled: false,
+    };
+
+    on.insert(&conn)?;
+    off.insert(&conn)?;
+
+    let stored: Vec<i64> = conn
+        .prepare("SELECT enabled FROM flags ORDER BY name ASC")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(stored, vec![0, 1]);
+
+    let mut stmt = conn.prepare("SELECT * FROM flags ORDER BY name ASC")?;
+    let mut iter = stmt.query_and_then([], Flag::from_row)?;
+
+    assert_eq!(off, iter.next().unwrap()?);
+    assert_eq!(on, iter.next().unwrap()?);
+
+    drop(iter);
+
+    conn.execute("INSERT INTO flags VALUES ('corrupt', 2)", [])?;
+
+    let corrupt = conn.query_row(
+        "SELECT * FROM flags WHERE name = 'corrupt'",
+        [],
+        Flag::from_row
+    );
+
+    assert!(corrupt.is_err());
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("events")]
+struct Event {
+    name: String,
+    #[bind(exemplar::convert::bind_unix_secs)]
+    #[extr(exemplar::convert::extr_unix_secs)]
+    at: SystemTime,
+}
+
+#[test]
+fn test_unix_secs() -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE events (name, at INTEGER);
+    ")?;
+
+    let launch = Event {
+        name: "launch".to_owned(),
+        at: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+    };
+
+    let moon = Event {
+        name: "moon".to_owned(),
+        at: UNIX_EPOCH - Duration::from_secs(16_000_000),
+    };
+
+    launch.insert(&conn)?;
+    moon.insert(&conn)?;
+
+    let stored: Vec<i64> = conn
+        .prepare("SELECT at FROM events ORDER BY name ASC")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(stored, vec![1_700_000_000, -16_000_000]);
+
+    let mut stmt = conn.prepare("SELECT * FROM events ORDER BY name ASC")?;
+    let mut iter = stmt.query_and_then([], Event::from_row)?;
+
+    assert_eq!(launch, iter.next().unwrap()?);
+    assert_eq!(moon, iter.next().unwrap()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_unix_secs_truncation() -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE events (name, at INTEGER);
+    ")?;
+
+    let precise = Event {
+        name: "precise".to_owned(),
+        at: UNIX_EPOCH + Duration::from_millis(1_500),
+    };
+
+    precise.insert(&conn)?;
+
+    let loaded = conn.query_row("SELECT * FROM events", [], Event::from_row)?;
+
+    assert_eq!(loaded.at, UNIX_EPOCH + Duration::from_secs(1));
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("timings")]
+struct Timing {
+    name: String,
+    #[bind(exemplar::convert::bind_duration)]
+    #[extr(exemplar::convert::extr_duration)]
+    elapsed: Duration,
+}
+
+#[test]
+fn test_duration() -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE timings (name, elapsed INTEGER);
+    ")?;
+
+    let query = Timing {
+        name: "query".to_owned(),
+        elapsed: Duration::new(2, 123_456_789),
+    };
+
+    let zero = Timing {
+        name: "zero".to_owned(),
+        elapsed: Duration::ZERO,
+    };
+
+    query.insert(&conn)?;
+    zero.insert(&conn)?;
+
+    let stored: Vec<i64> = conn
+        .prepare("SELECT elapsed FROM timings ORDER BY name ASC")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(stored, vec![2_123_456_789, 0]);
+
+    let mut stmt = conn.prepare("SELECT * FROM timings ORDER BY name ASC")?;
+    let mut iter = stmt.query_and_then([], Timing::from_row)?;
+
+    // Sub-second precision survives the round trip.
+    assert_eq!(query, iter.next().unwrap()?);
+    assert_eq!(zero, iter.next().unwrap()?);
+
+    drop(iter);
+
+    let forever = Timing {
+        name: "forever".to_owned(),
+        elapsed: Duration::MAX,
+    };
+
+    assert!(forever.insert(&conn).is_err());
+
+    conn.execute("INSERT INTO timings VALUES ('negative', -1)", [])?;
+
+    let negative = conn.query_row(
+        "SELECT * FROM timings WHERE name = 'negative'",
+        [],
+        Timing::from_row
+    );
+
+    assert!(negative.is_err());
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Model)]
+#[table("readings")]
+struct Reading {
+    sensor: String,
+    #[bind(exemplar::convert::bind_f32)]
+    #[extr(exemplar::convert::extr_f32)]
+    value: f32,
+}
+
+#[test]
+fn test_f32() -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE readings (sensor, value REAL);
+    ")?;
+
+    let reading = Reading {
+        sensor: "thermometer".to_owned(),
+        value: 21.7,
+    };
+
+    reading.insert(&conn)?;
+
+    let loaded = conn.query_row("SELECT * FROM readings", [], Reading::from_row)?;
+
+    assert_eq!(reading, loaded);
+
+    conn.execute("UPDATE readings SET value = 0.1", [])?;
+
+    let loaded = conn.query_row("SELECT * FROM readings", [], Reading::from_row)?;
+
+    assert!((loaded.value - 0.1).abs() <= f32::EPSILON);
+
+    conn.execute("UPDATE readings SET value = 3", [])?;
+
+    let loaded = conn.query_row("SELECT * FROM readings", [], Reading::from_row)?;
+
+    assert_eq!(loaded.value, 3.0);
+
+    conn.execute("UPDATE readings SET value = 1e300", [])?;
+
+    let loaded = conn.query_row("SELECT * FROM readings", [], Reading::from_row);
+
+    assert!(loaded.is_err());
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("grades")]
+struct Grade {
+    student: String,
+    #[bind(exemplar::convert::bind_char)]
+    #[extr(exemplar::convert::extr_char)]
+    letter: char,
+}
+
+#[test]
+fn test_char() -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE grades (student, letter TEXT);
+    ")?;
+
+    let alice = Grade {
+        student: "Alice".to_owned(),
+        letter: 'A',
+    };
+
+    let bob = Grade {
+        student: "Bob".to_owned(),
+        letter: 'ß',
+    };
+
+    alice.insert(&conn)?;
+    bob.insert(&conn)?;
+
+    let mut stmt = conn.prepare("SELECT * FROM grades ORDER BY student ASC")?;
+    let mut iter = stmt.query_and_then([], Grade::from_row)?;
+
+    assert_eq!(alice, iter.next().unwrap()?);
+    assert_eq!(bob, iter.next().unwrap()?);
+
+    drop(iter);
+
+    conn.execute("INSERT INTO grades VALUES ('Carol', 'A+'), ('Dave', '')", [])?;
+
+    let carol = conn.query_row("SELECT * FROM grades WHERE student = 'Carol'", [], Grade::from_row);
+    let dave = conn.query_row("SELECT * FROM grades WHERE student = 'Dave'", [], Grade::from_row);
+
+    assert!(carol.is_err());
+    assert!(dave.is_err());
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("codes")]
+struct Code {
+    #[extr(exemplar::convert::extr_trimmed_string)]
+    code: String,
+    #[extr(exemplar::convert::extr_trimmed_string, lift)]
+    note: Option<String>,
+}
+
+#[test]
+fn test_trimmed_string() -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE codes (code TEXT, note TEXT);
+        INSERT INTO codes VALUES ('AB12      ', '  padded both ways\t\n'), ('  CD34', NULL), ('  ', '');
+    ")?;
+
+    let codes = Code::select_all(&conn)?;
+
+    assert_eq!(codes, vec![
+        Code { code: "AB12".to_owned(), note: Some("padded both ways".to_owned()) },
+        Code { code: "CD34".to_owned(), note: None },
+        Code { code: String::new(), note: Some(String::new()) },
+    ]);
+
+    // Non-TEXT values are rejected.
+    conn.execute_batch("INSERT INTO codes VALUES (X'4142', NULL);")?;
+
+    assert!(Code::select_all(&conn).is_err());
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("files")]
+struct File {
+    #[bind(exemplar::convert::bind_path)]
+    #[extr(exemplar::convert::extr_path)]
+    path: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("files")]
+struct LossyFile {
+    #[bind(exemplar::convert::bind_path_lossy)]
+    #[extr(exemplar::convert::extr_path_lossy)]
+    path: PathBuf,
+}
+
+#[test]
+fn test_path() -> Result<()> {
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE files (path TEXT);
+    ")?;
+
+    let file = File {
+        path: "/var/home/alice/ünïcödé.txt".into()
+    };
+
+    let lossy = LossyFile {
+        path: "/var/home/bob/notes.txt".into()
+    };
+
+    file.insert(&conn)?;
+    lossy.insert(&conn)?;
+
+    let mut stmt = conn.prepare("SELECT * FROM files ORDER BY path ASC")?;
+    let mut iter = stmt.query_and_then([], File::from_row)?;
+
+    assert_eq!(file, iter.next().unwrap()?);
+    assert_eq!(lossy.path, iter.next().unwrap()?.path);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_path_non_utf8() -> Result<()> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE files (path TEXT);
+    ")?;
+
+    let path = PathBuf::from(
+        OsStr::from_bytes(b"/tmp/invalid-\xFF.txt")
+    );
+
+    let strict = File {
+        path: path.clone()
+    };
+
+    assert!(strict.insert(&conn).is_err());
+
+    let lossy = LossyFile {
+        path
+    };
+
+    lossy.insert(&conn)?;
+
+    let loaded = conn.query_row("SELECT * FROM files", [], LossyFile::from_row)?;
+
+    assert_eq!(loaded.path, PathBuf::from("/tmp/invalid-\u{FFFD}.txt"));
+
+    conn.execute("UPDATE files SET path = CAST(x'2F746D702F6E6F742DFF2E747874' AS TEXT)", [])?;
+
+    assert!(conn.query_row("SELECT * FROM files", [], File::from_row).is_err());
+
+    let loaded = conn.query_row("SELECT * FROM files", [], LossyFile::from_row)?;
+
+    assert_eq!(loaded.path, PathBuf::from("/tmp/not-\u{FFFD}.txt"));
+
+    Ok(())
+}
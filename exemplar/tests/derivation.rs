@@ -8,11 +8,15 @@ use exemplar::{
     ExtrResult
 };
 
-use rusqlite::types::ValueRef;
+use rusqlite::types::{
+    FromSqlError,
+    ValueRef
+};
 
 // Simple case
 #[derive(Debug, PartialEq, Eq, Model)]
 #[table("people")]
+#[filter]
 struct Person {
     name: String,
     age: u16,
@@ -61,6 +65,35 @@ fn test_person() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_person_uncached() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+    ")?;
+
+    let alice = Person {
+        name: "Alice".to_owned(),
+        age: 21,
+        alive: true
+    };
+
+    alice.insert_uncached(&conn)?;
+
+    let mut stmt = conn.prepare("SELECT * FROM people")?;
+
+    let mut iter = stmt.query_and_then([], Person::from_row)?;
+
+    assert_eq!(alice, iter.next().unwrap()?);
+    assert!(iter.next().is_none());
+
+    Ok(())
+}
+
 #[test]
 fn test_person_metadata() {
     let alice = Person {
@@ -75,12 +108,53 @@ fn test_person_metadata() {
     assert_eq!(meta.table, "people");
     assert_eq!(meta.fields, &["name", "age", "alive"]);
     assert_eq!(meta.columns, &["name", "age", "alive"]);
+    assert!(meta.primary_key.is_empty());
+}
+
+// Single column case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("tags")]
+struct Tag {
+    #[column("label")]
+    name: String,
+}
+
+#[test]
+fn test_tag() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE tags (label);
+    ")?;
+
+    let tag = Tag {
+        name: "rust".to_owned()
+    };
+
+    tag.insert(&conn)?;
+    tag.insert_uncached(&conn)?;
+
+    let mut stmt = conn.prepare("SELECT * FROM tags")?;
+
+    let mut iter = stmt.query_and_then([], Tag::from_row)?;
+
+    assert_eq!(tag, iter.next().unwrap()?);
+    assert_eq!(tag, iter.next().unwrap()?);
+    assert!(iter.next().is_none());
+
+    assert_eq!(Tag::metadata().columns, &["label"]);
+
+    Ok(())
 }
 
 // Complicated case
 #[derive(Debug, PartialEq, Eq, Model)]
 #[table("users")]
 #[check("schema.sql")]
+#[filter]
 struct User {
     username: String,
     #[bind(bind_path)]
@@ -144,6 +218,54 @@ fn test_user() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_user_shared() -> Result<()> {
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::thread;
+
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(
+        include_str!("schema.sql")
+    )?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    alice.insert(&conn)?;
+
+    let mut stmt = conn.prepare("SELECT * FROM users")?;
+
+    let users: Vec<Arc<User>> = stmt
+        .query_and_then([], User::from_row_arc)?
+        .collect::<Result<_, _>>()?;
+
+    let shared = Arc::clone(&users[0]);
+
+    let username = thread::spawn(move || shared.username.clone())
+        .join()
+        .unwrap();
+
+    assert_eq!(username, "Alice");
+    assert_eq!(alice, *users[0]);
+    assert_eq!(Arc::strong_count(&users[0]), 1);
+
+    let users: Vec<Rc<User>> = stmt
+        .query_and_then([], User::from_row_rc)?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(alice, *users[0]);
+
+    Ok(())
+}
+
 #[test]
 fn test_user_metadata() {
     let alice = User {
@@ -158,4 +280,3626 @@ fn test_user_metadata() {
     assert_eq!(meta.table, "users");
     assert_eq!(meta.fields, &["username", "home_dir", "password"]);
     assert_eq!(meta.columns, &["username", "home_dir", "pwd"]);
-}
\ No newline at end of file
+}
+
+// Documented/attribute-laden case
+/// A user account.
+/// 
+/// Doc comments on the struct and its fields should be ignored by the derive.
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("users")]
+#[check("schema.sql")]
+struct DocumentedUser {
+    /// The user's name.
+    /// 
+    /// This doc comment spans multiple lines, and includes `#[column("not_a_column")]`.
+    #[allow(dead_code)]
+    username: String,
+    #[doc = "The user's home directory."]
+    #[bind(bind_path)]
+    #[allow(clippy::all)]
+    #[extr(extr_path)]
+    /// Doc comments interleaved with other attributes.
+    home_dir: PathBuf,
+    /// The user's password.
+    #[cfg_attr(test, allow(unused))]
+    #[column("pwd")]
+    #[allow(dead_code)]
+    password: Vec<u8>,
+}
+
+#[test]
+fn test_documented_user() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(
+        include_str!("schema.sql")
+    )?;
+
+    let alice = DocumentedUser {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    alice.insert(&conn)?;
+
+    let mut stmt = conn.prepare("SELECT * FROM users")?;
+
+    let mut iter = stmt.query_and_then([], DocumentedUser::from_row)?;
+
+    assert_eq!(alice, iter.next().unwrap()?);
+
+    let meta = DocumentedUser::metadata();
+
+    assert_eq!(meta.fields, &["username", "home_dir", "password"]);
+    assert_eq!(meta.columns, &["username", "home_dir", "pwd"]);
+
+    Ok(())
+}
+
+// Named extraction case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("users")]
+struct NamedUser {
+    username: String,
+    #[bind(bind_path)]
+    #[extr_named(extr_path_named)]
+    home_dir: PathBuf,
+    #[column("pwd")]
+    password: Vec<u8>,
+}
+
+pub fn extr_path_named(value: &ValueRef, column: &str) -> ExtrResult<PathBuf> {
+    let path = value.as_str().map_err(|_| {
+        FromSqlError::Other(
+            format!("column `{column}` does not contain a valid path").into()
+        )
+    })?;
+
+    Ok(PathBuf::from(path))
+}
+
+#[test]
+fn test_named_user() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(
+        include_str!("schema.sql")
+    )?;
+
+    let alice = NamedUser {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    alice.insert(&conn)?;
+
+    let loaded = conn.query_row("SELECT * FROM users", [], NamedUser::from_row)?;
+
+    assert_eq!(alice, loaded);
+
+    conn.execute("UPDATE users SET home_dir = 42", [])?;
+
+    let err = conn
+        .query_row("SELECT * FROM users", [], NamedUser::from_row)
+        .unwrap_err();
+
+    assert!(err.to_string().contains("column `home_dir`"));
+
+    Ok(())
+}
+
+// Skipped field case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("people")]
+struct CachedPerson {
+    name: String,
+    age: u16,
+    #[skip]
+    greeting: Option<String>,
+    alive: bool,
+}
+
+#[test]
+fn test_column_count() {
+    assert_eq!(Person::COLUMN_COUNT, 3);
+    assert_eq!(Tag::COLUMN_COUNT, 1);
+    assert_eq!(User::COLUMN_COUNT, 3);
+    assert_eq!(CachedPerson::COLUMN_COUNT, 3);
+
+    assert_eq!(CachedPerson::COLUMN_COUNT, CachedPerson::metadata().columns.len());
+
+    const BUF: [&str; CachedPerson::COLUMN_COUNT] = ["name", "age", "alive"];
+
+    assert_eq!(&BUF, CachedPerson::metadata().columns);
+}
+
+#[test]
+fn test_column_order() -> Result<()> {
+    use rusqlite::Connection;
+
+    let meta = CachedPerson::metadata();
+
+    assert_eq!(meta.fields, &["name", "age", "alive"]);
+    assert_eq!(meta.columns, &["name", "age", "alive"]);
+    assert_eq!(meta.create_table_sql_untyped(), "CREATE TABLE people (name, age, alive);");
+    assert_eq!(meta.insert_sql(exemplar::OnConflict::Abort), "INSERT INTO people (name, age, alive) VALUES(:name, :age, :alive);");
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(&meta.create_table_sql_untyped())?;
+    conn.execute_batch("INSERT INTO people VALUES ('Alice', 21, 1);")?;
+
+    let declared: Vec<String> = conn
+        .prepare("SELECT * FROM people")?
+        .column_names()
+        .into_iter()
+        .map(str::to_owned)
+        .collect();
+
+    assert_eq!(declared, meta.columns);
+
+    // Reading unnamed columns positionally agrees with the declared order.
+    let positional = conn.query_row(
+        "SELECT name AS a, age AS b, alive AS c FROM people",
+        [],
+        PositionalPerson::from_row
+    )?;
+
+    assert_eq!(positional, PositionalPerson {
+        name: "Alice".to_owned(),
+        age: 21,
+        alive: true,
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_cached_person() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+    ")?;
+
+    let alice = CachedPerson {
+        name: "Alice".to_owned(),
+        age: 21,
+        greeting: Some("Hello, Alice!".to_owned()),
+        alive: true,
+    };
+
+    alice.insert(&conn)?;
+
+    let loaded = conn.query_row("SELECT * FROM people", [], CachedPerson::from_row)?;
+
+    assert_eq!(loaded.name, "Alice");
+    assert_eq!(loaded.age, 21);
+    assert!(loaded.alive);
+    assert_eq!(loaded.greeting, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_try_from_values() -> Result<()> {
+    use rusqlite::types::Value;
+
+    let values = vec![
+        Value::Text("Alice".to_owned()),
+        Value::Text("/var/home/alice".to_owned()),
+        Value::Blob(b"hunter2".to_vec()),
+    ];
+
+    let alice = User::try_from(values)?;
+
+    assert_eq!(alice, User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    });
+
+    let values = vec![
+        Value::Text("Bob".to_owned()),
+        Value::Integer(90),
+        Value::Integer(0),
+    ];
+
+    let bob = CachedPerson::try_from(values)?;
+
+    assert_eq!(bob.name, "Bob");
+    assert_eq!(bob.age, 90);
+    assert!(!bob.alive);
+    assert_eq!(bob.greeting, None);
+
+    let short = vec![
+        Value::Text("Carol".to_owned()),
+    ];
+
+    assert!(matches!(
+        User::try_from(short),
+        Err(rusqlite::Error::InvalidParameterCount(1, 3))
+    ));
+
+    let mistyped = vec![
+        Value::Text("Dave".to_owned()),
+        Value::Integer(42),
+        Value::Blob(vec![]),
+    ];
+
+    assert!(User::try_from(mistyped).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_from_value_map() -> Result<()> {
+    use std::collections::HashMap;
+    use rusqlite::types::Value;
+
+    let mut map = HashMap::from([
+        ("username".to_owned(), Value::Text("Alice".to_owned())),
+        ("home_dir".to_owned(), Value::Text("/var/home/alice".to_owned())),
+        ("pwd".to_owned(), Value::Blob(b"hunter2".to_vec())),
+        ("unrelated".to_owned(), Value::Null),
+    ]);
+
+    let alice = User::from_value_map(&map)?;
+
+    assert_eq!(alice, User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    });
+
+    // Keys are column names, not field names.
+    let password = map.remove("pwd").unwrap();
+    map.insert("password".to_owned(), password);
+
+    assert!(matches!(
+        User::from_value_map(&map),
+        Err(rusqlite::Error::InvalidColumnName(name)) if name == "pwd"
+    ));
+
+    let map = HashMap::from([
+        ("name".to_owned(), Value::Text("Bob".to_owned())),
+        ("age".to_owned(), Value::Integer(90)),
+        ("alive".to_owned(), Value::Integer(0)),
+    ]);
+
+    let bob = CachedPerson::from_value_map(&map)?;
+
+    assert_eq!(bob.name, "Bob");
+    assert_eq!(bob.age, 90);
+    assert!(!bob.alive);
+    assert_eq!(bob.greeting, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_create_table_sql_untyped() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(
+        &User::metadata().create_table_sql_untyped()
+    )?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    alice.insert(&conn)?;
+
+    let loaded = conn.query_row("SELECT * FROM users", [], User::from_row)?;
+
+    assert_eq!(alice, loaded);
+
+    Ok(())
+}
+
+// Generic conversion case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("hosts")]
+struct Host {
+    name: String,
+    #[bind(bind_display)]
+    #[extr(extr_from_str)]
+    addr: std::net::Ipv4Addr,
+    #[bind(bind_display::<std::net::Ipv6Addr>)]
+    #[extr(extr_from_str::<std::net::Ipv6Addr>)]
+    addr_v6: std::net::Ipv6Addr,
+    #[bind(bind_impl_display)]
+    #[extr(extr_from_str)]
+    port: std::num::NonZeroU16,
+}
+
+pub fn bind_display<T: std::fmt::Display>(value: &T) -> BindResult {
+    use rusqlite::types::Value;
+    use rusqlite::types::ToSqlOutput;
+
+    Ok(ToSqlOutput::Owned(
+        Value::Text(value.to_string())
+    ))
+}
+
+pub fn bind_impl_display(value: &impl std::fmt::Display) -> BindResult {
+    bind_display(value)
+}
+
+pub fn extr_from_str<T>(value: &ValueRef) -> ExtrResult<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static
+{
+    value
+        .as_str()?
+        .parse()
+        .map_err(|err| FromSqlError::Other(Box::new(err)))
+}
+
+#[test]
+fn test_host() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE hosts (name, addr, addr_v6, port);
+    ")?;
+
+    let localhost = Host {
+        name: "localhost".to_owned(),
+        addr: std::net::Ipv4Addr::LOCALHOST,
+        addr_v6: std::net::Ipv6Addr::LOCALHOST,
+        port: std::num::NonZeroU16::new(8080).unwrap(),
+    };
+
+    localhost.insert(&conn)?;
+
+    let (addr, port): (String, String) = conn.query_row(
+        "SELECT addr, port FROM hosts",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?))
+    )?;
+
+    assert_eq!(addr, "127.0.0.1");
+    assert_eq!(port, "8080");
+
+    let loaded = conn.query_row("SELECT * FROM hosts", [], Host::from_row)?;
+
+    assert_eq!(localhost, loaded);
+
+    Ok(())
+}
+
+// Projection case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("users")]
+#[check(inline = "CREATE TABLE users (username);")]
+struct UserSummary {
+    username: String,
+}
+
+// Identifier case folding (identifiers are never quoted)
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("Badges")]
+struct Badge {
+    #[column("Title")]
+    title: String,
+    level: u8,
+}
+
+#[test]
+fn test_identifier_case_folding() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    assert_eq!(
+        Badge::metadata().insert_sql(exemplar::OnConflict::Abort),
+        "INSERT INTO Badges (Title, level) VALUES(:Title, :level);"
+    );
+
+    // Unquoted identifiers fold case, so the differently-cased schema still matches.
+    conn.execute_batch("CREATE TABLE badges (title, LEVEL);")?;
+
+    let badge = Badge { title: "Pioneer".to_owned(), level: 3 };
+
+    badge.insert(&conn)?;
+
+    assert_eq!(Badge::select_all(&conn)?, vec![badge]);
+
+    Ok(())
+}
+
+// WITHOUT ROWID case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("labels")]
+#[check(inline = "CREATE TABLE labels (name TEXT PRIMARY KEY, color) without rowid;", without_rowid)]
+struct Label {
+    #[primary_key]
+    name: String,
+    color: String,
+}
+
+#[test]
+fn test_check_without_rowid() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE labels (name TEXT PRIMARY KEY, color) WITHOUT ROWID;")?;
+
+    exemplar::__check_without_rowid(&conn, "labels");
+
+    Label { name: "urgent".to_owned(), color: "red".to_owned() }.insert(&conn)?;
+
+    assert_eq!(Label::select_all(&conn)?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "has a rowid")]
+fn test_check_without_rowid_mismatch() {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    // "WITHOUT ROWID" inside the column definitions doesn't count.
+    conn.execute_batch("CREATE TABLE labels (name TEXT PRIMARY KEY, color DEFAULT 'without rowid');")
+        .unwrap();
+
+    exemplar::__check_without_rowid(&conn, "labels");
+}
+
+// The generated check test also confirms the index exists.
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("subscribers")]
+#[check(
+    inline = "CREATE TABLE subscribers (email, name); CREATE UNIQUE INDEX idx_subscribers_email ON subscribers (email);",
+    index = "idx_subscribers_email"
+)]
+struct Subscriber {
+    email: String,
+    name: String,
+}
+
+#[test]
+fn test_check_index() {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE subscribers (email, name);
+        CREATE UNIQUE INDEX idx_subscribers_email ON subscribers (email);
+    ").unwrap();
+
+    exemplar::__check_index(&conn, "subscribers", "idx_subscribers_email");
+
+    let subscriber = Subscriber { email: "alice@example.com".to_owned(), name: "Alice".to_owned() };
+
+    subscriber.insert(&conn).unwrap();
+
+    // The unique index rejects a second row with the same email.
+    assert!(subscriber.insert(&conn).is_err());
+}
+
+#[test]
+#[should_panic(expected = "doesn't exist")]
+fn test_check_index_missing() {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    // An index of the same name on another table doesn't count.
+    conn.execute_batch("
+        CREATE TABLE subscribers (email, name);
+        CREATE TABLE unsubscribers (email);
+        CREATE UNIQUE INDEX idx_subscribers_email ON unsubscribers (email);
+    ").unwrap();
+
+    exemplar::__check_index(&conn, "subscribers", "idx_subscribers_email");
+}
+
+// The generated check only passes if both migrations are applied, in order - the second alters the table created by the first.
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("releases")]
+#[check(migrations = "migrations/")]
+struct Release {
+    version: String,
+    notes: Option<String>,
+}
+
+#[test]
+fn test_check_migrations() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    // Migrations are read relative to this file, in order of file name.
+    let migrations = exemplar::__read_migrations(file!(), env!("CARGO_MANIFEST_DIR"), "migrations/");
+
+    assert_eq!(migrations, [
+        include_str!("migrations/0001_create_releases.sql"),
+        include_str!("migrations/0002_add_notes.sql"),
+    ]);
+
+    for sql in &migrations {
+        conn.execute_batch(sql)?;
+    }
+
+    let release = Release {
+        version: "1.0.0".to_owned(),
+        notes: Some("Initial release".to_owned()),
+    };
+
+    release.insert(&conn)?;
+
+    assert_eq!(Release::select_all(&conn)?, [release]);
+
+    Ok(())
+}
+
+#[test]
+fn test_user_summary() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(
+        include_str!("schema.sql")
+    )?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    alice.insert(&conn)?;
+
+    let summaries = UserSummary::select_all(&conn)?;
+
+    assert_eq!(summaries, vec![UserSummary { username: "Alice".to_owned() }]);
+
+    let users = User::select_all(&conn)?;
+
+    assert_eq!(users, vec![alice]);
+
+    Ok(())
+}
+
+#[test]
+fn test_user_summary_projection() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    // The `home_dir` and `pwd` columns of this view raise an integer overflow
+    // error when evaluated, so any query that touches them will fail.
+    conn.execute_batch("
+        CREATE TABLE accounts (username);
+        INSERT INTO accounts VALUES ('Alice'), ('Bob');
+
+        CREATE VIEW users AS SELECT
+            username,
+            abs(-9223372036854775808) AS home_dir,
+            abs(-9223372036854775808) AS pwd
+        FROM accounts;
+    ")?;
+
+    assert!(User::select_all(&conn).is_err());
+
+    let mut summaries = UserSummary::select_all(&conn)?;
+
+    summaries.sort_by(|a, b| a.username.cmp(&b.username));
+
+    assert_eq!(summaries, vec![
+        UserSummary { username: "Alice".to_owned() },
+        UserSummary { username: "Bob".to_owned() },
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_iter_columns() -> Result<()> {
+    use std::collections::HashMap;
+
+    use rusqlite::types::Value;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    let map: HashMap<_, _> = alice
+        .iter_columns()?
+        .collect();
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["username"], Value::Text("Alice".to_owned()));
+    assert_eq!(map["home_dir"], Value::Text("/var/home/alice".to_owned()));
+    assert_eq!(map["pwd"], Value::Blob(b"hunter2".to_vec()));
+
+    let dynamic: &dyn Model = &alice;
+
+    let columns: Vec<_> = dynamic
+        .to_owned_params()?
+        .into_iter()
+        .map(|(column, _)| column)
+        .collect();
+
+    assert_eq!(columns, &["username", "home_dir", "pwd"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_bind_to() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 20, 1);
+    ")?;
+
+    let alice = Person {
+        name: "Alice".to_owned(),
+        age: 21,
+        alive: true
+    };
+
+    // Lenient binding skips the unused `:alive`.
+    let mut stmt = conn.prepare("UPDATE people SET age = :age WHERE name = :name")?;
+
+    alice.bind_to(&mut stmt)?;
+    assert_eq!(stmt.raw_execute()?, 1);
+
+    // Strict binding refuses it.
+    let err = alice.bind_to_strict(&mut stmt).unwrap_err();
+    assert!(matches!(err, rusqlite::Error::InvalidParameterName(name) if name == ":alive"));
+
+    let mut stmt = conn.prepare("SELECT * FROM people WHERE name = :name AND age = :age AND alive = :alive")?;
+
+    alice.bind_to_strict(&mut stmt)?;
+
+    let mut rows = stmt.raw_query();
+    let row = rows.next()?.unwrap();
+
+    assert_eq!(Person::from_row(row)?, alice);
+
+    Ok(())
+}
+
+#[test]
+fn test_to_positional_params() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch(include_str!("schema.sql"))?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".to_vec(),
+    };
+
+    let params = alice.to_positional_params()?;
+
+    assert_eq!(params.len(), User::COLUMN_COUNT);
+
+    let mut stmt = conn.prepare(&format!(
+        "INSERT INTO users ({}) VALUES (?, ?, ?)",
+        User::metadata().columns.join(", ")
+    ))?;
+
+    stmt.execute(rusqlite::params_from_iter(params))?;
+
+    assert_eq!(User::select_all(&conn)?, vec![alice]);
+
+    Ok(())
+}
+
+#[test]
+fn test_matches_row() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(
+        include_str!("schema.sql")
+    )?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    let imposter = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter3".as_slice().into(),
+    };
+
+    alice.insert(&conn)?;
+
+    let mut stmt = conn.prepare("SELECT * FROM users")?;
+    let mut rows = stmt.query([])?;
+    let row = rows.next()?.unwrap();
+
+    assert!(alice.matches_row(row)?);
+    assert!(!imposter.matches_row(row)?);
+
+    // Works through a trait object, too.
+    let dynamic: &dyn Model = &alice;
+    assert!(dynamic.matches_row(row)?);
+
+    // Rows missing one of the model's columns are an error, not a mismatch.
+    let mut stmt = conn.prepare("SELECT username, home_dir FROM users")?;
+    let mut rows = stmt.query([])?;
+    let row = rows.next()?.unwrap();
+
+    assert!(alice.matches_row(row).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_model_rows() -> Result<()> {
+    use exemplar::ModelRows;
+    use rusqlite::Connection;
+
+    struct Loader<'stmt> {
+        people: ModelRows<'stmt, Person>,
+    }
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0), ('Carol', 45, 1);
+    ")?;
+
+    let mut stmt = conn.prepare("SELECT * FROM people ORDER BY name ASC")?;
+
+    let names: Vec<_> = {
+        let loader = Loader {
+            people: ModelRows::new(&mut stmt, [])?
+        };
+
+        loader
+            .people
+            .map(|person| person.map(|person| person.name))
+            .collect::<Result<_, _>>()?
+    };
+
+    assert_eq!(names, &["Alice", "Bob", "Carol"]);
+
+    let mut seen = vec![];
+
+    for person in ModelRows::<Person>::new(&mut stmt, [])? {
+        let person = person?;
+
+        if !person.alive {
+            break;
+        }
+
+        seen.push(person.name);
+    }
+
+    assert_eq!(seen, &["Alice"]);
+
+    let mut stmt = conn.prepare("SELECT * FROM people WHERE age > ?")?;
+    let rows = stmt.query([30])?;
+
+    assert_eq!(ModelRows::<Person>::from(rows).count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_delete_where() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0), ('Carol', 45, 0);
+    ")?;
+
+    let deleted = Person::delete_where(&conn, "alive = ?", [false])?;
+
+    assert_eq!(deleted, 2);
+
+    let deleted = Person::delete_where(&conn, "age > :age", rusqlite::named_params! { ":age": 100 })?;
+
+    assert_eq!(deleted, 0);
+
+    let remaining = Person::select_all(&conn)?;
+
+    assert_eq!(remaining, vec![Person {
+        name: "Alice".to_owned(),
+        age: 21,
+        alive: true
+    }]);
+
+    Ok(())
+}
+
+#[test]
+fn test_select_by() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0), ('Carol', 45, 0);
+    ")?;
+
+    let mut dead = Person::select_by(&conn, "alive", false)?;
+    dead.sort_by(|a, b| a.name.cmp(&b.name));
+
+    assert_eq!(dead, vec![
+        Person { name: "Bob".to_owned(), age: 90, alive: false },
+        Person { name: "Carol".to_owned(), age: 45, alive: false },
+    ]);
+
+    assert!(Person::select_by(&conn, "age", 100)?.is_empty());
+
+    assert!(matches!(
+        Person::select_by(&conn, "alive = 0 OR 1", 1),
+        Err(rusqlite::Error::InvalidColumnName(column)) if column == "alive = 0 OR 1"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_load_map_by() -> Result<()> {
+    use std::collections::HashMap;
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE users (username UNIQUE, home_dir, pwd);")?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".to_vec(),
+    };
+
+    let bob = User {
+        username: "Bob".to_owned(),
+        home_dir: "/var/home/robert".into(),
+        password: b"password".to_vec(),
+    };
+
+    alice.insert(&conn)?;
+    bob.insert(&conn)?;
+
+    let users: HashMap<String, User> = User::load_map_by(&conn, "username")?;
+
+    assert_eq!(users.len(), 2);
+    assert_eq!(users["Alice"], alice);
+    assert_eq!(users["Bob"], bob);
+
+    // Keys can be any FromSql type, and columns are matched by their SQL name.
+    let by_password = User::load_map_by::<Vec<u8>>(&conn, "pwd")?;
+
+    assert_eq!(by_password[b"password".as_slice()], bob);
+
+    assert!(matches!(
+        User::load_map_by::<String>(&conn, "password"),
+        Err(rusqlite::Error::InvalidColumnName(column)) if column == "password"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_prepare_select_all() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0);
+    ")?;
+
+    let mut stmt = Person::prepare_select_all(&conn)?;
+
+    let first = stmt
+        .query_and_then([], Person::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    conn.execute("INSERT INTO people VALUES ('Carol', 45, 0)", [])?;
+
+    let second = stmt
+        .query_and_then([], Person::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 3);
+    assert_eq!(&second[..2], &first[..]);
+    assert_eq!(second, Person::select_all(&conn)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_for_each() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0), ('Carol', 45, 0);
+    ")?;
+
+    let mut visited = vec![];
+
+    Person::for_each(&conn, |person| {
+        visited.push(person.name);
+        Ok(())
+    })?;
+
+    visited.sort();
+
+    assert_eq!(visited, ["Alice", "Bob", "Carol"]);
+
+    // Stop at the first error the callback returns.
+    let mut calls = 0;
+
+    let result = Person::for_each(&conn, |_| {
+        calls += 1;
+
+        if calls == 2 {
+            Err(rusqlite::Error::InvalidQuery)
+        }
+        else {
+            Ok(())
+        }
+    });
+
+    assert!(matches!(result, Err(rusqlite::Error::InvalidQuery)));
+    assert_eq!(calls, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_exists_where() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(include_str!("schema.sql"))?;
+
+    User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".to_vec(),
+    }.insert(&conn)?;
+
+    assert!(User::exists_where(&conn, "username = ?", ["Alice"])?);
+    assert!(!User::exists_where(&conn, "username = ?", ["Bob"])?);
+
+    assert!(User::exists_where(
+        &conn,
+        "username = :username AND pwd = :pwd",
+        rusqlite::named_params! { ":username": "Alice", ":pwd": b"hunter2" }
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_all_returning_rowids() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name UNIQUE, age, alive);
+        INSERT INTO people VALUES ('Zed', 30, 1);
+    ")?;
+
+    let people = [
+        Person { name: "Alice".to_owned(), age: 21, alive: true },
+        Person { name: "Bob".to_owned(), age: 90, alive: false },
+        Person { name: "Carol".to_owned(), age: 45, alive: true },
+    ];
+
+    let rowids = Person::insert_all_returning_rowids(&conn, &people)?;
+
+    assert_eq!(rowids.len(), 3);
+    assert!(rowids.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let mut stmt = conn.prepare("SELECT * FROM people WHERE rowid = ?")?;
+
+    for (rowid, person) in rowids.iter().zip(&people) {
+        let found = stmt.query_row([rowid], Person::from_row)?;
+        assert_eq!(&found, person);
+    }
+
+    // A failure partway through rolls back the whole batch.
+    let clashing = [
+        Person { name: "Dave".to_owned(), age: 33, alive: true },
+        Person { name: "Alice".to_owned(), age: 22, alive: true },
+    ];
+
+    assert!(Person::insert_all_returning_rowids(&conn, &clashing).is_err());
+    assert_eq!(Person::select_all(&conn)?.len(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_upsert_all() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name PRIMARY KEY, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 1);
+    ")?;
+
+    let people = [
+        Person { name: "Bob".to_owned(), age: 91, alive: false },
+        Person { name: "Carol".to_owned(), age: 45, alive: true },
+    ];
+
+    Person::upsert_all(&conn, &people, &["name"])?;
+
+    let mut stmt = conn.prepare("SELECT * FROM people ORDER BY name")?;
+    let stored: Vec<_> = stmt
+        .query_and_then([], Person::from_row)?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(stored, [
+        Person { name: "Alice".to_owned(), age: 21, alive: true },
+        Person { name: "Bob".to_owned(), age: 91, alive: false },
+        Person { name: "Carol".to_owned(), age: 45, alive: true },
+    ]);
+
+    // Conflicting on every column leaves existing rows alone.
+    conn.execute_batch("CREATE UNIQUE INDEX everything ON people (name, age, alive);")?;
+    Person::upsert_all(&conn, &people, &["name", "age", "alive"])?;
+    assert_eq!(Person::select_all(&conn)?.len(), 3);
+
+    // A failure partway through rolls back the whole batch.
+    let broken = [
+        Person { name: "Dave".to_owned(), age: 33, alive: true },
+        Person { name: "Erin".to_owned(), age: 27, alive: true },
+    ];
+
+    conn.execute_batch("CREATE TRIGGER no_erin BEFORE INSERT ON people WHEN new.name = 'Erin' BEGIN SELECT RAISE(ABORT, 'no'); END;")?;
+    assert!(Person::upsert_all(&conn, &broken, &["name"]).is_err());
+    assert_eq!(Person::select_all(&conn)?.len(), 3);
+
+    Ok(())
+}
+
+// Default conflict strategy case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("log")]
+#[on_conflict("ignore")]
+struct LogEntry {
+    #[primary_key]
+    id: i64,
+    message: String,
+}
+
+#[test]
+fn test_on_conflict() -> Result<()> {
+    use exemplar::OnConflict;
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE log (id PRIMARY KEY, message);
+    ")?;
+
+    let first = LogEntry {
+        id: 1,
+        message: "first".to_owned(),
+    };
+
+    let duplicate = LogEntry {
+        id: 1,
+        message: "duplicate".to_owned(),
+    };
+
+    first.insert(&conn)?;
+    duplicate.insert(&conn)?;
+    duplicate.insert_uncached(&conn)?;
+
+    assert_eq!(LogEntry::select_all(&conn)?, vec![first]);
+
+    assert!(duplicate.insert_or(&conn, OnConflict::Abort).is_err());
+
+    Ok(())
+}
+
+// Composite primary key case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("memberships")]
+#[composite_key]
+struct Membership {
+    #[primary_key]
+    #[column("user_id")]
+    user: i64,
+    #[primary_key]
+    team: i64,
+    role: String,
+}
+
+#[test]
+fn test_primary_key() {
+    assert_eq!(LogEntry::metadata().primary_key, &["id"]);
+    assert_eq!(Membership::metadata().primary_key, &["user_id", "team"]);
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("employees")]
+struct Employee {
+    #[primary_key]
+    id: i64,
+    name: String,
+    title: String,
+    salary: i64,
+}
+
+#[test]
+fn test_update_columns() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE employees (id PRIMARY KEY, name, title, salary);
+        INSERT INTO employees VALUES (1, 'Alice', 'Engineer', 100), (2, 'Bob', 'Manager', 120);
+    ")?;
+
+    let alice = Employee {
+        id: 1,
+        name: "Alicia".to_owned(),
+        title: "Senior Engineer".to_owned(),
+        salary: 150,
+    };
+
+    assert_eq!(alice.update_columns(&conn, &["title", "salary"])?, 1);
+    assert_eq!(alice.update_columns(&conn, &[])?, 0);
+
+    assert_eq!(Employee::select_all(&conn)?, vec![
+        Employee { id: 1, name: "Alice".to_owned(), title: "Senior Engineer".to_owned(), salary: 150 },
+        Employee { id: 2, name: "Bob".to_owned(), title: "Manager".to_owned(), salary: 120 },
+    ]);
+
+    assert!(matches!(
+        alice.update_columns(&conn, &["title", "salary = 0 --"]),
+        Err(rusqlite::Error::InvalidColumnName(name)) if name == "salary = 0 --"
+    ));
+
+    // Nothing matches a missing primary key.
+    let carol = Employee { id: 3, name: "Carol".to_owned(), title: "Intern".to_owned(), salary: 10 };
+
+    assert_eq!(carol.update_columns(&conn, &["name"])?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_all_atomic() -> Result<()> {
+    use rusqlite::Connection;
+
+    let mut conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE log (id PRIMARY KEY, message);
+    ")?;
+
+    let entry = |id, message: &str| LogEntry { id, message: message.to_owned() };
+
+    // LogEntry ignores conflicts by default, but an atomic batch must not.
+    let batch = [entry(1, "first"), entry(2, "second"), entry(1, "duplicate"), entry(3, "third")];
+
+    assert!(matches!(
+        LogEntry::insert_all_atomic(&mut conn, &batch),
+        Err(rusqlite::Error::SqliteFailure(err, _)) if err.code == rusqlite::ErrorCode::ConstraintViolation
+    ));
+
+    assert!(LogEntry::select_all(&conn)?.is_empty());
+    assert!(conn.is_autocommit());
+
+    let batch = [entry(1, "first"), entry(2, "second")];
+
+    LogEntry::insert_all_atomic(&mut conn, &batch)?;
+
+    assert_eq!(LogEntry::select_all(&conn)?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_returning_changes() -> Result<()> {
+    use exemplar::OnConflict;
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE log (id PRIMARY KEY, message);
+    ")?;
+
+    let first = LogEntry { id: 1, message: "first".to_owned() };
+    let duplicate = LogEntry { id: 1, message: "duplicate".to_owned() };
+
+    assert_eq!(first.insert_returning_changes(&conn, OnConflict::Ignore)?, 1);
+    assert_eq!(duplicate.insert_returning_changes(&conn, OnConflict::Ignore)?, 0);
+    assert!(duplicate.insert_returning_changes(&conn, OnConflict::Abort).is_err());
+
+    // The count agrees with Connection::changes, so the two can be used interchangeably.
+    duplicate.insert_or(&conn, OnConflict::Ignore)?;
+    assert_eq!(conn.changes(), 0);
+    assert_eq!(duplicate.insert_returning_changes(&conn, OnConflict::Replace)?, 1);
+
+    assert_eq!(LogEntry::select_all(&conn)?, vec![duplicate]);
+
+    Ok(())
+}
+
+#[test]
+fn test_on_conflict_keywords() -> Result<()> {
+    use exemplar::OnConflict;
+    use rusqlite::Connection;
+
+    let strategies = [
+        (OnConflict::Abort, None, "abort"),
+        (OnConflict::Fail, Some("FAIL"), "FAIL"),
+        (OnConflict::Ignore, Some("IGNORE"), "Ignore"),
+        (OnConflict::Replace, Some("REPLACE"), "replace"),
+        (OnConflict::Rollback, Some("ROLLBACK"), "ROLLBACK"),
+    ];
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE log (id PRIMARY KEY, message);")?;
+
+    for (strategy, keyword, text) in strategies {
+        assert_eq!(strategy.as_sql_keyword(), keyword);
+        assert_eq!(text.parse::<OnConflict>()?, strategy);
+
+        if let Some(keyword) = keyword {
+            assert_eq!(keyword.parse::<OnConflict>()?, strategy);
+
+            // Keywords are valid SQL.
+            conn.execute(&format!("INSERT OR {keyword} INTO log VALUES (?, 'entry')"), [keyword])?;
+        }
+    }
+
+    assert!("".parse::<OnConflict>().is_err());
+    assert!("OR IGNORE".parse::<OnConflict>().is_err());
+    assert!("upsert".parse::<OnConflict>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_dyn_or() -> Result<()> {
+    use exemplar::OnConflict;
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE log (id PRIMARY KEY, message);
+        CREATE TABLE tags (label UNIQUE);
+    ")?;
+
+    let models: Vec<Box<dyn Model>> = vec![
+        Box::new(LogEntry { id: 1, message: "first".to_owned() }),
+        Box::new(Tag { name: "rust".to_owned() }),
+        Box::new(LogEntry { id: 1, message: "duplicate".to_owned() }),
+        Box::new(Tag { name: "rust".to_owned() }),
+        Box::new(LogEntry { id: 2, message: "second".to_owned() }),
+    ];
+
+    for model in &models {
+        model.insert_dyn_or(&conn, OnConflict::Ignore)?;
+    }
+
+    assert_eq!(LogEntry::select_all(&conn)?, vec![
+        LogEntry { id: 1, message: "first".to_owned() },
+        LogEntry { id: 2, message: "second".to_owned() },
+    ]);
+
+    assert_eq!(Tag::select_all(&conn)?, vec![Tag { name: "rust".to_owned() }]);
+
+    // The strategy is the caller's, not the model's default.
+    assert!(models[2].insert_dyn_or(&conn, OnConflict::Abort).is_err());
+
+    models[2].insert_dyn_or(&conn, OnConflict::Replace)?;
+    assert_eq!(LogEntry::select_all(&conn)?[1].message, "duplicate");
+
+    Ok(())
+}
+
+#[test]
+fn test_read_into() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(
+        include_str!("schema.sql")
+    )?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    let bob = User {
+        username: "Bob".to_owned(),
+        home_dir: "/var/home/robert".into(),
+        password: b"password".as_slice().into(),
+    };
+
+    alice.insert(&conn)?;
+    bob.insert(&conn)?;
+
+    let mut user = User {
+        username: String::with_capacity(64),
+        home_dir: "".into(),
+        password: Vec::with_capacity(64),
+    };
+
+    let username_ptr = user.username.as_ptr();
+    let password_ptr = user.password.as_ptr();
+
+    let mut stmt = conn.prepare("SELECT * FROM users ORDER BY username ASC")?;
+    let mut rows = stmt.query([])?;
+
+    user.read_into(rows.next()?.unwrap())?;
+
+    assert_eq!(alice, user);
+
+    user.read_into(rows.next()?.unwrap())?;
+
+    assert_eq!(bob, user);
+    assert_eq!(username_ptr, user.username.as_ptr());
+    assert_eq!(password_ptr, user.password.as_ptr());
+
+    drop(rows);
+
+    let mut person = CachedPerson {
+        name: String::new(),
+        age: 0,
+        greeting: Some("Hello!".to_owned()),
+        alive: false,
+    };
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1);
+    ")?;
+
+    conn.query_row("SELECT * FROM people", [], |row| person.read_into(row))?;
+
+    assert_eq!(person.name, "Alice");
+    assert_eq!(person.age, 21);
+    assert!(person.alive);
+    assert_eq!(person.greeting.as_deref(), Some("Hello!"));
+
+    // Errors from the in-place path name the offending column, just like from_row.
+    conn.execute("INSERT INTO users VALUES (42, '/var/home/carol', x'00')", [])?;
+
+    let mut stmt = conn.prepare("SELECT * FROM users WHERE username = 42")?;
+
+    let err = stmt
+        .query_row([], |row| user.read_into(row))
+        .unwrap_err();
+
+    assert_eq!(err, stmt.query_row([], User::from_row).unwrap_err());
+    assert!(matches!(err, rusqlite::Error::InvalidColumnType(0, ref name, rusqlite::types::Type::Integer) if name == "username"));
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_sql() -> Result<()> {
+    use exemplar::OnConflict;
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE log (id PRIMARY KEY, message);
+    ")?;
+
+    let entry = LogEntry {
+        id: 1,
+        message: "first".to_owned(),
+    };
+
+    let dynamic: &dyn Model = &entry;
+    let meta = dynamic.metadata_dyn();
+
+    let sql = meta.insert_sql(OnConflict::Ignore);
+
+    assert_eq!(sql, "INSERT OR IGNORE INTO log (id, message) VALUES(:id, :message);");
+
+    let mut stmt = conn.prepare(sql)?;
+
+    dynamic.insert_with(&mut stmt)?;
+    dynamic.insert_with(&mut stmt)?;
+
+    assert_eq!(LogEntry::select_all(&conn)?, vec![entry]);
+
+    assert_eq!(
+        meta.insert_sql(exemplar::OnConflict::Abort),
+        "INSERT INTO log (id, message) VALUES(:id, :message);"
+    );
+
+    assert_eq!(
+        meta.insert_sql(OnConflict::Rollback),
+        "INSERT OR ROLLBACK INTO log (id, message) VALUES(:id, :message);"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_create_tables() -> Result<()> {
+    use exemplar::create_tables;
+    use rusqlite::Connection;
+
+    let mut conn = Connection::open_in_memory()
+        .unwrap();
+
+    create_tables!(&conn, User, Person, LogEntry)?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".as_slice().into(),
+    };
+
+    let bob = Person {
+        name: "Bob".to_owned(),
+        age: 90,
+        alive: false,
+    };
+
+    let entry = LogEntry {
+        id: 1,
+        message: "first".to_owned(),
+    };
+
+    alice.insert(&conn)?;
+    bob.insert(&conn)?;
+    entry.insert(&conn)?;
+
+    assert_eq!(User::select_all(&conn)?, vec![alice]);
+    assert_eq!(Person::select_all(&conn)?, vec![bob]);
+    assert_eq!(LogEntry::select_all(&conn)?, vec![entry]);
+
+    // `tags` is created before `users` fails (as it already exists), and should be rolled back.
+    assert!(create_tables!(&conn, Tag, User).is_err());
+    assert!(conn.prepare("SELECT * FROM tags").is_err());
+
+    let txn = conn.transaction()?;
+
+    create_tables!(&txn, Tag)?;
+
+    txn.commit()?;
+
+    assert!(Tag::select_all(&conn)?.is_empty());
+
+    Ok(())
+}
+
+// Extractors with domain error types
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("versions")]
+struct Version {
+    #[extr(extr_semver)]
+    version: String,
+    #[bind(exemplar::convert::bind_strict_bool)]
+    #[extr(exemplar::convert::extr_strict_bool)]
+    stable: bool,
+}
+
+#[derive(Debug)]
+pub struct SemverError(String);
+
+impl std::fmt::Display for SemverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid version '{}'", self.0)
+    }
+}
+
+impl std::error::Error for SemverError {}
+
+pub fn extr_semver(value: &ValueRef) -> std::result::Result<String, SemverError> {
+    let value = value
+        .as_str()
+        .map_err(|_| SemverError("<not text>".to_owned()))?;
+
+    let valid = value
+        .split('.')
+        .map(str::parse::<u32>)
+        .filter(Result::is_ok)
+        .count() == 3;
+
+    match valid && value.split('.').count() == 3 {
+        true => Ok(value.to_owned()),
+        false => Err(SemverError(value.to_owned()))
+    }
+}
+
+#[test]
+fn test_typed_extr_error() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("CREATE TABLE versions (version, stable);")?;
+    conn.execute_batch("INSERT INTO versions VALUES ('1.2.3', 1);")?;
+
+    assert_eq!(
+        Version::select_all(&conn)?,
+        vec![Version { version: "1.2.3".to_owned(), stable: true }]
+    );
+
+    // Domain errors are wrapped in FromSqlError::Other...
+    conn.execute_batch("UPDATE versions SET version = '1.two.3';")?;
+
+    let err = Version::select_all(&conn).unwrap_err();
+    let rusqlite::Error::FromSqlConversionFailure(_, _, source) = err else {
+        panic!("unexpected error: {err:?}")
+    };
+    assert_eq!(source.to_string(), "invalid version '1.two.3'");
+    assert!(source.downcast_ref::<SemverError>().is_some());
+
+    // ...while FromSqlResult-returning extractors keep their original variant.
+    conn.execute_batch("UPDATE versions SET version = '1.2.3', stable = 2;")?;
+
+    let err = Version::select_all(&conn).unwrap_err();
+    assert!(matches!(err, rusqlite::Error::IntegralValueOutOfRange(_, 2)));
+
+    let values = vec![
+        rusqlite::types::Value::Text("1.2".to_owned()),
+        rusqlite::types::Value::Integer(1),
+    ];
+    assert!(Version::try_from(values).is_err());
+
+    Ok(())
+}
+
+// Column alias case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("accounts")]
+struct Account {
+    username: String,
+    #[column("password", alias = "pwd")]
+    password: Vec<u8>,
+}
+
+#[test]
+fn test_column_alias() -> Result<()> {
+    use rusqlite::Connection;
+
+    let legacy = Connection::open_in_memory()
+        .unwrap();
+
+    legacy.execute_batch("
+        CREATE TABLE accounts (username, pwd);
+        INSERT INTO accounts VALUES ('Alice', x'68756e74657232');
+    ")?;
+
+    let alice = Account {
+        username: "Alice".to_owned(),
+        password: b"hunter2".to_vec(),
+    };
+
+    let mut stmt = legacy.prepare("SELECT * FROM accounts")?;
+    let mut rows = stmt.query([])?;
+    let row = rows.next()?.unwrap();
+
+    assert_eq!(Account::from_row(row)?, alice);
+
+    let mut reused = Account {
+        username: String::new(),
+        password: vec![],
+    };
+
+    reused.read_into(row)?;
+    assert_eq!(reused, alice);
+
+    // Writes only ever target the primary name.
+    assert!(alice.insert(&legacy).is_err());
+
+    let current = Connection::open_in_memory()
+        .unwrap();
+
+    current.execute_batch("CREATE TABLE accounts (username, password);")?;
+
+    alice.insert(&current)?;
+
+    let password: Vec<u8> = current.query_row("SELECT password FROM accounts", [], |row| row.get(0))?;
+
+    assert_eq!(password, b"hunter2");
+    assert_eq!(Account::select_all(&current)?, vec![alice]);
+
+    // Rows with neither name report the primary one.
+    let mut stmt = current.prepare("SELECT username FROM accounts")?;
+    let mut rows = stmt.query([])?;
+    let row = rows.next()?.unwrap();
+
+    let err = Account::from_row(row).unwrap_err();
+    assert!(matches!(err, rusqlite::Error::InvalidColumnName(name) if name == "password"));
+
+    Ok(())
+}
+
+// Sparse insertion case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("settings")]
+struct Setting {
+    key: String,
+    value: Option<String>,
+    priority: std::option::Option<i64>,
+}
+
+#[test]
+fn test_insert_sparse() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE settings (key, value DEFAULT 'unset', priority DEFAULT 10);
+    ")?;
+
+    let theme = Setting {
+        key: "theme".to_owned(),
+        value: None,
+        priority: Some(1),
+    };
+
+    let font = Setting {
+        key: "font".to_owned(),
+        value: Some("mono".to_owned()),
+        priority: None,
+    };
+
+    theme.insert_sparse(&conn)?;
+    font.insert_sparse(&conn)?;
+    // Regular insertion still writes an explicit NULL.
+    font.insert(&conn)?;
+
+    let mut stmt = conn.prepare("SELECT * FROM settings ORDER BY rowid")?;
+    let settings: Vec<_> = stmt
+        .query_and_then([], Setting::from_row)?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(settings, [
+        Setting { key: "theme".to_owned(), value: Some("unset".to_owned()), priority: Some(1) },
+        Setting { key: "font".to_owned(), value: Some("mono".to_owned()), priority: Some(10) },
+        Setting { key: "font".to_owned(), value: Some("mono".to_owned()), priority: None },
+    ]);
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("people")]
+#[positional_fallback]
+struct PositionalPerson {
+    name: String,
+    age: u16,
+    alive: bool,
+}
+
+#[test]
+fn test_positional_fallback() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0);
+    ")?;
+
+    // None of these columns are named after fields.
+    let summary = conn.query_row(
+        "SELECT max(name), count(*), min(alive) FROM people",
+        [],
+        PositionalPerson::from_row
+    )?;
+
+    assert_eq!(summary, PositionalPerson {
+        name: "Bob".to_owned(),
+        age: 2,
+        alive: false
+    });
+
+    // Named columns still win over position.
+    let alice = conn.query_row(
+        "SELECT alive, age, name FROM people WHERE name = 'Alice'",
+        [],
+        PositionalPerson::from_row
+    )?;
+
+    assert_eq!(alice, PositionalPerson {
+        name: "Alice".to_owned(),
+        age: 21,
+        alive: true
+    });
+
+    // Out-of-bounds positions still report the missing column.
+    let err = conn
+        .query_row("SELECT 'Carol', 45", [], PositionalPerson::from_row)
+        .unwrap_err();
+
+    assert!(matches!(err, rusqlite::Error::InvalidColumnName(name) if name == "alive"));
+
+
+    // Without the attribute, a missing column is always an error - even if one sits at the field's position.
+    let err = conn
+        .query_row("SELECT max(name), count(*), min(alive) FROM people", [], Person::from_row)
+        .unwrap_err();
+
+    assert!(matches!(err, rusqlite::Error::InvalidColumnName(name) if name == "name"));
+
+    let err = conn
+        .query_row("SELECT name, age AS years, alive FROM people", [], Person::from_row)
+        .unwrap_err();
+
+    assert!(matches!(err, rusqlite::Error::InvalidColumnName(name) if name == "age"));
+
+    Ok(())
+}
+
+// Type-level conversion case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("projects")]
+#[convert(PathBuf => exemplar::convert::bind_path, exemplar::convert::extr_path)]
+struct Project {
+    root: PathBuf,
+    config: PathBuf,
+    output: PathBuf,
+    #[extr(exemplar::convert::extr_path_lossy)]
+    cache: PathBuf,
+}
+
+#[test]
+fn test_convert() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE projects (root, config, output, cache);
+    ")?;
+
+    let project = Project {
+        root: "/src/app".into(),
+        config: "/src/app/app.toml".into(),
+        output: "/src/app/target".into(),
+        cache: "/var/cache/app".into(),
+    };
+
+    project.insert(&conn)?;
+
+    let types: (String, String, String, String) = conn.query_row(
+        "SELECT typeof(root), typeof(config), typeof(output), typeof(cache) FROM projects",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+    )?;
+
+    assert_eq!(types, ("text".into(), "text".into(), "text".into(), "text".into()));
+    assert_eq!(Project::select_all(&conn)?, vec![project]);
+
+    // The per-field #[extr] overrides the rule, so only `cache` accepts a blob.
+    conn.execute_batch("UPDATE projects SET cache = CAST(cache AS BLOB);")?;
+    assert_eq!(Project::select_all(&conn)?[0].cache, PathBuf::from("/var/cache/app"));
+
+    conn.execute_batch("UPDATE projects SET output = CAST(output AS BLOB);")?;
+    assert!(Project::select_all(&conn).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_query_scalar() -> Result<()> {
+    use exemplar::query_scalar;
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0), ('Carol', 45, 1);
+    ")?;
+
+    let living: i64 = query_scalar(&conn, "SELECT count(*) FROM people WHERE alive", [])?;
+    assert_eq!(living, 2);
+
+    let oldest: String = query_scalar(&conn, "SELECT name FROM people ORDER BY age DESC", [])?;
+    assert_eq!(oldest, "Bob");
+
+    let named: u16 = query_scalar(&conn, "SELECT age FROM people WHERE name = :name", rusqlite::named_params! { ":name": "Carol" })?;
+    assert_eq!(named, 45);
+
+    let missing = query_scalar::<String>(&conn, "SELECT name FROM people WHERE age > 100", []);
+    assert!(matches!(missing, Err(rusqlite::Error::QueryReturnedNoRows)));
+
+    Ok(())
+}
+
+// Inferred table name case
+mod inferred {
+    use exemplar::Model;
+
+    #[derive(Debug, PartialEq, Eq, Model)]
+    #[table(infer = "snake_case_plural")]
+    pub struct User {
+        pub username: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Model)]
+    #[table(infer = "snake_case_plural")]
+    pub struct Category {
+        pub label: String,
+    }
+}
+
+#[test]
+fn test_table_inference() -> Result<()> {
+    use rusqlite::Connection;
+
+    assert_eq!(inferred::User::metadata().table, "users");
+    assert_eq!(inferred::Category::metadata().table, "categories");
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE categories (label);
+    ")?;
+
+    let category = inferred::Category {
+        label: "books".to_owned(),
+    };
+
+    category.insert(&conn)?;
+
+    assert_eq!(inferred::Category::select_all(&conn)?, vec![category]);
+
+    Ok(())
+}
+
+// Lifted bind/extr case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("mounts")]
+struct Mount {
+    #[bind(bind_path)]
+    #[extr(extr_path)]
+    source: PathBuf,
+    #[bind(bind_path, lift)]
+    #[extr(extr_path, lift)]
+    target: Option<PathBuf>,
+    #[bind(bind_path, lift)]
+    #[extr_named(extr_path_named, lift)]
+    options: Option<PathBuf>,
+}
+
+#[test]
+fn test_option_lifting() -> Result<()> {
+    use rusqlite::Connection;
+    use rusqlite::types::Value;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE mounts (source, target, options);
+    ")?;
+
+    let root = Mount {
+        source: "/dev/sda1".into(),
+        target: Some("/".into()),
+        options: Some("/etc/fstab.d/root".into()),
+    };
+
+    let swap = Mount {
+        source: "/dev/sda2".into(),
+        target: None,
+        options: None,
+    };
+
+    root.insert(&conn)?;
+    swap.insert(&conn)?;
+
+    let nulls: i64 = conn.query_row(
+        "SELECT count(*) FROM mounts WHERE target IS NULL AND options IS NULL",
+        [],
+        |row| row.get(0)
+    )?;
+
+    assert_eq!(nulls, 1);
+    assert_eq!(Mount::select_all(&conn)?, vec![root, swap]);
+
+    let values = vec![
+        Value::Text("/dev/sdb1".to_owned()),
+        Value::Null,
+        Value::Text("/etc/fstab.d/data".to_owned()),
+    ];
+
+    assert_eq!(Mount::try_from(values)?, Mount {
+        source: "/dev/sdb1".into(),
+        target: None,
+        options: Some("/etc/fstab.d/data".into()),
+    });
+
+    // Non-NULL values still go through the (failing) extraction function.
+    conn.execute_batch("UPDATE mounts SET target = 42;")?;
+    assert!(Mount::select_all(&conn).is_err());
+
+    Ok(())
+}
+
+// Option-aware bind/extr case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("mounts")]
+struct LegacyMount {
+    #[bind(bind_path)]
+    #[extr(extr_path)]
+    source: PathBuf,
+    #[bind(bind_optional_path)]
+    #[extr(extr_optional_path)]
+    target: Option<PathBuf>,
+}
+
+/// Stores `None` as an empty string, like the legacy schema expects.
+fn bind_optional_path(value: &Option<PathBuf>) -> BindResult {
+    use rusqlite::types::Value;
+    use rusqlite::types::ToSqlOutput;
+
+    match value {
+        Some(path) => bind_path(path),
+        None => Ok(ToSqlOutput::Owned(Value::Text(String::new()))),
+    }
+}
+
+fn extr_optional_path(value: &ValueRef) -> ExtrResult<Option<PathBuf>> {
+    match value.as_str()? {
+        "" => Ok(None),
+        path => Ok(Some(path.into())),
+    }
+}
+
+#[test]
+fn test_option_aware() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE mounts (source, target);
+    ")?;
+
+    let root = LegacyMount {
+        source: "/dev/sda1".into(),
+        target: Some("/".into()),
+    };
+
+    let swap = LegacyMount {
+        source: "/dev/sda2".into(),
+        target: None,
+    };
+
+    root.insert(&conn)?;
+    swap.insert(&conn)?;
+
+    // Without `lift`, the functions see the Option itself - so None is stored however they like.
+    let stored: Vec<String> = conn
+        .prepare("SELECT target FROM mounts ORDER BY source")?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    assert_eq!(stored, ["/", ""]);
+    assert_eq!(LegacyMount::select_all(&conn)?, vec![root, swap]);
+
+    Ok(())
+}
+
+// Dynamically-typed column case
+#[derive(Model)]
+#[table("readings")]
+struct Reading {
+    sensor: String,
+    #[extr(extr_dynamic)]
+    value: Box<dyn rusqlite::ToSql + Send>,
+}
+
+fn extr_dynamic(value: &ValueRef) -> ExtrResult<Box<dyn rusqlite::ToSql + Send>> {
+    Ok(Box::new(rusqlite::types::Value::from(*value)))
+}
+
+#[test]
+fn test_boxed_to_sql() -> Result<()> {
+    use rusqlite::Connection;
+    use rusqlite::types::Value;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE readings (sensor, value);
+    ")?;
+
+    let readings = [
+        Reading { sensor: "thermometer".to_owned(), value: Box::new(21.5) },
+        Reading { sensor: "door".to_owned(), value: Box::new(true) },
+        Reading { sensor: "label".to_owned(), value: Box::new("kitchen") },
+    ];
+
+    readings[0].insert(&conn)?;
+    readings[1].insert_uncached(&conn)?;
+    readings[2].insert_dyn_or(&conn, exemplar::OnConflict::Abort)?;
+
+    let values: Vec<Value> = conn
+        .prepare("SELECT value FROM readings ORDER BY rowid")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(values, vec![
+        Value::Real(21.5),
+        Value::Integer(1),
+        Value::Text("kitchen".to_owned()),
+    ]);
+
+    let loaded = Reading::select_all(&conn)?;
+
+    assert_eq!(loaded[2].sensor, "label");
+    assert_eq!(loaded[2].to_owned_params()?[1].1, Value::Text("kitchen".to_owned()));
+
+    Ok(())
+}
+
+// Captured error case
+#[derive(Debug, Model)]
+#[table("samples")]
+struct Sample {
+    name: String,
+    count: Result<i64, rusqlite::Error>,
+    #[bind(exemplar::convert::bind_strict_bool)]
+    #[extr(exemplar::convert::extr_strict_bool)]
+    valid: rusqlite::Result<bool>,
+}
+
+#[test]
+fn test_captured_errors() -> Result<()> {
+    use rusqlite::Connection;
+    use rusqlite::types::Value;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE samples (name, count, valid);
+        INSERT INTO samples VALUES ('good', 3, 1), ('bad', 'three', 2);
+    ")?;
+
+    Sample { name: "new".to_owned(), count: Ok(5), valid: Ok(false) }.insert(&conn)?;
+
+    let samples = conn
+        .prepare("SELECT * FROM samples ORDER BY rowid")?
+        .query_and_then([], Sample::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(samples.len(), 3);
+    
+    assert_eq!(samples[0].name, "good");
+    assert_eq!(samples[0].count.as_ref().ok(), Some(&3));
+    assert_eq!(samples[0].valid.as_ref().ok(), Some(&true));
+
+    // Each bad column is captured in its own field, without failing the row.
+    assert_eq!(samples[1].name, "bad");
+    assert!(matches!(samples[1].count, Err(rusqlite::Error::InvalidColumnType(..))));
+    assert!(matches!(samples[1].valid, Err(rusqlite::Error::IntegralValueOutOfRange(..))));
+
+    assert_eq!(samples[2].count.as_ref().ok(), Some(&5));
+    assert_eq!(samples[2].valid.as_ref().ok(), Some(&false));
+
+    // Missing columns are captured too.
+    let partial = conn.query_row("SELECT name FROM samples", [], Sample::from_row)?;
+
+    assert_eq!(partial.name, "good");
+    assert!(matches!(partial.count, Err(rusqlite::Error::InvalidColumnName(..))));
+
+    // Non-captured fields still propagate errors.
+    assert!(conn.query_row("SELECT count, valid FROM samples", [], Sample::from_row).is_err());
+
+    let values = Sample::try_from(vec![
+        Value::Text("values".to_owned()),
+        Value::Blob(vec![]),
+        Value::Integer(0),
+    ])?;
+
+    assert!(values.count.is_err());
+    assert_eq!(values.valid.as_ref().ok(), Some(&false));
+
+    // Errors can't be written back.
+    assert!(samples[1].insert(&conn).is_err());
+    assert!(samples[1].to_params().is_err());
+
+    Ok(())
+}
+
+// Multi-column case
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DateTime {
+    date: (u16, u8, u8),
+    time: (u8, u8, u8),
+}
+
+fn split_datetime(value: &DateTime) -> exemplar::BindMultiResult<2> {
+    use rusqlite::types::{ToSqlOutput, Value};
+
+    let (y, mo, d) = value.date;
+    let (h, mi, s) = value.time;
+
+    Ok([
+        ToSqlOutput::Owned(Value::Text(format!("{y:04}-{mo:02}-{d:02}"))),
+        ToSqlOutput::Owned(Value::Text(format!("{h:02}:{mi:02}:{s:02}"))),
+    ])
+}
+
+fn join_datetime(values: &[ValueRef; 2]) -> ExtrResult<DateTime> {
+    fn parts<const N: usize>(value: &ValueRef, sep: char) -> Result<[u16; N], FromSqlError> {
+        let parts: Vec<u16> = value
+            .as_str()?
+            .split(sep)
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|e| FromSqlError::Other(Box::new(e)))?;
+
+        parts
+            .try_into()
+            .map_err(|_| FromSqlError::InvalidType)
+    }
+
+    let [y, mo, d] = parts::<3>(&values[0], '-')?;
+    let [h, mi, s] = parts::<3>(&values[1], ':')?;
+
+    Ok(DateTime {
+        date: (y, mo as u8, d as u8),
+        time: (h as u8, mi as u8, s as u8),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Model)]
+#[table("appointments")]
+struct Appointment {
+    title: String,
+    #[bind_multi(["date", "time"], split_datetime)]
+    #[extr_multi(join_datetime)]
+    at: DateTime,
+    room: Option<String>,
+}
+
+#[test]
+fn test_multi_columns() -> Result<()> {
+    use rusqlite::Connection;
+    use rusqlite::types::Value;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE appointments (title, date, time, room);")?;
+
+    assert_eq!(Appointment::COLUMN_COUNT, 4);
+    assert_eq!(Appointment::metadata().columns, &["title", "date", "time", "room"]);
+    assert_eq!(Appointment::metadata().fields, &["title", "at", "at", "room"]);
+    assert_eq!(Appointment::metadata().bound, &[false, true, true, false]);
+
+    let appointments = vec![
+        Appointment {
+            title: "Dentist".to_owned(),
+            at: DateTime { date: (2024, 3, 9), time: (14, 30, 0) },
+            room: None,
+        },
+        Appointment {
+            title: "Standup".to_owned(),
+            at: DateTime { date: (2024, 12, 31), time: (9, 5, 7) },
+            room: Some("B2".to_owned()),
+        },
+    ];
+
+    appointments[0].insert(&conn)?;
+    appointments[1].insert_uncached(&conn)?;
+
+    let raw: Vec<(String, String)> = conn
+        .prepare("SELECT date, time FROM appointments ORDER BY rowid")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(raw, vec![
+        ("2024-03-09".to_owned(), "14:30:00".to_owned()),
+        ("2024-12-31".to_owned(), "09:05:07".to_owned()),
+    ]);
+
+    assert_eq!(Appointment::select_all(&conn)?, appointments);
+
+    let params = appointments[1].to_owned_params()?;
+
+    assert_eq!(params.len(), 4);
+    assert_eq!(params[1], ("date", Value::Text("2024-12-31".to_owned())));
+    assert_eq!(params[2], ("time", Value::Text("09:05:07".to_owned())));
+    assert_eq!(params[3], ("room", Value::Text("B2".to_owned())));
+
+    Ok(())
+}
+
+// Transparent newtype case
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct UserId(i64);
+
+#[derive(Debug, PartialEq, Eq)]
+struct Handle<T>(T);
+
+#[derive(Debug, Model)]
+#[table("members")]
+struct Member {
+    #[primary_key]
+    #[transparent]
+    id: UserId,
+    #[transparent]
+    handle: Handle<String>,
+    #[transparent]
+    referrer: Result<UserId, rusqlite::Error>,
+}
+
+#[test]
+fn test_transparent() -> Result<()> {
+    use std::collections::HashMap;
+
+    use rusqlite::Connection;
+    use rusqlite::types::Value;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE members (id INTEGER PRIMARY KEY, handle, referrer);")?;
+
+    let account = Member {
+        id: UserId(7),
+        handle: Handle("alice".to_owned()),
+        referrer: Ok(UserId(3)),
+    };
+
+    account.insert(&conn)?;
+
+    let stored: (i64, String, i64) = conn.query_row(
+        "SELECT id, handle, referrer FROM members",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+    )?;
+
+    assert_eq!(stored, (7, "alice".to_owned(), 3));
+
+    let loaded = Member::select_all(&conn)?;
+
+    assert_eq!(loaded[0].id, UserId(7));
+    assert_eq!(loaded[0].handle, account.handle);
+    assert_eq!(loaded[0].referrer.as_ref().ok(), Some(&UserId(3)));
+
+    assert_eq!(account.to_owned_params()?[0], ("id", Value::Integer(7)));
+
+    let map = HashMap::from([
+        ("id".to_owned(), Value::Integer(8)),
+        ("handle".to_owned(), Value::Text("bob".to_owned())),
+        ("referrer".to_owned(), Value::Null),
+    ]);
+
+    let bob = Member::from_value_map(&map)?;
+
+    assert_eq!(bob.id, UserId(8));
+    assert!(bob.referrer.is_err());
+
+    Ok(())
+}
+
+// Identity equality case
+#[derive(Debug, Model)]
+#[table("repositories")]
+#[composite_key]
+#[hash_by_pk]
+struct Repository {
+    #[primary_key]
+    owner: String,
+    #[primary_key]
+    slug: String,
+    stars: u32,
+}
+
+#[test]
+fn test_hash_by_pk() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let project = |owner: &str, slug: &str, stars| Repository {
+        owner: owner.to_owned(),
+        slug: slug.to_owned(),
+        stars,
+    };
+
+    let old = project("alice", "exemplar", 10);
+    let new = project("alice", "exemplar", 99);
+    let other = project("alice", "other", 10);
+    let swapped = project("exemplar", "alice", 10);
+
+    assert_eq!(old, new);
+    assert_eq!(hash(&old), hash(&new));
+
+    assert_ne!(old, other);
+    assert_ne!(old, swapped);
+
+    let set = HashSet::from([old, new, other, swapped]);
+
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn test_from_row_cached() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE accounts (username, pwd);
+        INSERT INTO accounts VALUES ('alice', X'01'), ('bob', X'0203');
+        CREATE TABLE appointments (title, date, time, room);
+        INSERT INTO appointments VALUES ('Dentist', '2024-03-09', '14:30:00', NULL);
+    ")?;
+
+    // Reordered, and reading the password through its alias.
+    let mut stmt = conn.prepare("SELECT pwd, 'ignored', username FROM accounts ORDER BY username")?;
+    let map = Account::column_indices(&stmt)?;
+
+    assert_eq!(map.indices(), &[2, 0]);
+
+    let cached = stmt
+        .query_and_then([], |row| Account::from_row_cached(row, &map))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let uncached = stmt
+        .query_and_then([], Account::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(cached, uncached);
+    assert_eq!(cached[1], Account { username: "bob".to_owned(), password: vec![2, 3] });
+
+    // Fields spanning several columns resolve each of them.
+    let mut stmt = conn.prepare("SELECT room, time, title, date FROM appointments")?;
+    let map = Appointment::column_indices(&stmt)?;
+
+    assert_eq!(map.indices(), &[2, 3, 1, 0]);
+
+    let appointment = stmt.query_row([], |row| Appointment::from_row_cached(row, &map))?;
+
+    assert_eq!(appointment.at, DateTime { date: (2024, 3, 9), time: (14, 30, 0) });
+
+    // Maps are Debug/Clone/Eq even when the model isn't.
+    conn.execute_batch("CREATE TABLE readings (sensor, value);")?;
+
+    let stmt = conn.prepare("SELECT value, sensor FROM readings")?;
+    let map = Reading::column_indices(&stmt)?;
+
+    assert_eq!(map.clone(), map);
+    assert_eq!(format!("{map:?}"), "ColumnMap { indices: [1, 0] }");
+
+    // Unresolvable columns fail up front.
+    let stmt = conn.prepare("SELECT username FROM accounts")?;
+
+    assert!(matches!(
+        Account::column_indices(&stmt),
+        Err(rusqlite::Error::InvalidColumnName(column)) if column == "password"
+    ));
+
+    Ok(())
+}
+
+// Per-deployment table case
+fn tenant_table() -> &'static str {
+    use std::sync::OnceLock;
+
+    static TABLE: OnceLock<String> = OnceLock::new();
+
+    TABLE.get_or_init(|| format!("{}_invoices", "acme"))
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("invoices")]
+#[resolve_table(tenant_table)]
+struct Invoice {
+    number: u32,
+    total: i64,
+    // Braces in the SQL must survive the table name being filled in.
+    #[select_expr("'{' || number || '}'")]
+    label: String,
+}
+
+#[test]
+fn test_resolve_table() -> Result<()> {
+    use exemplar::OnConflict;
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE invoices (number, total);
+        CREATE TABLE acme_invoices (number PRIMARY KEY, total);
+    ")?;
+
+    assert_eq!(Invoice::resolve_table(), "acme_invoices");
+    assert_eq!(Invoice::metadata().table, "acme_invoices");
+    assert_eq!(Invoice::metadata().insert_sql(OnConflict::Ignore), "INSERT OR IGNORE INTO acme_invoices (number, total) VALUES(:number, :total);");
+    assert_eq!(Person::resolve_table(), "people");
+
+    let invoice = Invoice { number: 1, total: 4200, label: "{1}".to_owned() };
+
+    invoice.insert(&conn)?;
+    invoice.insert_or(&conn, OnConflict::Ignore)?;
+
+    assert_eq!(Invoice::select_all(&conn)?, vec![invoice]);
+
+    let untouched: i64 = conn.query_row("SELECT COUNT(*) FROM invoices", [], |row| row.get(0))?;
+
+    assert_eq!(untouched, 0);
+
+    Ok(())
+}
+
+// Literal dump case
+#[derive(Debug, PartialEq, Model)]
+#[table("fixtures")]
+struct Fixture {
+    label: String,
+    payload: Vec<u8>,
+    weight: f64,
+    count: i64,
+    note: Option<String>,
+}
+
+#[test]
+fn test_to_insert_statement() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE fixtures (label, payload, weight, count, note);")?;
+
+    let fixtures = [
+        Fixture {
+            label: "it's a 'test'; DROP TABLE fixtures; --".to_owned(),
+            payload: vec![0x00, 0x0F, 0xAB, 0xFF],
+            weight: 0.1,
+            count: i64::MIN,
+            note: None,
+        },
+        Fixture {
+            label: "ünïcödé\nnewline".to_owned(),
+            payload: vec![],
+            weight: -1.5e-300,
+            count: 42,
+            note: Some("''".to_owned()),
+        },
+    ];
+
+    assert_eq!(
+        fixtures[0].to_insert_statement()?,
+        "INSERT INTO fixtures (label, payload, weight, count, note) \
+        VALUES ('it''s a ''test''; DROP TABLE fixtures; --', x'000fabff', 0.1, -9223372036854775808, NULL);"
+    );
+
+    for fixture in &fixtures {
+        conn.execute_batch(&fixture.to_insert_statement()?)?;
+    }
+
+    assert_eq!(Fixture::select_all(&conn)?, fixtures);
+
+    // Infinities survive the trip, and NaN is stored as NULL.
+    let statement = Fixture { weight: f64::NEG_INFINITY, ..Fixture::select_all(&conn)?.remove(1) }.to_insert_statement()?;
+
+    conn.execute_batch(&format!("DELETE FROM fixtures; {statement}"))?;
+
+    assert_eq!(Fixture::select_all(&conn)?[0].weight, f64::NEG_INFINITY);
+
+    let statement = Fixture { weight: f64::NAN, ..Fixture::select_all(&conn)?.remove(0) }.to_insert_statement()?;
+
+    assert!(statement.contains("x'', NULL, 42"));
+
+    Ok(())
+}
+
+// Type-level conversion case
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Home(PathBuf);
+
+impl exemplar::SqlConvert for Home {
+    fn bind(&self) -> BindResult {
+        exemplar::convert::bind_path(&self.0)
+    }
+
+    fn extr(value: &ValueRef) -> ExtrResult<Self> {
+        exemplar::convert::extr_path(value).map(Self)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("residents")]
+struct Resident {
+    name: String,
+    home: Home,
+    holiday_home: Option<Home>,
+}
+
+#[test]
+fn test_sql_convert() -> Result<()> {
+    use std::collections::HashMap;
+    use rusqlite::Connection;
+    use rusqlite::types::Value;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE residents (name, home, holiday_home);")?;
+
+    let alice = Resident {
+        name: "Alice".to_owned(),
+        home: Home("/home/alice".into()),
+        holiday_home: Some(Home("/mnt/villa".into())),
+    };
+
+    let bob = Resident {
+        name: "Bob".to_owned(),
+        home: Home("/home/bob".into()),
+        holiday_home: None,
+    };
+
+    alice.insert(&conn)?;
+    bob.insert(&conn)?;
+
+    assert_eq!(
+        alice.to_owned_params()?[1],
+        ("home", Value::Text("/home/alice".to_owned()))
+    );
+
+    let stored: Option<String> = conn.query_row("SELECT holiday_home FROM residents WHERE name = 'Bob'", [], |row| row.get(0))?;
+
+    assert_eq!(stored, None);
+    assert_eq!(Resident::select_all(&conn)?, vec![alice, bob]);
+
+    let values: HashMap<_, _> = [
+        ("name".to_owned(), Value::Text("Carol".to_owned())),
+        ("home".to_owned(), Value::Text("/home/carol".to_owned())),
+        ("holiday_home".to_owned(), Value::Null),
+    ].into();
+
+    let carol = Resident::from_value_map(&values)?;
+
+    assert_eq!(carol.home, Home("/home/carol".into()));
+    assert_eq!(carol.holiday_home, None);
+
+    // Conversion errors surface as usual.
+    conn.execute_batch("INSERT INTO residents VALUES ('Dave', 42, NULL);")?;
+
+    assert!(Resident::select_all(&conn).is_err());
+
+    Ok(())
+}
+
+// CHECK constraint case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("products")]
+#[check(inline = "CREATE TABLE products (sku, price CHECK(price >= 0) CHECK (price < 1000000), stock check ( stock>=0 ));")]
+struct Product {
+    sku: String,
+    #[sql_check("price >= 0")]
+    #[sql_check("price < 1000000")]
+    price: i64,
+    #[sql_check("stock >= 0")]
+    stock: i64,
+}
+
+#[test]
+fn test_sql_check() -> Result<()> {
+    use rusqlite::Connection;
+
+    let meta = Product::metadata();
+
+    assert_eq!(meta.checks, &[
+        ("price", "price >= 0"),
+        ("price", "price < 1000000"),
+        ("stock", "stock >= 0"),
+    ]);
+
+    assert_eq!(
+        meta.create_table_sql_untyped(),
+        "CREATE TABLE products (sku, price CHECK (price >= 0) CHECK (price < 1000000), stock CHECK (stock >= 0));"
+    );
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch(&meta.create_table_sql_untyped())?;
+
+    for expr in ["price >= 0", "price < 1000000", "stock >= 0"] {
+        exemplar::__check_constraint(&conn, "products", expr);
+    }
+
+    let widget = Product { sku: "W-1".to_owned(), price: 250, stock: 3 };
+
+    widget.insert(&conn)?;
+
+    assert!(matches!(
+        Product { sku: "W-2".to_owned(), price: -1, stock: 3 }.insert(&conn),
+        Err(rusqlite::Error::SqliteFailure(err, _)) if err.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_CHECK
+    ));
+
+    assert!(Product { sku: "W-3".to_owned(), price: 1, stock: -3 }.insert(&conn).is_err());
+    assert_eq!(Product::select_all(&conn)?, vec![widget]);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "lacks")]
+fn test_sql_check_mismatch() {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE products (sku, price CHECK (price > 0), stock);")
+        .unwrap();
+
+    exemplar::__check_constraint(&conn, "products", "price >= 0");
+}
+
+#[test]
+fn test_columns_for_select() -> Result<()> {
+    use rusqlite::Connection;
+
+    assert_eq!(User::columns_for_select("u"), "u.username AS username, u.home_dir AS home_dir, u.pwd AS pwd");
+    assert_eq!(
+        Appointment::columns_for_select("appt"),
+        "appt.title AS title, appt.date AS date, appt.time AS time, appt.room AS room"
+    );
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE users (username, home_dir, pwd);
+        CREATE TABLE sessions (username, pwd);
+        INSERT INTO users VALUES ('Alice', '/var/home/alice', X'01');
+        INSERT INTO sessions VALUES ('Alice', X'FF');
+    ")?;
+
+    let sql = format!(
+        "SELECT {}, s.pwd AS token FROM users u JOIN sessions s ON s.username = u.username",
+        User::columns_for_select("u")
+    );
+
+    let (user, token) = conn.query_row(&sql, [], |row| {
+        Ok((User::from_row(row)?, row.get::<_, Vec<u8>>("token")?))
+    })?;
+
+    assert_eq!(user.password, vec![0x01]);
+    assert_eq!(user.home_dir, PathBuf::from("/var/home/alice"));
+    assert_eq!(token, vec![0xFF]);
+
+    Ok(())
+}
+
+// Computed field case
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("users")]
+#[check(inline = "CREATE TABLE users (username, pwd);")]
+struct Credential {
+    username: String,
+    #[column("pwd")]
+    password: Vec<u8>,
+    #[select_expr("LENGTH(pwd)")]
+    #[column("pwd_len")]
+    password_len: i64,
+    #[select_expr("UPPER(username) || ':' || typeof(pwd)")]
+    label: String,
+}
+
+#[test]
+fn test_select_expr() -> Result<()> {
+    use std::collections::HashMap;
+    use rusqlite::Connection;
+    use rusqlite::types::Value;
+
+    let meta = Credential::metadata();
+
+    assert_eq!(meta.columns, &["username", "pwd"]);
+    assert_eq!(meta.computed, &[
+        ("pwd_len", "LENGTH(pwd)"),
+        ("label", "UPPER(username) || ':' || typeof(pwd)"),
+    ]);
+    assert_eq!(meta.insert_sql(exemplar::OnConflict::Abort), "INSERT INTO users (username, pwd) VALUES(:username, :pwd);");
+    assert_eq!(Credential::COLUMN_COUNT, 2);
+
+    assert_eq!(
+        Credential::columns_for_select("u"),
+        "u.username AS username, u.pwd AS pwd, LENGTH(pwd) AS pwd_len, UPPER(username) || ':' || typeof(pwd) AS label"
+    );
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE users (username, pwd);")?;
+
+    // Computed fields are never written.
+    let alice = Credential {
+        username: "alice".to_owned(),
+        password: b"hunter2".to_vec(),
+        password_len: -1,
+        label: "ignored".to_owned(),
+    };
+
+    alice.insert(&conn)?;
+
+    assert_eq!(alice.to_params()?.len(), 2);
+
+    let expected = Credential {
+        username: "alice".to_owned(),
+        password: b"hunter2".to_vec(),
+        password_len: 7,
+        label: "ALICE:blob".to_owned(),
+    };
+
+    assert_eq!(Credential::select_all(&conn)?, vec![expected]);
+    assert_eq!(Credential::select_by(&conn, "username", "alice")?[0].password_len, 7);
+    assert_eq!(Credential::load_map_by::<String>(&conn, "username")?["alice"].label, "ALICE:blob");
+
+    let mut stmt = Credential::prepare_select_all(&conn)?;
+    let map = Credential::column_indices(&stmt)?;
+
+    assert_eq!(map.indices(), &[0, 1, 2, 3]);
+
+    let cached = stmt.query_row([], |row| Credential::from_row_cached(row, &map))?;
+
+    assert_eq!(cached.password_len, 7);
+
+    let mut reused = Credential {
+        username: String::new(),
+        password: vec![],
+        password_len: 0,
+        label: String::new(),
+    };
+
+    stmt.query_row([], |row| reused.read_into(row))?;
+
+    assert_eq!(reused.label, "ALICE:blob");
+
+    // Reading from other sources expects the computed values too.
+    let values: HashMap<_, _> = [
+        ("username".to_owned(), Value::Text("bob".to_owned())),
+        ("pwd".to_owned(), Value::Blob(vec![1, 2])),
+        ("pwd_len".to_owned(), Value::Integer(2)),
+        ("label".to_owned(), Value::Text("BOB:/".to_owned())),
+    ].into();
+
+    assert_eq!(Credential::from_value_map(&values)?.password_len, 2);
+
+    let values = vec![
+        Value::Text("bob".to_owned()),
+        Value::Blob(vec![1, 2]),
+        Value::Integer(2),
+        Value::Text("BOB:/".to_owned()),
+    ];
+
+    assert_eq!(Credential::try_from(values)?.label, "BOB:/");
+    assert!(Credential::try_from(vec![Value::Text("bob".to_owned()), Value::Blob(vec![])]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_with() -> Result<()> {
+    use exemplar::SyncReport;
+    use rusqlite::Connection;
+
+    let mut conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE repositories (owner, slug, stars, PRIMARY KEY (owner, slug));
+        INSERT INTO repositories VALUES
+            ('alice', 'exemplar', 10),
+            ('alice', 'dotfiles', 1),
+            ('bob', 'exemplar', 3),
+            ('bob', 'blog', 7);
+    ")?;
+
+    let repo = |owner: &str, slug: &str, stars| Repository {
+        owner: owner.to_owned(),
+        slug: slug.to_owned(),
+        stars,
+    };
+
+    let desired = [
+        // Unchanged
+        repo("alice", "exemplar", 10),
+        // Changed
+        repo("bob", "exemplar", 4),
+        // New - the key only partially overlaps existing rows.
+        repo("bob", "dotfiles", 0),
+        repo("carol", "blog", 2),
+    ];
+
+    let report = Repository::sync_with(&mut conn, &desired)?;
+
+    assert_eq!(report, SyncReport { inserted: 2, updated: 1, deleted: 2 });
+
+    let mut rows: Vec<_> = conn
+        .prepare("SELECT owner, slug, stars FROM repositories ORDER BY owner, slug")?
+        .query_and_then([], Repository::from_row)?
+        .map(|repo| repo.map(|repo| (repo.owner, repo.slug, repo.stars)))
+        .collect::<Result<_, _>>()?;
+
+    rows.sort();
+
+    assert_eq!(rows, vec![
+        ("alice".to_owned(), "exemplar".to_owned(), 10),
+        ("bob".to_owned(), "dotfiles".to_owned(), 0),
+        ("bob".to_owned(), "exemplar".to_owned(), 4),
+        ("carol".to_owned(), "blog".to_owned(), 2),
+    ]);
+
+    // Syncing again is a no-op.
+    assert_eq!(Repository::sync_with(&mut conn, &desired)?, SyncReport::default());
+
+    // Failures roll everything back.
+    let duplicated = [repo("dave", "blog", 1), repo("dave", "blog", 2)];
+
+    assert!(Repository::sync_with(&mut conn, &duplicated).is_err());
+    assert_eq!(Repository::select_all(&conn)?.len(), 4);
+
+    // Syncing with nothing empties the table.
+    assert_eq!(Repository::sync_with(&mut conn, &[])?.deleted, 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_savepoint_rollback() -> Result<()> {
+    use rusqlite::Connection;
+
+    let mut conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE people (name, age, alive);")?;
+
+    let person = |name: &str| Person { name: name.to_owned(), age: 30, alive: true };
+
+    let mut tx = conn.transaction()?;
+
+    person("Alice").insert(&tx)?;
+
+    {
+        let mut sp = tx.savepoint()?;
+
+        person("Bob").insert(&sp)?;
+
+        {
+            let nested = sp.savepoint()?;
+
+            person("Carol").insert(&nested)?;
+            nested.commit()?;
+        }
+
+        assert_eq!(Person::select_all(&sp)?.len(), 3);
+
+        sp.rollback()?;
+
+        // The savepoint is still usable after rolling back, and the cached statement along with it.
+        person("Dave").insert(&sp)?;
+        sp.commit()?;
+    }
+
+    person("Erin").insert(&tx)?;
+    tx.commit()?;
+
+    let names: Vec<_> = Person::select_all(&conn)?
+        .into_iter()
+        .map(|person| person.name)
+        .collect();
+
+    assert_eq!(names, ["Alice", "Dave", "Erin"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_filter() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0), ('Carol', 17, 1), ('Alice', 16, 1);
+    ")?;
+
+    let filter = Person::filter()
+        .name_eq("Alice")
+        .age_gt(18);
+
+    assert_eq!(filter.sql(), "SELECT name, age, alive FROM people WHERE name IS ? AND age > ?;");
+
+    let people = filter.fetch(&conn)?;
+
+    assert_eq!(people, vec![Person { name: "Alice".to_owned(), age: 21, alive: true }]);
+    assert_eq!(Person::filter().fetch(&conn)?.len(), 4);
+
+    // Fields with a #[bind] function are bound through it.
+    conn.execute_batch(include_str!("schema.sql"))?;
+
+    let alice = User {
+        username: "Alice".to_owned(),
+        home_dir: "/var/home/alice".into(),
+        password: b"hunter2".to_vec(),
+    };
+
+    alice.insert(&conn)?;
+
+    let users = User::filter()
+        .home_dir_eq(&PathBuf::from("/var/home/alice"))
+        .password_ne(b"hunter3".as_slice())
+        .fetch(&conn)?;
+
+    assert_eq!(users, vec![alice]);
+
+    Ok(())
+}
+
+// Blob-backed type that only sometimes owns its bytes.
+#[derive(Debug, PartialEq, Eq)]
+enum Payload {
+    Bytes(Vec<u8>),
+    Empty,
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("attachments")]
+struct Attachment {
+    name: String,
+    #[bind(bind_payload)]
+    #[extr(extr_payload)]
+    payload: Payload,
+    #[bind(bind_payload, lift)]
+    #[extr(extr_payload, lift)]
+    thumbnail: Option<Payload>,
+}
+
+fn bind_payload(value: &Payload) -> rusqlite::Result<std::borrow::Cow<'_, [u8]>> {
+    use std::borrow::Cow;
+
+    match value {
+        Payload::Bytes(bytes) => Ok(Cow::Borrowed(bytes)),
+        Payload::Empty => Ok(Cow::Owned(Vec::new())),
+    }
+}
+
+fn extr_payload(value: &ValueRef) -> ExtrResult<Payload> {
+    match value.as_blob()? {
+        [] => Ok(Payload::Empty),
+        bytes => Ok(Payload::Bytes(bytes.to_vec())),
+    }
+}
+
+#[test]
+fn test_borrowed_bind() -> Result<()> {
+    use rusqlite::Connection;
+    use rusqlite::ToSql;
+    use rusqlite::types::ToSqlOutput;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("CREATE TABLE attachments (name, payload BLOB, thumbnail BLOB);")?;
+
+    let attachment = Attachment {
+        name: "report.pdf".to_owned(),
+        payload: Payload::Bytes(b"%PDF-1.7".to_vec()),
+        thumbnail: Some(Payload::Empty),
+    };
+
+    // The payload is bound without copying its bytes.
+    let params = attachment.to_params()?;
+
+    let ToSqlOutput::Borrowed(ValueRef::Blob(bytes)) = params[1].1.to_sql()? else {
+        panic!("payload should be bound as a borrowed blob")
+    };
+
+    let Payload::Bytes(payload) = &attachment.payload else { unreachable!() };
+
+    assert_eq!(bytes.as_ptr(), payload.as_ptr());
+
+    drop(params);
+
+    attachment.insert(&conn)?;
+
+    let empty = Attachment {
+        name: "empty.txt".to_owned(),
+        payload: Payload::Empty,
+        thumbnail: None,
+    };
+
+    empty.insert(&conn)?;
+
+    assert_eq!(Attachment::select_all(&conn)?, vec![attachment, empty]);
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("entities")]
+#[column_prefix("post_")]
+#[check(inline = "CREATE TABLE entities (post_title, post_body, author_id);")]
+struct Post {
+    title: String,
+    body: String,
+    #[column("author_id")]
+    author: i64,
+}
+
+#[test]
+fn test_column_prefix() -> Result<()> {
+    use rusqlite::Connection;
+
+    assert_eq!(Post::metadata().columns, &["post_title", "post_body", "author_id"]);
+    assert_eq!(Post::metadata().fields, &["title", "body", "author"]);
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("
+        CREATE TABLE entities (kind, post_title, post_body, author_id);
+        INSERT INTO entities (kind, post_title, post_body, author_id) VALUES ('post', 'Hello', 'First!', 1);
+    ")?;
+
+    let post = Post {
+        title: "Again".to_owned(),
+        body: "Second!".to_owned(),
+        author: 2,
+    };
+
+    post.insert(&conn)?;
+
+    let (title, author): (String, i64) = conn.query_row(
+        "SELECT post_title, author_id FROM entities WHERE rowid = 2",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?))
+    )?;
+
+    assert_eq!((title.as_str(), author), ("Again", 2));
+
+    let posts = Post::select_all(&conn)?;
+
+    assert_eq!(posts[0], Post { title: "Hello".to_owned(), body: "First!".to_owned(), author: 1 });
+    assert_eq!(posts[1], post);
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("digests")]
+struct Digest {
+    name: String,
+    sha256: [u8; 32],
+}
+
+#[test]
+fn test_byte_array() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("CREATE TABLE digests (name, sha256 BLOB);")?;
+
+    let digest = Digest {
+        name: "empty".to_owned(),
+        sha256: *b"\xe3\xb0\xc4\x42\x98\xfc\x1c\x14\x9a\xfb\xf4\xc8\x99\x6f\xb9\x24\x27\xae\x41\xe4\x64\x9b\x93\x4c\xa4\x95\x99\x1b\x78\x52\xb8\x55",
+    };
+
+    digest.insert(&conn)?;
+
+    assert_eq!(Digest::select_all(&conn)?, vec![digest]);
+
+    conn.execute("INSERT INTO digests VALUES ('truncated', x'e3b0c442')", [])?;
+
+    let err = Digest::select_by(&conn, "name", "truncated").unwrap_err();
+
+    assert!(
+        matches!(&err, rusqlite::Error::FromSqlConversionFailure(_, _, inner) if inner.to_string().contains("32")),
+        "unexpected error: {err:?}"
+    );
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Admin;
+
+// PhantomData fields are skipped without #[skip].
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("grants")]
+#[check(inline = "CREATE TABLE grants (username, scope);")]
+struct Grant {
+    username: String,
+    scope: String,
+    role: std::marker::PhantomData<Admin>,
+}
+
+#[test]
+fn test_phantom_data() -> Result<()> {
+    use std::marker::PhantomData;
+
+    use rusqlite::Connection;
+
+    assert_eq!(Grant::COLUMN_COUNT, 2);
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("CREATE TABLE grants (username, scope);")?;
+
+    let grant = Grant {
+        username: "alice".to_owned(),
+        scope: "billing".to_owned(),
+        role: PhantomData,
+    };
+
+    grant.insert(&conn)?;
+
+    assert_eq!(Grant::select_all(&conn)?, vec![grant]);
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("legacy_contacts")]
+#[columns("CONTACT_NAME", "CONTACT_PHONE", "unused")]
+#[check(inline = "CREATE TABLE legacy_contacts (CONTACT_NAME, CONTACT_PHONE, EMAIL_ADDR);")]
+struct Contact {
+    name: String,
+    phone: String,
+    #[column("EMAIL_ADDR")]
+    email: String,
+    #[skip]
+    cached: Option<String>,
+}
+
+#[test]
+fn test_columns() -> Result<()> {
+    use rusqlite::Connection;
+
+    assert_eq!(Contact::metadata().columns, &["CONTACT_NAME", "CONTACT_PHONE", "EMAIL_ADDR"]);
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("CREATE TABLE legacy_contacts (CONTACT_NAME, CONTACT_PHONE, EMAIL_ADDR);")?;
+
+    let contact = Contact {
+        name: "Alice".to_owned(),
+        phone: "555-0100".to_owned(),
+        email: "alice@example.com".to_owned(),
+        cached: None,
+    };
+
+    contact.insert(&conn)?;
+
+    let phone: String = conn.query_row("SELECT CONTACT_PHONE FROM legacy_contacts", [], |row| row.get(0))?;
+
+    assert_eq!(phone, "555-0100");
+    assert_eq!(Contact::select_all(&conn)?, vec![contact]);
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Model)]
+#[table("notes")]
+struct Note {
+    title: String,
+    body: Option<String>,
+    score: f64,
+}
+
+#[test]
+fn test_export_csv() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("CREATE TABLE notes (title, body, score);")?;
+
+    let notes = vec![
+        Note { title: "plain".to_owned(), body: Some("text".to_owned()), score: 1.0 },
+        Note { title: "commas, \"quotes\"".to_owned(), body: Some("multiple\r\nlines".to_owned()), score: -0.25 },
+        Note { title: "empty".to_owned(), body: None, score: 1e10 },
+    ];
+
+    for note in &notes {
+        note.insert(&conn)?;
+    }
+
+    let mut out = Vec::new();
+
+    Note::export_csv(&conn, &mut out)?;
+
+    assert!(out.starts_with(b"title,body,score\r\nplain,text,1.0\r\n\"commas, \"\"quotes\"\"\",\"multiple\r\nlines\",-0.25\r\n"));
+
+    let mut reader = csv::Reader::from_reader(out.as_slice());
+
+    assert_eq!(reader.headers()?, vec!["title", "body", "score"]);
+
+    let parsed = reader
+        .records()
+        .map(|record| {
+            let record = record?;
+
+            Ok(Note {
+                title: record[0].to_owned(),
+                body: Some(record[1].to_owned()).filter(|body| !body.is_empty()),
+                score: record[2].parse()?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    assert_eq!(parsed, notes);
+
+    // Failures are reported by which side of the export they happened on.
+    struct Broken;
+
+    impl std::io::Write for Broken {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    assert!(matches!(
+        Note::export_csv(&conn, Broken),
+        Err(exemplar::ExportError::Io(err)) if err.to_string() == "disk full"
+    ));
+
+    conn.execute_batch("DROP TABLE notes;")?;
+
+    assert!(matches!(
+        Note::export_csv(&conn, Vec::new()),
+        Err(exemplar::ExportError::Sql(_))
+    ));
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("services")]
+struct Service {
+    name: String,
+    #[bind(exemplar::convert::bind_display)]
+    #[extr(exemplar::convert::extr_from_str, default = default_port)]
+    port: u16,
+    #[bind(exemplar::convert::bind_display, lift)]
+    #[extr(exemplar::convert::extr_from_str, default = default_port, lift)]
+    admin_port: Option<u16>,
+}
+
+fn default_port() -> u16 {
+    8080
+}
+
+#[test]
+fn test_extr_default() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("
+        CREATE TABLE services (name, port, admin_port);
+        INSERT INTO services VALUES ('good', '443', '8443'), ('bad', 'https', NULL), ('worse', '99999', 'admin');
+    ")?;
+
+    let services = Service::select_all(&conn)?;
+
+    assert_eq!(services, vec![
+        Service { name: "good".to_owned(), port: 443, admin_port: Some(8443) },
+        // NULL is still None, rather than the default.
+        Service { name: "bad".to_owned(), port: 8080, admin_port: None },
+        Service { name: "worse".to_owned(), port: 8080, admin_port: Some(8080) },
+    ]);
+
+    Ok(())
+}
+
+#[derive(Debug, Model)]
+#[table("tickets")]
+#[hash_by_pk]
+#[order_by_pk]
+struct Ticket {
+    #[primary_key]
+    id: i64,
+    title: String,
+    priority: u8,
+}
+
+#[test]
+fn test_order_by_pk() {
+    let ticket = |id, title: &str, priority| Ticket { id, title: title.to_owned(), priority };
+
+    let mut tickets: Vec<Ticket> = [(3, "a", 1), (1, "c", 3), (2, "b", 2)]
+        .into_iter()
+        .map(|(id, title, priority)| ticket(id, title, priority))
+        .collect();
+
+    tickets.sort();
+
+    let ids: Vec<_> = tickets.iter().map(|ticket| ticket.id).collect();
+
+    assert_eq!(ids, [1, 2, 3]);
+
+    // Other fields are ignored, consistently with equality.
+    assert_eq!(ticket(1, "a", 1).cmp(&ticket(1, "z", 9)), std::cmp::Ordering::Equal);
+    assert!(ticket(1, "z", 9) < ticket(2, "a", 1));
+}
+
+#[test]
+fn test_update() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("
+        CREATE TABLE tickets (id INTEGER PRIMARY KEY, title, priority);
+        INSERT INTO tickets VALUES (1, 'Crash on start', 1), (2, 'Typo', 5);
+        CREATE TABLE repositories (owner, slug, stars, PRIMARY KEY (owner, slug));
+        INSERT INTO repositories VALUES ('alice', 'exemplar', 10), ('alice', 'other', 10), ('bob', 'exemplar', 10);
+    ")?;
+
+    Ticket { id: 1, title: "Crash on startup".to_owned(), priority: 0 }.update(&conn)?;
+
+    let (title, priority): (String, u8) = conn.query_row(
+        "SELECT title, priority FROM tickets WHERE id = 1",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?))
+    )?;
+
+    assert_eq!((title.as_str(), priority), ("Crash on startup", 0));
+
+    // Other rows are untouched, and missing rows are a no-op.
+    Ticket { id: 3, title: "Missing".to_owned(), priority: 9 }.update(&conn)?;
+
+    let titles: Vec<String> = conn
+        .prepare("SELECT title FROM tickets ORDER BY id")?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    assert_eq!(titles, ["Crash on startup", "Typo"]);
+
+    // Composite keys match on every key column.
+    Repository { owner: "alice".to_owned(), slug: "exemplar".to_owned(), stars: 99 }.update(&conn)?;
+
+    let stars: Vec<u32> = conn
+        .prepare("SELECT stars FROM repositories ORDER BY owner, slug")?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    assert_eq!(stars, [99, 10, 10]);
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("archive.tickets")]
+struct ArchivedTicket {
+    id: i64,
+    title: String,
+}
+
+#[test]
+fn test_table_exists() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    assert!(!Person::table_exists(&conn)?);
+
+    conn.execute_batch(&Person::metadata().create_table_sql_untyped())?;
+
+    assert!(Person::table_exists(&conn)?);
+    assert!(!Ticket::table_exists(&conn)?);
+
+    // Temporary tables count too.
+    conn.execute_batch("CREATE TEMP TABLE TICKETS (id, title, priority);")?;
+
+    assert!(Ticket::table_exists(&conn)?);
+
+    // Qualified names only search their own schema.
+    conn.execute_batch("
+        ATTACH DATABASE ':memory:' AS archive;
+        CREATE TABLE main.tickets (id, title, priority);
+    ")?;
+
+    assert!(!ArchivedTicket::table_exists(&conn)?);
+
+    conn.execute_batch(&ArchivedTicket::metadata().create_table_sql_untyped())?;
+
+    assert!(ArchivedTicket::table_exists(&conn)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_delete() -> Result<()> {
+    use exemplar::PrimaryKey;
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("
+        CREATE TABLE tickets (id INTEGER PRIMARY KEY, title, priority);
+        INSERT INTO tickets VALUES (1, 'Crash on start', 1), (2, 'Typo', 5);
+        CREATE TABLE repositories (owner, slug, stars, PRIMARY KEY (owner, slug));
+        INSERT INTO repositories VALUES ('alice', 'exemplar', 10), ('alice', 'other', 10), ('bob', 'exemplar', 10);
+    ")?;
+
+    // Only the key is bound...
+    fn names(params: &[(&str, exemplar::Parameter)]) -> Vec<String> {
+        params.iter().map(|(name, _)| name.to_string()).collect()
+    }
+
+    assert_eq!(names(&Ticket { id: 1, title: String::new(), priority: 0 }.to_key_params()?), [":id"]);
+    assert_eq!(names(&Repository { owner: String::new(), slug: String::new(), stars: 0 }.to_key_params()?), [":owner", ":slug"]);
+
+    // ...and matched.
+    Ticket { id: 1, title: "Something else".to_owned(), priority: 9 }.delete(&conn)?;
+    Ticket { id: 3, title: "Missing".to_owned(), priority: 9 }.delete(&conn)?;
+
+    assert_eq!(exemplar::query_scalar::<String>(&conn, "SELECT title FROM tickets", [])?, "Typo");
+
+    // Composite keys match on every key column.
+    Repository { owner: "alice".to_owned(), slug: "exemplar".to_owned(), stars: 0 }.delete(&conn)?;
+
+    let remaining: Vec<(String, String)> = conn
+        .prepare("SELECT owner, slug FROM repositories ORDER BY owner, slug")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    assert_eq!(remaining, [
+        ("alice".to_owned(), "other".to_owned()),
+        ("bob".to_owned(), "exemplar".to_owned()),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_select_all_with_rowid() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()?;
+
+    conn.execute_batch("
+        CREATE TABLE notes (title, body, score);
+        INSERT INTO notes VALUES ('first', NULL, 1.0), ('second', 'text', 2.0), ('third', NULL, 3.0);
+        DELETE FROM notes WHERE title = 'second';
+        INSERT INTO notes VALUES ('fourth', NULL, 4.0);
+    ")?;
+
+    let pairs = Note::select_all_with_rowid(&conn)?;
+
+    let rowids: Vec<i64> = conn
+        .prepare("SELECT rowid FROM notes")?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    assert_eq!(pairs.iter().map(|(rowid, _)| *rowid).collect::<Vec<_>>(), rowids);
+
+    for (rowid, note) in &pairs {
+        let title: String = conn.query_row("SELECT title FROM notes WHERE rowid = ?", [rowid], |row| row.get(0))?;
+        assert_eq!(note.title, title);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+
+use exemplar::Model;
+use exemplar::interact::InteractModel;
+
+#[derive(Debug, Clone, PartialEq, Eq, Model)]
+#[table("people")]
+struct Person {
+    name: String,
+    age: u16,
+}
+
+#[cfg(feature = "deadpool")]
+#[tokio::test]
+async fn test_deadpool_interact() -> Result<()> {
+    use deadpool_sqlite::{Config, Runtime};
+    use exemplar::OnConflict;
+
+    let pool = Config::new(":memory:").create_pool(Runtime::Tokio1)?;
+    let conn = pool.get().await?;
+
+    conn.interact(|conn| conn.execute_batch("CREATE TABLE people (name PRIMARY KEY, age);"))
+        .await
+        .unwrap()?;
+
+    let alice = Person { name: "Alice".to_owned(), age: 21 };
+    let bob = Person { name: "Bob".to_owned(), age: 90 };
+
+    conn.interact(alice.clone().insert_interact()).await.unwrap()?;
+
+    // Conflicts surface as ordinary rusqlite errors inside the interaction.
+    assert!(conn.interact(alice.clone().insert_interact()).await.unwrap().is_err());
+
+    conn.interact(Person { age: 22, ..alice.clone() }.insert_or_interact(OnConflict::Replace))
+        .await
+        .unwrap()?;
+
+    conn.interact(Person::insert_all_interact(vec![bob.clone()])).await.unwrap()?;
+
+    let mut people = conn.interact(Person::select_all_interact()).await.unwrap()?;
+    people.sort_by(|a, b| a.name.cmp(&b.name));
+
+    assert_eq!(people, vec![Person { age: 22, ..alice }, bob]);
+
+    Ok(())
+}
+
+#[test]
+fn test_interact_closures() -> Result<()> {
+    let mut conn = rusqlite::Connection::open_in_memory()?;
+
+    conn.execute_batch("CREATE TABLE people (name, age);")?;
+
+    let carol = Person { name: "Carol".to_owned(), age: 45 };
+
+    (carol.clone().insert_interact())(&mut conn)?;
+
+    assert_eq!((Person::select_all_interact())(&mut conn)?, vec![carol]);
+
+    Ok(())
+}
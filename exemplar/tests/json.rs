@@ -0,0 +1,119 @@
+#![cfg(feature = "json")]
+
+use std::collections::{
+    BTreeSet,
+    HashSet,
+};
+
+use anyhow::Result;
+
+use exemplar::Model;
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("articles")]
+struct Article {
+    title: String,
+    #[bind(exemplar::convert::bind_json)]
+    #[extr(exemplar::convert::extr_json)]
+    tags: BTreeSet<String>,
+    #[bind(exemplar::convert::bind_json_set)]
+    #[extr(exemplar::convert::extr_json)]
+    authors: HashSet<String>,
+}
+
+#[test]
+fn test_json_sets() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE articles (title, tags, authors);
+    ")?;
+
+    let names = ["mallory", "alice", "eve", "bob", "carol", "dave"];
+
+    let article = Article {
+        title: "Sets".to_owned(),
+        tags: ["sqlite", "rust", "json"].map(str::to_owned).into(),
+        authors: names.map(str::to_owned).into(),
+    };
+
+    article.insert(&conn)?;
+
+    let (tags, authors): (String, String) = conn.query_row(
+        "SELECT tags, authors FROM articles",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?))
+    )?;
+
+    assert_eq!(tags, r#"["json","rust","sqlite"]"#);
+    assert_eq!(authors, r#"["alice","bob","carol","dave","eve","mallory"]"#);
+
+    // Differently-ordered (and differently-hashed) sets produce identical text.
+    let mut reversed = names;
+    reversed.reverse();
+
+    let again = Article {
+        title: "Sets".to_owned(),
+        tags: ["json", "sqlite", "rust"].map(str::to_owned).into(),
+        authors: reversed.map(str::to_owned).into(),
+    };
+
+    let params = again.to_owned_params()?;
+
+    assert_eq!(params[1].1, rusqlite::types::Value::Text(tags));
+    assert_eq!(params[2].1, rusqlite::types::Value::Text(authors));
+
+    assert_eq!(Article::select_all(&conn)?, vec![article]);
+
+    conn.execute("UPDATE articles SET tags = 'not json'", [])?;
+
+    assert!(Article::select_all(&conn).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_describe() -> Result<()> {
+    use std::path::PathBuf;
+
+    use exemplar::{BindResult, ExtrResult};
+    use rusqlite::types::{ToSqlOutput, Value, ValueRef};
+
+    fn bind_path(value: &std::path::Path) -> BindResult {
+        Ok(ToSqlOutput::Owned(Value::Text(value.to_string_lossy().into_owned())))
+    }
+
+    fn extr_path(value: &ValueRef) -> ExtrResult<PathBuf> {
+        Ok(PathBuf::from(value.as_str()?))
+    }
+
+    #[derive(Model)]
+    #[table("users")]
+    struct User {
+        username: String,
+        #[bind(bind_path)]
+        #[extr(extr_path)]
+        home_dir: PathBuf,
+        #[column("pwd")]
+        password: Vec<u8>,
+    }
+
+    let description: serde_json::Value = serde_json::from_str(
+        &User::metadata().describe()
+    )?;
+
+    assert_eq!(description, serde_json::json!({
+        "model": "User",
+        "table": "users",
+        "columns": [
+            { "field": "username", "column": "username", "has_bind": false },
+            { "field": "home_dir", "column": "home_dir", "has_bind": true },
+            { "field": "password", "column": "pwd", "has_bind": false },
+        ]
+    }));
+
+    Ok(())
+}
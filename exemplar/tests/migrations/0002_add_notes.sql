@@ -0,0 +1,2 @@
+-- Depends on 0001 having run first.
+ALTER TABLE releases ADD COLUMN notes;
@@ -69,4 +69,116 @@ fn test_person() -> Result<()> {
     assert_eq!(bob.age, 90);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+record! {
+    Name => Author,
+    name => String,
+    age  => u16,
+}
+
+record! {
+    Name => Post,
+    title  => String,
+    author => Author [nested],
+}
+
+#[test]
+fn test_nested() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        CREATE TABLE posts (title, author);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0);
+        INSERT INTO posts VALUES ('Hello', 'Alice'), ('Goodbye', 'Bob');
+    ")?;
+
+    assert_eq!(Author::COLUMN_COUNT, 2);
+    assert_eq!(Post::COLUMN_COUNT, 3);
+
+    let post = conn.query_row(
+        "SELECT posts.title, people.name, people.age FROM posts JOIN people ON posts.author = people.name WHERE title = 'Hello'",
+        [],
+        Post::from_row
+    )?;
+
+    assert_eq!(post.title, "Hello");
+    assert_eq!(post.author.name, "Alice");
+    assert_eq!(post.author.age, 21);
+
+    // The post's title is selected as `name`, colliding with the author's name - so read positionally instead.
+    let post = conn.query_row(
+        "SELECT posts.title AS name, people.name, people.age FROM posts JOIN people ON posts.author = people.name WHERE title = 'Goodbye'",
+        [],
+        |row| Post::from_row_offset(row, 0)
+    )?;
+
+    assert_eq!(post.title, "Goodbye");
+    assert_eq!(post.author.name, "Bob");
+    assert_eq!(post.author.age, 90);
+
+    // Offsets skip over leading columns.
+    let author = conn.query_row(
+        "SELECT 'ignored', name, age FROM people WHERE name = 'Bob'",
+        [],
+        |row| Author::from_row_offset(row, 1)
+    )?;
+
+    assert_eq!(author.name, "Bob");
+    assert_eq!(author.age, 90);
+
+    Ok(())
+}
+
+record! {
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    Name => NameKey,
+    name => std::borrow::Cow<'static, str>,
+    age  => u16,
+}
+
+#[test]
+fn test_cow() -> Result<()> {
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, age, alive);
+        INSERT INTO people VALUES ('Alice', 21, 1), ('Bob', 90, 0);
+    ")?;
+
+    let keys = conn
+        .prepare("SELECT name, age FROM people ORDER BY name")?
+        .query_and_then([], NameKey::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert!(matches!(keys[0].name, Cow::Owned(_)));
+
+    let ages: HashMap<Cow<str>, u16> = keys
+        .into_iter()
+        .map(|key| (key.name, key.age))
+        .collect();
+
+    // Lookups borrow, rather than allocating a key.
+    assert_eq!(ages[&Cow::Borrowed("Alice")], 21);
+    assert_eq!(ages.get("Bob"), Some(&90));
+
+    let key = conn.query_row(
+        "SELECT name, age FROM people WHERE name = 'Bob'",
+        [],
+        |row| NameKey::from_row_offset(row, 0)
+    )?;
+
+    assert_eq!(key, NameKey { name: Cow::Borrowed("Bob"), age: 90 });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "returning")]
+
+use anyhow::Result;
+
+use exemplar::Model;
+
+#[derive(Debug, PartialEq, Eq, Model)]
+#[table("people")]
+struct Person {
+    name: String,
+    age: u16,
+}
+
+#[test]
+fn test_insert_if_absent() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name UNIQUE, age);
+    ")?;
+
+    let alice = Person {
+        name: "Alice".to_owned(),
+        age: 21,
+    };
+
+    let impostor = Person {
+        name: "Alice".to_owned(),
+        age: 90,
+    };
+
+    assert!(alice.insert_if_absent(&conn)?);
+    assert!(!impostor.insert_if_absent(&conn)?);
+    assert!(!alice.insert_if_absent(&conn)?);
+
+    let mut stmt = conn.prepare("SELECT * FROM people")?;
+
+    let people: Vec<_> = stmt
+        .query_and_then([], Person::from_row)?
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(people, vec![alice]);
+
+    Ok(())
+}
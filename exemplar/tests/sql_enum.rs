@@ -51,4 +51,87 @@ fn test_person() -> Result<()> {
     assert_eq!(bob, iter.next().unwrap()?);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+impl std::fmt::Display for Gender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Male => f.write_str("male"),
+            Self::Female => f.write_str("female"),
+        }
+    }
+}
+
+impl std::str::FromStr for Gender {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "male" => Ok(Self::Male),
+            "female" => Ok(Self::Female),
+            _ => Err(format!("unknown gender '{s}'"))
+        }
+    }
+}
+
+#[derive(Model, Debug, PartialEq, Eq)]
+#[table("people")]
+pub struct TextPerson {
+    pub name: String,
+    #[as_text]
+    pub gender: Gender,
+    pub age: u16,
+}
+
+#[test]
+fn test_as_text() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("
+        CREATE TABLE people (name, gender, age);
+    ")?;
+
+    let alice = TextPerson {
+        name: "Alice".to_owned(),
+        gender: Gender::Female,
+        age: 21,
+    };
+
+    let bob = Person {
+        name: "Bob".to_owned(),
+        gender: Gender::Male,
+        age: 90,
+    };
+
+    alice.insert(&conn)?;
+    bob.insert(&conn)?;
+
+    let genders: Vec<(String, String)> = conn
+        .prepare("SELECT name, typeof(gender) FROM people ORDER BY name ASC")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    // The enum's own ToSql is untouched - only the annotated field is stored as text.
+    assert_eq!(genders, [
+        ("Alice".to_owned(), "text".to_owned()),
+        ("Bob".to_owned(), "integer".to_owned()),
+    ]);
+
+    let mut stmt = conn.prepare("SELECT * FROM people WHERE name = 'Alice'")?;
+    let mut iter = stmt.query_and_then([], TextPerson::from_row)?;
+
+    assert_eq!(alice, iter.next().unwrap()?);
+    drop(iter);
+
+    conn.execute_batch("UPDATE people SET gender = 'other' WHERE name = 'Alice';")?;
+
+    let mut iter = stmt.query_and_then([], TextPerson::from_row)?;
+    let err = iter.next().unwrap().unwrap_err();
+
+    assert!(err.to_string().contains("unknown gender 'other'"));
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anyhow::Result;
+
+use exemplar::{Model, SqlTransparent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SqlTransparent)]
+struct UserId(i64);
+
+#[derive(Debug, Clone, PartialEq, Eq, SqlTransparent)]
+struct Username(String);
+
+#[derive(Debug, Clone, PartialEq, Eq, SqlTransparent)]
+struct Tagged<T>(T);
+
+#[derive(Debug, Clone, PartialEq, Eq, Model)]
+#[table("users")]
+struct User {
+    #[primary_key]
+    id: UserId,
+    name: Username,
+    nickname: Option<Username>,
+    score: Tagged<u32>,
+}
+
+#[test]
+fn test_sql_transparent() -> Result<()> {
+    use rusqlite::Connection;
+
+    let conn = Connection::open_in_memory()
+        .unwrap();
+
+    conn.execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, nickname TEXT, score INTEGER);")?;
+
+    let users = vec![
+        User {
+            id: UserId(1),
+            name: Username("alice".to_owned()),
+            nickname: Some(Username("Al".to_owned())),
+            score: Tagged(10),
+        },
+        User {
+            id: UserId(2),
+            name: Username("bob".to_owned()),
+            nickname: None,
+            score: Tagged(20),
+        },
+    ];
+
+    for user in &users {
+        user.insert(&conn)?;
+    }
+
+    // Stored exactly like the inner values.
+    let (id, name, kind): (i64, String, String) = conn.query_row(
+        "SELECT id, name, typeof(id) FROM users WHERE name = 'bob'",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+    )?;
+
+    assert_eq!((id, name.as_str(), kind.as_str()), (2, "bob", "integer"));
+
+    assert_eq!(User::select_all(&conn)?, users);
+
+    // Usable as query parameters.
+    let found = User::select_by(&conn, "id", UserId(1))?;
+
+    assert_eq!(found, vec![users[0].clone()]);
+
+    let name: Username = conn.query_row(
+        "SELECT name FROM users WHERE id = ?",
+        [UserId(2)],
+        |row| row.get(0)
+    )?;
+
+    assert_eq!(name, Username("bob".to_owned()));
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+//! Checks the diagnostics produced by the derive macros.
+//! 
+//! Each file in `tests/ui` is expected to fail to compile with exactly the output in the matching `.stderr` file.
+//! After an intentional change to a diagnostic, regenerate them with `TRYBUILD=overwrite cargo test --test ui`.
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+
+    cases.compile_fail("tests/ui/*.rs");
+}
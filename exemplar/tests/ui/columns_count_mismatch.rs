@@ -0,0 +1,11 @@
+use exemplar::Model;
+
+#[derive(Model)]
+#[table("legacy_people")]
+#[columns("PERSON_NAME")]
+pub struct Person {
+    pub name: String,
+    pub age: u16,
+}
+
+fn main() {}
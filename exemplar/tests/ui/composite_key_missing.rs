@@ -0,0 +1,12 @@
+use exemplar::Model;
+
+#[derive(Model)]
+#[table("memberships")]
+pub struct Membership {
+    #[primary_key]
+    pub user: i64,
+    #[primary_key]
+    pub team: i64,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use exemplar::Model;
+
+#[derive(Model)]
+#[table("people")]
+struct Person {
+    name: String,
+    age: u16,
+}
+
+fn main() {
+    // Without #[filter], no builder is generated.
+    let _: Option<PersonFilter> = None;
+}
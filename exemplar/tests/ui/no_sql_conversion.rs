@@ -0,0 +1,12 @@
+use exemplar::Model;
+
+struct Opaque;
+
+#[derive(Model)]
+#[table("things")]
+struct Thing {
+    name: String,
+    opaque: Opaque,
+}
+
+fn main() {}
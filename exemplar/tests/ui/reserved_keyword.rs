@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+
+use exemplar::Model;
+
+#[derive(Model)]
+#[table("members")]
+pub struct Member {
+    pub name: String,
+    pub group: String,
+}
+
+fn main() {}
@@ -1,39 +1,507 @@
 use super::*;
 
+use crate::util::Schema;
+use crate::util::TABLE_PLACEHOLDER;
+
+use syn::ext::IdentExt;
+
 pub fn from_row(derivee: &Derivee) -> QuoteStream {
-    let field_idents = derivee.field_idents();
-    let col_names    = derivee.col_names().map(|s| Literal::string(&s));
+    let inline = inline_hint();
 
-    let getters = derivee
-        .fields
-        .iter()
-        .zip(col_names)
+    let field_idents: Vec<_> = derivee.read_idents().collect();
+    let skipped_idents: Vec<_> = derivee.skipped_idents().collect();
+
+    let getters = row_getters(derivee, false);
+    let cached_getters = row_getters(derivee, true);
+
+    let indices = derivee
+        .read_ordinals()
+        .flat_map(|(field, idx)| {
+            if let Some(multi) = util::get_multi(field) {
+                return multi
+                    .columns
+                    .iter()
+                    .zip(idx..)
+                    .map(|(name, idx)| {
+                        let ordinal = ordinal(derivee, idx);
+                        quote! { ::exemplar::__statement_column_index(stmt, &[#name], #ordinal)? }
+                    })
+                    .collect()
+            }
+
+            let name = Literal::string(&util::get_col_name(field));
+            let aliases = util::get_col_aliases(field);
+            let ordinal = ordinal(derivee, idx);
+
+            vec![quote! { ::exemplar::__statement_column_index(stmt, &[#name, #(#aliases),*], #ordinal)? }]
+        });
+
+    quote! {
+        #inline
+        fn from_row(row: &::rusqlite::Row) -> ::rusqlite::Result<Self> 
+        where
+            Self: ::std::marker::Sized,
+        {
+            Ok(Self {
+                #(#field_idents : #getters,)*
+                #(#skipped_idents : ::std::default::Default::default(),)*
+            })
+        }
+
+        fn column_indices(stmt: &::rusqlite::Statement) -> ::rusqlite::Result<::exemplar::ColumnMap<Self>>
+        where
+            Self: ::std::marker::Sized,
+        {
+            Ok(::exemplar::ColumnMap::__new(::std::boxed::Box::new([#(#indices),*])))
+        }
+
+        #inline
+        fn from_row_cached(row: &::rusqlite::Row, map: &::exemplar::ColumnMap<Self>) -> ::rusqlite::Result<Self>
+        where
+            Self: ::std::marker::Sized,
+        {
+            let indices = map.indices();
+
+            Ok(Self {
+                #(#field_idents : #cached_getters,)*
+                #(#skipped_idents : ::std::default::Default::default(),)*
+            })
+        }
+    }
+}
+
+/// Generate the getter for each field, for use in `from_row` (or `from_row_cached`, in which case columns are looked up in its `indices`.)
+fn row_getters(derivee: &Derivee, cached: bool) -> Vec<QuoteStream> {
+    derivee
+        .read_ordinals()
         // Handle #[extr]/no #[extr]
-        .map(|(field, name)| {
-            let ty = &field.ty;
+        .map(|(field, idx)| {
+            if let Some(multi) = util::get_multi(field) {
+                let values = match cached {
+                    true => (idx..idx + multi.columns.len())
+                        .map(|idx| quote! { row.get_ref(indices[#idx])? })
+                        .collect(),
+                    false => multi_column_ref(derivee, &multi, idx),
+                };
 
-            if let Some(extr) = util::get_extr_path(field) {
-                quote! { #extr(&row.get_ref(#name)?)? }
+                return extract_multi(&multi, values)
+            }
+
+            let name = Literal::string(&util::get_col_name(field));
+
+            capture(field, |field| {
+                let ty  = &field.ty;
+                let col = match cached {
+                    true => quote! { indices[#idx] },
+                    false => column_ref(derivee, field, &name, idx),
+                };
+
+                if let Some(extr) = extract(derivee, field, &quote! { &row.get_ref(#col)? }, &name) {
+                    extr
+                }
+                else {
+                    row_value(ty, &col)
+                }
+            })
+        })
+        .collect()
+}
+
+/// Generate the index used to look up the field's column in a row.
+/// 
+/// The column is resolved at runtime by trying the column name and then any aliases - falling back to the field's ordinal position,
+/// if the model opted in with `#[positional_fallback]`.
+fn column_ref(derivee: &Derivee, field: &Field, name: &Literal, idx: usize) -> QuoteStream {
+    let aliases = util::get_col_aliases(field);
+    let ordinal = ordinal(derivee, idx);
+
+    quote! { ::exemplar::__column_index(row, &[#name, #(#aliases),*], #ordinal)? }
+}
+
+/// Generate the positional fallback for a column lookup - `Some(idx)` if the model opted in with `#[positional_fallback]`, and `None` otherwise.
+fn ordinal(derivee: &Derivee, idx: usize) -> QuoteStream {
+    match derivee.positional {
+        true => quote! { ::std::option::Option::Some(#idx) },
+        false => quote! { ::std::option::Option::None },
+    }
+}
+
+/// Generate the values of a `#[bind_multi]` field's columns in a row, starting from the ordinal of its first column.
+/// 
+/// Each column is resolved by name, falling back to its ordinal position - exactly like [`column_ref`].
+fn multi_column_ref(derivee: &Derivee, multi: &util::Multi, idx: usize) -> Vec<QuoteStream> {
+    multi
+        .columns
+        .iter()
+        .zip(idx..)
+        .map(|(name, idx)| {
+            let ordinal = ordinal(derivee, idx);
+            quote! { row.get_ref(::exemplar::__column_index(row, &[#name], #ordinal)?)? }
+        })
+        .collect()
+}
+
+/// Generate a call to a field's `#[extr_multi]` function, passing it an array of the provided column values.
+fn extract_multi(multi: &util::Multi, values: Vec<QuoteStream>) -> QuoteStream {
+    let extr = &multi.extr;
+
+    quote! { #extr(&[#(#values),*]).map_err(::exemplar::__extr_error)? }
+}
+
+/// Generate the getter for a field, capturing any error into the field if it's typed `Result<T, E>`.
+/// 
+/// In that case, the getter is generated for `T` and wrapped in a closure - so anything that would be propagated with `?`
+/// (a missing column, a type mismatch, an `#[extr]` error) is converted into `E` and stored in the field instead.
+fn capture(field: &Field, getter: impl FnOnce(&Field) -> QuoteStream) -> QuoteStream {
+    let Some(inner) = util::captured_field(field) else {
+        return getter(field)
+    };
+
+    let getter = getter(&inner);
+
+    quote! {
+        (|| -> ::rusqlite::Result<_> {
+            let value = #getter;
+            Ok(value)
+        })()
+        .map_err(::std::convert::Into::into)
+    }
+}
+
+/// Generate a call to the field's `#[extr]` or `#[extr_named]` function (or its type's `#[convert]` rule), if it has one.
+/// 
+/// Extractor errors are funneled through `__extr_error`, so any `E: Into<Box<dyn Error + Send + Sync>>` is accepted -
+/// unless the `#[extr]` attribute has a `default`, in which case they're replaced by calling it.
+/// If the function is lifted over an `Option` field (with `lift`), `NULL` becomes `None`, and anything else is extracted and wrapped in `Some`.
+fn extract(derivee: &Derivee, field: &Field, value: &QuoteStream, name: &Literal) -> Option<QuoteStream> {
+    if util::is_transparent(field) {
+        let ctor = util::get_transparent_ctor(field);
+
+        return Some(quote! { #ctor(::rusqlite::types::FromSql::column_result(*#value)?) })
+    }
+
+    let call = |value: &QuoteStream| {
+        if let Some(extr) = util::get_extr_named_path(field) {
+            Some(quote! { #extr(#value, #name).map_err(::exemplar::__extr_error)? })
+        }
+        else if let Some(default) = util::get_extr_default(field) {
+            let extr = derivee.extr_path(field)?;
+
+            Some(quote! { #extr(#value).unwrap_or_else(|_| #default()) })
+        }
+        else {
+            derivee
+                .extr_path(field)
+                .map(|extr| quote! { #extr(#value).map_err(::exemplar::__extr_error)? })
+        }
+    };
+
+    if !util::is_extr_lifted(field) {
+        return call(value)
+    }
+
+    let call = call(&quote! { value })?;
+
+    Some(quote! {
+        match #value {
+            &::rusqlite::types::ValueRef::Null => ::std::option::Option::None,
+            value => ::std::option::Option::Some(#call),
+        }
+    })
+}
+
+/// Generate a call to the field's `#[bind]` function (or its type's `#[convert]` rule), if it has one.
+/// 
+/// If the function is lifted over an `Option` field (with `lift`), `None` is bound as `NULL`, and `Some` values are passed through.
+fn bind(derivee: &Derivee, field: &Field, ident: &Ident) -> Option<QuoteStream> {
+    if let Some(inner) = util::captured_field(field) {
+        let bind = derivee.bind_path(&inner)?;
+        let value = captured_value(ident);
+
+        return Some(quote! { #bind(#value)? })
+    }
+
+    let bind = derivee.bind_path(field)?;
+
+    if !util::is_bind_lifted(field) {
+        return Some(quote! { #bind(&self.#ident)? })
+    }
+
+    // Going through Option's ToSql implementation (rather than matching) lets the function return any ToSql type.
+    Some(quote! {
+        self.#ident.as_ref().map(|value| #bind(value)).transpose()?
+    })
+}
+
+/// Generate the `Parameter` for a field without a `#[bind]` function.
+/// 
+/// Fields are borrowed and bound through their own `ToSql` implementation, or (if they have none) boxed through their `SqlConvert` implementation.
+fn field_param(field: &Field, ident: &Ident) -> QuoteStream {
+    // Captured fields are borrowed through the Ok variant.
+    let value = if util::captured_field(field).is_some() {
+        captured_value(ident)
+    }
+    // Borrow trait objects directly, rather than going through Box's ToSql implementation.
+    else if util::is_boxed_to_sql(&field.ty) {
+        return quote! { ::exemplar::Parameter::Borrowed(&*self.#ident) }
+    }
+    else {
+        quote! { &self.#ident }
+    };
+
+    // Transparent newtypes are bound through their inner value.
+    if util::is_transparent(field) {
+        return quote! { ::exemplar::Parameter::Borrowed(&(#value).0) }
+    }
+
+    // Spanned to the field's type, so that a type with no conversion is reported there.
+    let bind = quote_spanned! { field.ty.span() => (&::exemplar::__Bind(#value)).__bind()? };
+
+    quote! {{
+        use ::exemplar::{__BindToSql as _, __BindConvert as _};
+        #bind
+    }}
+}
+
+/// Generate the extraction of a field without an `#[extr]` function from a column of a row.
+/// 
+/// Like [`field_param`], this goes through the field type's `FromSql` implementation, or (if it has none) its `SqlConvert` implementation.
+fn row_value(ty: &Type, col: &QuoteStream) -> QuoteStream {
+    let extr = quote_spanned! { ty.span() => (&::exemplar::__Extr::<#ty>::NEW).__extr_row(row, #col)? };
+
+    quote! {{
+        use ::exemplar::{__ExtrFromSql as _, __ExtrConvert as _};
+        #extr
+    }}
+}
+
+/// Generate the extraction of a field without an `#[extr]` function from a `ValueRef`, exactly like [`row_value`].
+fn ref_value(ty: &Type, value: &QuoteStream) -> QuoteStream {
+    let extr = quote_spanned! { ty.span() => (&::exemplar::__Extr::<#ty>::NEW).__extr_value(#value)? };
+
+    quote! {{
+        use ::exemplar::{__ExtrFromSql as _, __ExtrConvert as _};
+        #extr
+    }}
+}
+
+/// Generate the names of the local variables holding a `#[bind_multi]` field's column values.
+fn multi_vars(ident: &Ident, multi: &util::Multi) -> Vec<Ident> {
+    (0..multi.columns.len())
+        .map(|idx| format_ident!("__{}_{}", ident, idx))
+        .collect()
+}
+
+/// Generate statements that split each `#[bind_multi]` field into its column values, binding them to local variables.
+fn split_multi(derivee: &Derivee) -> QuoteStream {
+    derivee
+        .field_idents()
+        .zip(&derivee.fields)
+        .filter_map(|(ident, field)| {
+            let multi = util::get_multi(field)?;
+            let bind  = &multi.bind;
+            let vars  = multi_vars(ident, &multi);
+
+            Some(quote! { let [#(#vars),*] = #bind(&self.#ident)?; })
+        })
+        .collect()
+}
+
+/// Generate a reference to the `Ok` value of a captured (`Result<T, E>`) field, erroring if it holds an `Err`.
+fn captured_value(ident: &Ident) -> QuoteStream {
+    quote! { ::exemplar::__captured(&self.#ident, stringify!(#ident))? }
+}
+
+pub fn read_into(derivee: &Derivee) -> QuoteStream {
+    let inline = inline_hint();
+
+    let setters = derivee
+        .read_idents()
+        .zip(derivee.read_ordinals())
+        .map(|(ident, (field, idx))| {
+            let ty   = &field.ty;
+            let name = Literal::string(&util::get_col_name(field));
+            let col  = column_ref(derivee, field, &name, idx);
+
+            if let Some(multi) = util::get_multi(field) {
+                let value = extract_multi(&multi, multi_column_ref(derivee, &multi, idx));
+
+                quote! { self.#ident = #value; }
+            }
+            else if util::captured_field(field).is_some() {
+                let value = capture(field, |field| {
+                    let ty = &field.ty;
+
+                    extract(derivee, field, &quote! { &row.get_ref(#col)? }, &name)
+                        .unwrap_or_else(|| row_value(ty, &col))
+                });
+
+                quote! { self.#ident = #value; }
+            }
+            else if let Some(extr) = extract(derivee, field, &quote! { &row.get_ref(#col)? }, &name) {
+                quote! { self.#ident = #extr; }
+            }
+            // Reuse the existing allocation for strings and byte vectors.
+            else if util::is_string(ty) {
+                quote! { ::exemplar::__read_string_into(row, #col, &mut self.#ident)?; }
+            }
+            else if util::is_byte_vec(ty) {
+                quote! { ::exemplar::__read_blob_into(row, #col, &mut self.#ident)?; }
             }
             else {
-                quote! { row.get::<_, #ty>(#name)? }
+                let value = row_value(ty, &col);
+
+                quote! { self.#ident = #value; }
             }
         });
 
     quote! {
-        #[inline]
-        fn from_row(row: &::rusqlite::Row) -> ::rusqlite::Result<Self> 
+        #inline
+        fn read_into(&mut self, row: &::rusqlite::Row) -> ::rusqlite::Result<()> {
+            #(#setters)*
+
+            Ok(())
+        }
+    }
+}
+
+pub fn selects(derivee: &Derivee) -> QuoteStream {
+    let inline = inline_hint();
+
+    let select_sql = resolved_sql(derivee, derivee.gen_select());
+
+    quote! {
+        #inline
+        fn select_all(conn: &::rusqlite::Connection) -> ::rusqlite::Result<::std::vec::Vec<Self>>
+        where
+            Self: ::std::marker::Sized,
+        {
+            let mut stmt = conn.prepare_cached(#select_sql)?;
+
+            let models = stmt
+                .query_and_then([], Self::from_row)?
+                .collect();
+
+            models
+        }
+
+        #inline
+        fn prepare_select_all(conn: &::rusqlite::Connection) -> ::rusqlite::Result<::rusqlite::Statement<'_>>
+        where
+            Self: ::std::marker::Sized,
+        {
+            conn.prepare(#select_sql)
+        }
+    }
+}
+
+pub fn from_value_map(derivee: &Derivee) -> QuoteStream {
+    let inline = inline_hint();
+
+    let field_idents = derivee.read_idents();
+
+    let getters = derivee
+        .read_fields()
+        .map(|field| {
+            if let Some(multi) = util::get_multi(field) {
+                let values = multi
+                    .columns
+                    .iter()
+                    .map(|name| quote! { ::exemplar::__map_value(map, &[#name])? })
+                    .collect();
+
+                return extract_multi(&multi, values)
+            }
+
+            let name = Literal::string(&util::get_col_name(field));
+
+            capture(field, |field| {
+                let ty = &field.ty;
+                let aliases = util::get_col_aliases(field);
+                let value = quote! { ::exemplar::__map_value(map, &[#name, #(#aliases),*])? };
+
+                if let Some(extr) = extract(derivee, field, &quote! { &#value }, &name) {
+                    extr
+                }
+                else {
+                    ref_value(ty, &value)
+                }
+            })
+        });
+
+    let skipped_idents = derivee.skipped_idents();
+
+    quote! {
+        #inline
+        fn from_value_map(map: &::std::collections::HashMap<::std::string::String, ::rusqlite::types::Value>) -> ::rusqlite::Result<Self>
         where
             Self: ::std::marker::Sized,
         {
             Ok(Self {
-                #(#field_idents : #getters),*
+                #(#field_idents : #getters,)*
+                #(#skipped_idents : ::std::default::Default::default(),)*
             })
         }
     }
 }
 
+pub fn try_from_values(derivee: &Derivee) -> QuoteStream {
+    let name         = &derivee.name;
+    let column_count = derivee.col_names().count() + derivee.computed.len();
+    let field_idents = derivee.read_idents();
+
+    let getters = derivee
+        .read_ordinals()
+        .map(|(field, idx)| {
+            if let Some(multi) = util::get_multi(field) {
+                let values = (idx..idx + multi.columns.len())
+                    .map(|idx| quote! { ::rusqlite::types::ValueRef::from(&values[#idx]) })
+                    .collect();
+
+                return extract_multi(&multi, values)
+            }
+
+            let name = Literal::string(&util::get_col_name(field));
+
+            capture(field, |field| {
+                let ty = &field.ty;
+                let value = quote! { ::rusqlite::types::ValueRef::from(&values[#idx]) };
+
+                if let Some(extr) = extract(derivee, field, &quote! { &#value }, &name) {
+                    extr
+                }
+                else {
+                    ref_value(ty, &value)
+                }
+            })
+        });
+
+    let skipped_idents = derivee.skipped_idents();
+
+    quote! {
+        #[automatically_derived]
+        impl ::std::convert::TryFrom<::std::vec::Vec<::rusqlite::types::Value>> for #name {
+            type Error = ::rusqlite::Error;
+
+            fn try_from(values: ::std::vec::Vec<::rusqlite::types::Value>) -> Result<Self, Self::Error> {
+                if values.len() != #column_count {
+                    return Err(
+                        ::rusqlite::Error::InvalidParameterCount(values.len(), #column_count)
+                    )
+                }
+
+                Ok(Self {
+                    #(#field_idents : #getters,)*
+                    #(#skipped_idents : ::std::default::Default::default(),)*
+                })
+            }
+        }
+    }
+}
+
 pub fn inserts(derivee: &Derivee) -> QuoteStream {
+    let inline = inline_hint();
+
     let col_names: Vec<_> = derivee
         .col_names()
         .map(|mut str| {
@@ -42,44 +510,102 @@ pub fn inserts(derivee: &Derivee) -> QuoteStream {
         })
         .collect();
     
+    let split = split_multi(derivee);
+
     let field_idents: Vec<_> = derivee
         .field_idents()
         .zip(&derivee.fields)
         // Handle #[bind]/no #[bind]
-        .map(|(ident, field)| {
-            if let Some(bind) = util::get_bind_path(field) {
-                quote! { &#bind(&self.#ident)? }
+        .flat_map(|(ident, field)| {
+            if let Some(multi) = util::get_multi(field) {
+                return multi_vars(ident, &multi)
+                    .into_iter()
+                    .map(|var| quote! { &#var })
+                    .collect()
             }
-            else {
-                quote! { &self.#ident }
+
+            let value = if let Some(bind) = bind(derivee, field, ident) {
+                quote! { &#bind }
             }
+            else {
+                field_param(field, ident)
+            };
+
+            vec![value]
         })
         .collect();
 
-    let abort_sql    = derivee.gen_query(None);
-    let fail_sql     = derivee.gen_query(Some("FAIL"));
-    let ignore_sql   = derivee.gen_query(Some("IGNORE"));
-    let replace_sql  = derivee.gen_query(Some("REPLACE"));
-    let rollback_sql = derivee.gen_query(Some("ROLLBACK"));
+    let abort_sql    = resolved_sql(derivee, derivee.gen_query(None));
+    let fail_sql     = resolved_sql(derivee, derivee.gen_query(Some("FAIL")));
+    let ignore_sql   = resolved_sql(derivee, derivee.gen_query(Some("IGNORE")));
+    let replace_sql  = resolved_sql(derivee, derivee.gen_query(Some("REPLACE")));
+    let rollback_sql = resolved_sql(derivee, derivee.gen_query(Some("ROLLBACK")));
+
+    let default_strategy = format_ident!("{}", derivee.on_conflict.variant);
+    let default_sql      = resolved_sql(derivee, derivee.gen_query(derivee.on_conflict.clause));
+
+    #[cfg(feature = "returning")]
+    let if_absent = {
+        let if_absent_sql = resolved_sql(derivee, derivee.gen_query_if_absent());
+
+        quote! {
+            #inline
+            fn insert_if_absent(&self, conn: &::rusqlite::Connection) -> ::rusqlite::Result<bool> {
+                let mut stmt = conn.prepare_cached(#if_absent_sql)?;
+
+                #split
+
+                let mut rows = stmt.query(rusqlite::named_params! {
+                    #(#col_names: #field_idents),*
+                })?;
+
+                Ok(rows.next()?.is_some())
+            }
+        }
+    };
+
+    #[cfg(not(feature = "returning"))]
+    let if_absent = QuoteStream::new();
+
+    let present = derivee
+        .field_idents()
+        .zip(&derivee.fields)
+        .flat_map(|(ident, field)| {
+            let columns = util::get_field_columns(field).len();
+
+            if util::is_option(&field.ty) && columns == 1 {
+                vec![quote! { self.#ident.is_some() }]
+            }
+            else {
+                vec![quote! { true }; columns]
+            }
+        });
 
     quote! {
-        #[inline]
+        #inline
         fn insert(&self, conn: &::rusqlite::Connection) -> ::rusqlite::Result<()> {
-            self.insert_or(conn, ::exemplar::OnConflict::Abort)
+            self.insert_or(conn, ::exemplar::OnConflict::#default_strategy)
         }
 
-        #[inline]
+        #inline
         fn insert_or(&self, conn: &::rusqlite::Connection, strategy: ::exemplar::OnConflict) -> ::rusqlite::Result<()> {
+            self.insert_returning_changes(conn, strategy)?;
+
+            Ok(())
+        }
+
+        #inline
+        fn insert_returning_changes(&self, conn: &::rusqlite::Connection, strategy: ::exemplar::OnConflict) -> ::rusqlite::Result<usize> {
             use ::exemplar::OnConflict::*;
+
+            #split
             
-            let exec = |sql: &str| -> ::rusqlite::Result<()> {
+            let exec = |sql: &str| -> ::rusqlite::Result<usize> {
                 let mut stmt = conn.prepare_cached(sql)?;
 
                 stmt.execute(rusqlite::named_params! {
                     #(#col_names: #field_idents),*
-                })?;
-
-                Ok(())
+                })
             };
             
             match strategy {
@@ -91,18 +617,45 @@ pub fn inserts(derivee: &Derivee) -> QuoteStream {
             }
         }
 
-        #[inline]
+        #inline
+        fn insert_uncached(&self, conn: &::rusqlite::Connection) -> ::rusqlite::Result<()> {
+            #split
+
+            conn.execute(#default_sql, rusqlite::named_params! {
+                #(#col_names: #field_idents),*
+            })?;
+
+            Ok(())
+        }
+
+        #inline
         fn insert_with(&self, stmt: &mut::rusqlite::Statement) -> ::rusqlite::Result<()> {
+            #split
+
             stmt.execute(rusqlite::named_params! {
                 #(#col_names: #field_idents),*
             })?;
 
             Ok(())
         }
+
+        #inline
+        fn insert_sparse(&self, conn: &::rusqlite::Connection) -> ::rusqlite::Result<()> {
+            ::exemplar::__insert_sparse(
+                conn,
+                self,
+                ::exemplar::OnConflict::#default_strategy,
+                &[#(#present),*]
+            )
+        }
+
+        #if_absent
     }
 }
 
 pub fn to_params(derivee: &Derivee) -> QuoteStream {
+    let inline = inline_hint();
+
     let col_names = derivee
         .col_names()
         .map(|mut str| {
@@ -110,26 +663,40 @@ pub fn to_params(derivee: &Derivee) -> QuoteStream {
             Literal::string(&str)
         });
 
+    let split = split_multi(derivee);
+
     let field_idents = derivee
         .field_idents()
         .zip(&derivee.fields)
-        .map(|(ident, field)| {
-            if let Some(bind) = util::get_bind_path(field) {
+        .flat_map(|(ident, field)| {
+            if let Some(multi) = util::get_multi(field) {
+                // Fields with #[bind_multi] have already been split into their column values, which we box.
+                return multi_vars(ident, &multi)
+                    .into_iter()
+                    .map(|var| quote! { Boxed(Box::new(#var) as Box<dyn ::rusqlite::ToSql>) })
+                    .collect()
+            }
+
+            let value = if let Some(bind) = bind(derivee, field, ident) {
                 // If the field has a #[bind] attribute, then we execute it now and box the result.
-                quote! { Boxed(Box::new(#bind(&self.#ident)?) as Box<dyn ::rusqlite::ToSql>) }
+                quote! { Boxed(Box::new(#bind) as Box<dyn ::rusqlite::ToSql + '_>) }
             }
             else {
-                // Otherwise, we're good to just borrow directly from self and cast to a dyn ToSql.
-                quote! { Borrowed(&self.#ident as &dyn ::rusqlite::ToSql) }
-            }
+                // Otherwise, we're (usually) good to just borrow from self.
+                field_param(field, ident)
+            };
+
+            vec![value]
         });
     
     quote! {
-        #[inline]
+        #inline
         fn to_params(&self) -> ::rusqlite::Result<::exemplar::Parameters> {
             use ::std::boxed::Box;
             use ::exemplar::Parameter::*;
 
+            #split
+
             let params = [
                 #((#col_names, #field_idents)),*
             ];
@@ -142,29 +709,98 @@ pub fn to_params(derivee: &Derivee) -> QuoteStream {
 }
 
 pub fn metadata(derivee: &Derivee) -> QuoteStream {
+    let inline = inline_hint();
+
     let model = &derivee.name;
     let table = &derivee.table;
     
+    // Fields spanning several columns (#[bind_multi]) are repeated, so they line up with the columns.
     let field_names = derivee
         .fields
         .iter()
-        .map(|field| {
-            field
+        .flat_map(|field| {
+            let name = field
                 .ident
                 .as_ref()
                 .expect("All fields should have an identifier.")
-                .to_string()
+                .to_string();
+
+            vec![name; util::get_field_columns(field).len()]
         });
     
     let columns = derivee.col_names();
+
+    let bound = derivee
+        .fields
+        .iter()
+        .flat_map(|field| {
+            let bound = if util::get_multi(field).is_some() {
+                true
+            }
+            else {
+                let inner = util::captured_field(field);
+                derivee.bind_path(inner.as_ref().unwrap_or(field)).is_some()
+            };
+
+            vec![bound; util::get_field_columns(field).len()]
+        });
+
+    let primary_key = derivee
+        .primary_key
+        .iter()
+        .copied()
+        .map(util::get_col_name);
+
+    let checks = derivee
+        .fields
+        .iter()
+        .flat_map(|field| {
+            let column = util::get_col_name(field);
+
+            util::get_sql_checks(field)
+                .into_iter()
+                .map(move |expr| quote! { (#column, #expr) })
+        });
+
+    let computed = derivee
+        .computed
+        .iter()
+        .map(|field| {
+            let column = util::get_col_name(field);
+            let expr = util::get_select_expr(field).expect("Field should have a #[select_expr] attribute.");
+
+            quote! { (#column, #expr) }
+        });
+
+    let insert_sql = [
+        derivee.gen_query(None),
+        derivee.gen_query(Some("FAIL")),
+        derivee.gen_query(Some("IGNORE")),
+        derivee.gen_query(Some("REPLACE")),
+        derivee.gen_query(Some("ROLLBACK")),
+    ]
+    .map(|sql| resolved_sql(derivee, sql));
+
+    let resolve_table = match &derivee.resolve_table {
+        Some(resolver) => quote! { #resolver() },
+        None => quote! { #table },
+    };
     
     quote! {
-        #[inline]
+        #inline
+        fn resolve_table() -> &'static str
+        where
+            Self: ::std::marker::Sized
+        {
+            #resolve_table
+        }
+
+        #inline
         fn metadata_dyn(&self) -> ::exemplar::ModelMeta {
             Self::metadata()
         }
         
-        #[inline]
+        #inline
         fn metadata() -> ::exemplar::ModelMeta
         where
             Self: ::std::marker::Sized
@@ -179,26 +815,188 @@ pub fn metadata(derivee: &Derivee) -> QuoteStream {
                 #(#columns),*
             ];
 
-            ModelMeta {
-                model: stringify!(#model),
-                table: #table,
-                fields: FIELDS,
-                columns: COLUMNS,
+            static BOUND: &'static [bool] = &[
+                #(#bound),*
+            ];
+
+            static PRIMARY_KEY: &'static [&'static str] = &[
+                #(#primary_key),*
+            ];
+
+            static CHECKS: &'static [(&'static str, &'static str)] = &[
+                #(#checks),*
+            ];
+
+            static COMPUTED: &'static [(&'static str, &'static str)] = &[
+                #(#computed),*
+            ];
+
+            ModelMeta::__new(
+                stringify!(#model),
+                Self::resolve_table(),
+                FIELDS,
+                COLUMNS,
+                PRIMARY_KEY,
+                BOUND,
+                CHECKS,
+                COMPUTED,
+                [#(#insert_sql),*],
+            )
+        }
+    }
+}
+
+pub fn consts(derivee: &Derivee) -> QuoteStream {
+    let name = &derivee.name;
+    let column_count = derivee.col_names().count();
+
+    quote! {
+        #[automatically_derived]
+        impl #name {
+            /// The number of columns this model maps to.
+            pub const COLUMN_COUNT: usize = #column_count;
+        }
+    }
+}
+
+/// Comparison operators supported by generated filters, in the form `(suffix, operator, description)`.
+/// 
+/// Equality uses `IS` rather than `=`, so that filtering on `NULL` behaves as expected.
+const FILTER_OPS: &[(&str, &str, &str)] = &[
+    ("eq", "IS", "is equal to"),
+    ("ne", "IS NOT", "is not equal to"),
+    ("lt", "<", "is less than"),
+    ("le", "<=", "is less than or equal to"),
+    ("gt", ">", "is greater than"),
+    ("ge", ">=", "is greater than or equal to"),
+];
+
+pub fn filter(derivee: &Derivee) -> QuoteStream {
+    let name = &derivee.name;
+    let vis = &derivee.vis;
+    let filter = format_ident!("{}Filter", name);
+
+    let struct_doc = format!("A typed filter over [`{name}`] rows, created by [`{name}::filter`].");
+
+    let methods = derivee
+        .field_idents()
+        .zip(&derivee.fields)
+        // Fields spanning several columns can't be compared to a single value.
+        .filter(|(_, field)| util::get_multi(field).is_none())
+        .flat_map(|(ident, field)| {
+            let column = util::get_col_name(field);
+            let (param, value) = filter_value(derivee, field);
+
+            FILTER_OPS.iter().map(move |(suffix, op, desc)| {
+                let method = format_ident!("{}_{}", ident.unraw(), suffix, span = ident.span());
+                let doc = format!("Only match rows where `{column}` {desc} the provided value.");
+
+                quote! {
+                    #[doc = #doc]
+                    pub fn #method(mut self, value: #param) -> Self {
+                        self.0.push(#column, #op, #value);
+                        self
+                    }
+                }
+            })
+        });
+
+    quote! {
+        #[doc = #struct_doc]
+        #[must_use = "filters do nothing unless fetched"]
+        #[allow(dead_code)]
+        #vis struct #filter(::exemplar::__Filter<#name>);
+
+        #[automatically_derived]
+        #[allow(dead_code)]
+        impl #name {
+            /// Start building a filter over this model's table, matching every row.
+            /// 
+            /// Predicates added with the filter's methods are joined with `AND`.
+            pub fn filter() -> #filter {
+                #filter(::std::default::Default::default())
+            }
+        }
+
+        #[automatically_derived]
+        #[allow(dead_code, clippy::ptr_arg)]
+        impl #filter {
+            #(#methods)*
+
+            /// Generate the `SELECT` statement this filter executes, with a `?` placeholder for each value.
+            #[must_use]
+            pub fn sql(&self) -> ::std::string::String {
+                self.0.sql()
+            }
+
+            /// Execute the filter against the provided connection, returning every matching model.
+            /// 
+            /// If any value failed to bind, the error is returned here.
+            /// 
+            /// # Performance
+            /// This method uses [`prepare_cached`](::rusqlite::Connection::prepare_cached) to create the query SQL statement.
+            pub fn fetch(self, conn: &::rusqlite::Connection) -> ::rusqlite::Result<::std::vec::Vec<#name>> {
+                self.0.fetch(conn)
             }
         }
     }
 }
 
+/// Generate the parameter type and bound value of a field's filter methods.
+/// 
+/// Fields with a `#[bind]` function (or `#[convert]` rule) take a reference to the field's type, and are bound exactly as
+/// they would be when inserting. Other fields take any `ToSql` value.
+fn filter_value(derivee: &Derivee, field: &Field) -> (QuoteStream, QuoteStream) {
+    let (ty, bind) = match util::captured_field(field) {
+        Some(inner) => (inner.ty.clone(), derivee.bind_path(&inner)),
+        None => (field.ty.clone(), derivee.bind_path(field)),
+    };
+
+    let Some(bind) = bind else {
+        return (
+            quote! { impl ::rusqlite::ToSql },
+            quote! { ::std::result::Result::Ok(value) }
+        )
+    };
+
+    // Captured fields are bound as their inner type, so only the field type itself can lift an Option.
+    if util::captured_field(field).is_some() || !util::is_bind_lifted(field) {
+        return (quote! { &#ty }, quote! { #bind(value) })
+    }
+
+    (quote! { &#ty }, quote! { value.as_ref().map(|value| #bind(value)).transpose() })
+}
+
 pub fn check_test(derivee: &Derivee) -> QuoteStream {
-    let Some(path) = &derivee.schema else {
+    let Some(check) = &derivee.check else {
         return QuoteStream::new()
     };
 
+    let schema = match &check.schema {
+        Schema::Path(path) => quote! { [include_str!(#path)] },
+        Schema::Inline(sql) => quote! { [#sql] },
+        // Listed at test time (rather than during expansion), so newly added migrations are always applied.
+        Schema::Migrations(dir) => quote! {
+            ::exemplar::__read_migrations(file!(), env!("CARGO_MANIFEST_DIR"), #dir)
+        },
+    };
+
     let func = derivee.name.to_string().to_lowercase();
     let func = format_ident!("{}_exemplar_check", func);
 
     let table = &derivee.table;
     let fields = derivee.col_names();
+
+    let without_rowid = check.without_rowid.then(|| quote! {
+        ::exemplar::__check_without_rowid(&conn, #table);
+    });
+
+    let constraints = derivee
+        .fields
+        .iter()
+        .flat_map(|field| util::get_sql_checks(field));
+
+    let indexes = &check.indexes;
     
     quote! {
         #[cfg(test)]
@@ -208,13 +1006,13 @@ pub fn check_test(derivee: &Derivee) -> QuoteStream {
             use ::std::collections::HashSet;
             use ::rusqlite::Connection;
 
-            let schema = include_str!(#path);
-
             let conn = Connection::open_in_memory()
                 .expect("In-memory DB connection should open successfully.");
 
-            conn.execute_batch(schema)
-                .expect("Failed to apply provided schema to check DB.");
+            for sql in #schema {
+                conn.execute_batch(sql.as_ref())
+                    .expect("Failed to apply provided schema to check DB.");
+            }
 
             let mut columns = HashSet::new();
 
@@ -236,6 +1034,177 @@ pub fn check_test(derivee: &Derivee) -> QuoteStream {
             for column in columns.iter().map(String::as_str) {
                 assert!(fields.contains(&column), "A column in the schema ({column}) has no corresponding field in the model.");
             }
+
+            #without_rowid
+
+            #(::exemplar::__check_constraint(&conn, #table, #constraints);)*
+
+            #(::exemplar::__check_index(&conn, #table, #indexes);)*
+        }
+    }
+}
+
+pub fn primary_key(derivee: &Derivee) -> QuoteStream {
+    if derivee.primary_key.is_empty() {
+        return QuoteStream::new()
+    }
+
+    let inline = inline_hint();
+    let name = &derivee.name;
+
+    let (col_names, values): (Vec<_>, Vec<_>) = derivee
+        .primary_key
+        .iter()
+        .map(|field| {
+            let ident = field
+                .ident
+                .as_ref()
+                .expect("All fields should have an identifier.");
+
+            let col_name = Literal::string(&format!(":{}", util::get_col_name(field)));
+
+            // Key fields are bound exactly like they are in to_params.
+            let value = match bind(derivee, field, ident) {
+                Some(bind) => quote! { Boxed(Box::new(#bind) as Box<dyn ::rusqlite::ToSql + '_>) },
+                None => field_param(field, ident),
+            };
+
+            (col_name, value)
+        })
+        .unzip();
+
+    quote! {
+        #[automatically_derived]
+        impl ::exemplar::PrimaryKey for #name {
+            #inline
+            fn to_key_params(&self) -> ::rusqlite::Result<::exemplar::Parameters> {
+                use ::std::boxed::Box;
+                use ::exemplar::Parameter::*;
+
+                let params = [
+                    #((#col_names, #values)),*
+                ];
+
+                Ok(
+                    Box::new(params)
+                )
+            }
+        }
+    }
+}
+
+pub fn hash_by_pk(derivee: &Derivee) -> QuoteStream {
+    let name = &derivee.name;
+
+    let keys: Vec<_> = derivee
+        .primary_key
+        .iter()
+        .map(|field| &field.ident)
+        .collect();
+
+    quote! {
+        #[automatically_derived]
+        impl ::std::cmp::PartialEq for #name {
+            fn eq(&self, other: &Self) -> bool {
+                (#(&self.#keys,)*) == (#(&other.#keys,)*)
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::cmp::Eq for #name {}
+
+        #[automatically_derived]
+        impl ::std::hash::Hash for #name {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                #(::std::hash::Hash::hash(&self.#keys, state);)*
+            }
+        }
+    }
+}
+
+pub fn order_by_pk(derivee: &Derivee) -> QuoteStream {
+    let name = &derivee.name;
+
+    let keys: Vec<_> = derivee
+        .primary_key
+        .iter()
+        .map(|field| &field.ident)
+        .collect();
+
+    quote! {
+        #[automatically_derived]
+        impl ::std::cmp::PartialOrd for #name {
+            fn partial_cmp(&self, other: &Self) -> ::std::option::Option<::std::cmp::Ordering> {
+                ::std::option::Option::Some(::std::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::cmp::Ord for #name {
+            fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+                ::std::cmp::Ord::cmp(&(#(&self.#keys,)*), &(#(&other.#keys,)*))
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+pub fn reserved_warnings(derivee: &Derivee) -> QuoteStream {
+    // There's no stable way for a proc macro to emit a warning (proc_macro_error's emit_warning!
+    // is a no-op outside nightly), so we fake one by referencing a deprecated item spanned to the
+    // offending field. The exact diagnostic is pinned by tests/ui/reserved_keyword.rs.
+    derivee
+        .fields
+        .iter()
+        .flat_map(|field| {
+            util::get_field_columns(field)
+                .into_iter()
+                .map(move |column| (*field, column))
+        })
+        .filter(|(_, column)| util::is_reserved(column))
+        .map(|(field, column)| {
+            let span = util::get_col_span(field);
+
+            let note = format!(
+                "the column `{column}` is a reserved SQL keyword and will produce invalid SQL when unquoted; consider renaming it with #[column(\"...\")]"
+            );
+
+            quote_spanned! { span =>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    #[allow(non_upper_case_globals)]
+                    const reserved_sql_keyword: () = ();
+
+                    reserved_sql_keyword
+                };
+            }
+        })
+        .collect()
+}
+
+/// Generate an expression for a piece of generated SQL.
+/// 
+/// Usually, this is just the literal. If the table is resolved at runtime with `#[resolve_table]`, the SQL contains the
+/// [`TABLE_PLACEHOLDER`] instead; it is filled in with the resolved name on first use, and the result cached for the rest of the program.
+fn resolved_sql(derivee: &Derivee, sql: Literal) -> QuoteStream {
+    if derivee.resolve_table.is_none() {
+        return quote! { #sql }
+    }
+
+    quote! {{
+        static SQL: ::std::sync::OnceLock<::std::string::String> = ::std::sync::OnceLock::new();
+
+        SQL
+            .get_or_init(|| #sql.replace(#TABLE_PLACEHOLDER, <Self as ::exemplar::Model>::resolve_table()))
+            .as_str()
+    }}
+}
+
+/// The inlining hint placed on generated methods (including those of `SqlTransparent`) - `#[inline]`, unless the `no-inline` feature is enabled.
+pub(crate) fn inline_hint() -> QuoteStream {
+    if cfg!(feature = "no-inline") {
+        QuoteStream::new()
+    }
+    else {
+        quote! { #[inline] }
+    }
+}
@@ -6,6 +6,7 @@ use proc_macro_error::*;
 
 use proc_macro2::Ident;
 use proc_macro2::Literal;
+use proc_macro2::Span;
 use proc_macro2::TokenStream as QuoteStream;
 
 use quote::*;
@@ -18,10 +19,13 @@ use crate::util::Derivee;
 #[proc_macro_error]
 #[proc_macro_derive(
     Model,
-    attributes(table, check, bind, extr, column)
+    attributes(table, check, on_conflict, convert, composite_key, bind, extr, extr_named, bind_multi, extr_multi, as_text, column, skip, primary_key, transparent, hash_by_pk, resolve_table, sql_check, select_expr, column_prefix, columns, order_by_pk, positional_fallback, filter)
 )]
 pub fn derive_model(input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as DeriveInput);
+    let mut ast = parse_macro_input!(input as DeriveInput);
+
+    util::apply_columns(&mut ast);
+    util::apply_column_prefix(&mut ast);
 
     let name = &ast.ident;
     
@@ -47,38 +51,87 @@ pub fn derive_model(input: TokenStream) -> TokenStream {
         )
     };
 
-    let fields: Vec<_> = fields
+    if fields.named.is_empty() {
+        abort_call_site!(
+            "Model can only be derived for structs with named fields.";
+            note = "Tuple and unit structs are not supported.";
+        )
+    }
+
+    let (skipped, fields): (Vec<_>, Vec<_>) = fields
         .named
         .iter()
-        .collect();
+        .partition(|field| util::is_skipped(field));
 
     if fields.is_empty() {
         abort_call_site!(
-            "Model can only be derived for structs with named fields.";
-            note = "Tuple and unit structs are not supported.";
+            "Model can only be derived for structs with at least one non-skipped field.";
+            note = "Every field in this struct is marked with #[skip].";
+        )
+    }
+
+    let (computed, fields): (Vec<_>, Vec<_>) = fields
+        .into_iter()
+        .partition(|field| util::get_select_expr(field).is_some());
+
+    if fields.is_empty() {
+        abort_call_site!(
+            "Model can only be derived for structs with at least one stored field.";
+            note = "Every non-skipped field in this struct is marked with #[select_expr].";
         )
     }
 
     let table = util::get_table_name(&ast);
-    let schema = util::get_check_path(&ast);
+    let check = util::get_check(&ast);
+    let on_conflict = util::get_on_conflict(&ast);
+    let converts = util::get_converts(&ast);
+    let primary_key = util::get_primary_key(&ast, &fields, &skipped);
+    let hash_by_pk = util::is_hash_by_pk(&ast, &primary_key);
+    let order_by_pk = util::is_order_by_pk(&ast, hash_by_pk);
+    let resolve_table = util::get_resolve_table(&ast);
+    let positional = util::is_positional_fallback(&ast);
+    let filter = util::is_filter(&ast);
 
     let derivee = Derivee {
         name: name.to_owned(),
+        vis: ast.vis.clone(),
         table,
         fields,
-        schema
+        skipped,
+        computed,
+        check,
+        on_conflict,
+        converts,
+        primary_key,
+        resolve_table,
+        positional
     };
 
+    util::check_boxed_to_sql(&derivee);
+
     let from_row            = codegen::from_row(&derivee);
+    let read_into           = codegen::read_into(&derivee);
+    let from_value_map      = codegen::from_value_map(&derivee);
+    let selects             = codegen::selects(&derivee);
     let inserts             = codegen::inserts(&derivee);
     let to_params           = codegen::to_params(&derivee);
     let metadata            = codegen::metadata(&derivee);
     let check_test          = codegen::check_test(&derivee);
+    let consts              = codegen::consts(&derivee);
+    let filter              = filter.then(|| codegen::filter(&derivee));
+    let try_from_values     = codegen::try_from_values(&derivee);
+    let reserved_warnings   = codegen::reserved_warnings(&derivee);
+    let hash_by_pk          = hash_by_pk.then(|| codegen::hash_by_pk(&derivee));
+    let order_by_pk         = order_by_pk.then(|| codegen::order_by_pk(&derivee));
+    let primary_key         = codegen::primary_key(&derivee);
     
     quote! {
         #[automatically_derived]
         impl ::exemplar::Model for #name {
             #from_row
+            #read_into
+            #from_value_map
+            #selects
             #inserts
             #to_params
             #metadata
@@ -93,8 +146,71 @@ pub fn derive_model(input: TokenStream) -> TokenStream {
             }
         }
 
+        #try_from_values
+
+        #primary_key
+
+        #consts
+
+        #filter
+
         #check_test
+
+        #reserved_warnings
+
+        #hash_by_pk
+
+        #order_by_pk
     }
     .into()
 }
 
+#[proc_macro_error]
+#[proc_macro_derive(SqlTransparent)]
+pub fn derive_sql_transparent(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    sql_transparent(&ast).into()
+}
+
+fn sql_transparent(ast: &DeriveInput) -> QuoteStream {
+    let name = &ast.ident;
+
+    let inner = match &ast.data {
+        Data::Struct(DataStruct { fields: Fields::Unnamed(fields), .. }) if fields.unnamed.len() == 1 => {
+            &fields.unnamed[0].ty
+        },
+        _ => abort_call_site!(
+            "SqlTransparent can only be derived for tuple structs with exactly one field.";
+            hint = "Declare the newtype like this: struct UserId(i64);"
+        )
+    };
+
+    let mut generics = ast.generics.clone();
+
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote! { #inner: ::rusqlite::ToSql + ::rusqlite::types::FromSql });
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let inline = codegen::inline_hint();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::rusqlite::ToSql for #name #ty_generics #where_clause {
+            #inline
+            fn to_sql(&self) -> ::rusqlite::Result<::rusqlite::types::ToSqlOutput<'_>> {
+                ::rusqlite::ToSql::to_sql(&self.0)
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::rusqlite::types::FromSql for #name #ty_generics #where_clause {
+            #inline
+            fn column_result(value: ::rusqlite::types::ValueRef<'_>) -> ::rusqlite::types::FromSqlResult<Self> {
+                ::rusqlite::types::FromSql::column_result(value).map(Self)
+            }
+        }
+    }
+}
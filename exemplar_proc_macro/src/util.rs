@@ -1,13 +1,117 @@
 use super::*;
 
+use syn::ext::IdentExt;
+
+/// SQL keywords that can't be used as unquoted column names in the statements Exemplar generates.
+/// 
+/// This is a subset of the [full SQLite keyword list](https://www.sqlite.org/lang_keywords.html) - SQLite's parser
+/// falls back to treating most keywords as identifiers where unambiguous, so only the ones that actually break 
+/// (or, in the case of the `current_*` keywords, silently change meaning) are included.
+const RESERVED: &[&str] = &[
+    "add", "all", "alter", "and", "as", "autoincrement", "between", "case", "cast", "check",
+    "collate", "commit", "constraint", "create", "current_date", "current_time", "current_timestamp",
+    "default", "deferrable", "delete", "distinct", "drop", "else", "escape", "except", "exists",
+    "foreign", "from", "group", "having", "in", "index", "insert", "intersect", "into", "is",
+    "isnull", "join", "limit", "not", "nothing", "notnull", "null", "on", "or", "order", "primary",
+    "raise", "references", "returning", "select", "set", "table", "then", "to", "transaction",
+    "union", "unique", "update", "using", "values", "when", "where",
+];
+
+/// Stands in for the table name in generated SQL when it's resolved at runtime with `#[resolve_table]`.
+/// 
+/// NUL never appears in valid SQL text, so the placeholder can't collide with anything else in the statement.
+pub const TABLE_PLACEHOLDER: &str = "\0table\0";
+
 pub struct Derivee<'a> {
     pub name: Ident,
+    pub vis: Visibility,
     pub table: String,
     pub fields: Vec<&'a Field>,
-    pub schema: Option<String>,
+    pub skipped: Vec<&'a Field>,
+    pub computed: Vec<&'a Field>,
+    pub check: Option<Check>,
+    pub on_conflict: Strategy,
+    pub converts: Vec<Convert>,
+    pub primary_key: Vec<&'a Field>,
+    pub resolve_table: Option<Path>,
+    pub positional: bool,
+}
+
+#[derive(Clone, Copy)]
+pub struct Strategy {
+    pub variant: &'static str,
+    pub clause: Option<&'static str>,
+}
+
+/// Conflict resolution strategies, in the form `(name, variant, clause)`.
+const STRATEGIES: &[(&str, &str, Option<&str>)] = &[
+    ("abort", "Abort", None),
+    ("fail", "Fail", Some("FAIL")),
+    ("ignore", "Ignore", Some("IGNORE")),
+    ("replace", "Replace", Some("REPLACE")),
+    ("rollback", "Rollback", Some("ROLLBACK")),
+];
+
+impl Default for Strategy {
+    fn default() -> Self {
+        Self {
+            variant: "Abort",
+            clause: None
+        }
+    }
+}
+
+pub struct Check {
+    pub schema: Schema,
+    pub without_rowid: bool,
+    pub indexes: Vec<String>,
+}
+
+pub enum Schema {
+    Path(String),
+    Inline(String),
+    /// A directory of `.sql` migrations, relative to the current file. It's read when the generated test runs.
+    Migrations(String),
+}
+
+/// A field-level `#[bind_multi(["col", ...], bind)]`/`#[extr_multi(extr)]` pair, which maps one field to several columns.
+pub struct Multi {
+    pub columns: Vec<String>,
+    pub bind: Path,
+    pub extr: ExprPath,
+}
+
+/// A struct-level `#[convert(Type => bind, extr)]` rule.
+pub struct Convert {
+    pub ty: Type,
+    pub bind: Path,
+    pub extr: ExprPath,
 }
 
 impl<'a> Derivee<'a> {
+    /// Resolve the bind function for a field - its own `#[bind]` (or `#[as_text]`), then any matching `#[convert]` rule.
+    pub fn bind_path(&self, field: &Field) -> Option<Path> {
+        get_bind_path(field).or_else(|| {
+            self.convert_for(field).map(|rule| rule.bind.clone())
+        })
+    }
+
+    /// Resolve the extraction function for a field - its own `#[extr]` (or `#[as_text]`), then any matching `#[convert]` rule.
+    pub fn extr_path(&self, field: &Field) -> Option<ExprPath> {
+        get_extr_path(field).or_else(|| {
+            self.convert_for(field).map(|rule| rule.extr.clone())
+        })
+    }
+
+    fn convert_for(&self, field: &Field) -> Option<&Convert> {
+        let ty = field.ty.to_token_stream().to_string();
+
+        self
+            .converts
+            .iter()
+            .find(|rule| rule.ty.to_token_stream().to_string() == ty)
+    }
+
     pub fn field_idents(&self) -> impl Iterator<Item = &Ident> {
         self
             .fields
@@ -20,22 +124,119 @@ impl<'a> Derivee<'a> {
             })
     }
 
+    /// The fields populated when reading a row - every stored field, followed by any `#[select_expr]` fields.
+    pub fn read_fields(&self) -> impl Iterator<Item = &'a Field> + '_ {
+        self
+            .fields
+            .iter()
+            .chain(&self.computed)
+            .copied()
+    }
+
+    pub fn read_idents(&self) -> impl Iterator<Item = &Ident> {
+        self
+            .read_fields()
+            .map(|field| {
+                field
+                    .ident
+                    .as_ref()
+                    .expect("All fields should have an indentifier.")
+            })
+    }
+
+    pub fn skipped_idents(&self) -> impl Iterator<Item = &Ident> {
+        self
+            .skipped
+            .iter()
+            .map(|field| {
+                field
+                    .ident
+                    .as_ref()
+                    .expect("All fields should have an indentifier.")
+            })
+    }
+
+    /// The names of every column the model maps to, in order - fields with `#[bind_multi]` contribute several.
     pub fn col_names(&self) -> impl Iterator<Item = String> + '_ {
         self
             .fields
             .iter()
             .copied()
-            .map(get_col_name)
+            .flat_map(get_field_columns)
+    }
+
+    /// Pair each field with the ordinal of its first column.
+    pub fn field_ordinals(&self) -> impl Iterator<Item = (&'a Field, usize)> + '_ {
+        self
+            .fields
+            .iter()
+            .copied()
+            .scan(0, |ordinal, field| {
+                let start = *ordinal;
+                *ordinal += get_field_columns(field).len();
+
+                Some((field, start))
+            })
+    }
+
+    /// Pair each field populated when reading a row with the ordinal of its first column in the output of `select_all`.
+    /// 
+    /// `#[select_expr]` fields come after every stored column.
+    pub fn read_ordinals(&self) -> impl Iterator<Item = (&'a Field, usize)> + '_ {
+        let stored = self.col_names().count();
+
+        self
+            .field_ordinals()
+            .chain(self.computed.iter().copied().zip(stored..))
     }
 
     pub fn gen_query(&self, clause: Option<&str>) -> Literal {
+        let mut buf = self.insert_sql(clause);
+
+        buf += ";";
+
+        Literal::string(&buf)
+    }
+
+    pub fn gen_select(&self) -> Literal {
+        let cols: Vec<_> = self
+            .col_names()
+            .chain(self.computed.iter().map(|field| select_expr_sql(field)))
+            .collect();
+
+        Literal::string(&format!(
+            "SELECT {} FROM {};",
+            cols.join(", "),
+            self.table_sql()
+        ))
+    }
+
+    /// The table name as it appears in generated SQL - or, if the table is resolved at runtime with `#[resolve_table]`,
+    /// the [`TABLE_PLACEHOLDER`] to be replaced with the resolved name.
+    fn table_sql(&self) -> &str {
+        match self.resolve_table {
+            Some(_) => TABLE_PLACEHOLDER,
+            None => &self.table,
+        }
+    }
+
+    #[cfg(feature = "returning")]
+    pub fn gen_query_if_absent(&self) -> Literal {
+        let mut buf = self.insert_sql(None);
+
+        buf += " ON CONFLICT DO NOTHING RETURNING 1;";
+
+        Literal::string(&buf)
+    }
+
+    fn insert_sql(&self, clause: Option<&str>) -> String {
         let mut buf = String::from("INSERT ");
 
         if let Some(clause) = clause {
             buf += &format!("OR {} ", clause);
         }
 
-        buf += &format!("INTO {} ", self.table);
+        buf += &format!("INTO {} ", self.table_sql());
 
         let mut iter = self.col_names().peekable();
 
@@ -49,14 +250,153 @@ impl<'a> Derivee<'a> {
             }
             else {
                 cols += &format!("{}) ", col);
-                values += &format!(":{});", col);
+                values += &format!(":{})", col);
             }
         }
 
         buf += &cols;
         buf += &values;
 
-        Literal::string(&buf)
+        buf
+    }
+}
+
+pub fn get_resolve_table(ast: &DeriveInput) -> Option<Path> {
+    let resolve_table = ast
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("resolve_table")
+        });
+
+    let resolve_table = resolve_table?;
+
+    let Ok(path) = resolve_table.parse_args::<Path>() else {
+        abort!(
+            resolve_table.span(),
+            "The #[resolve_table] attribute expects a single path for its argument.";
+            hint = "Specify the path to your resolver function like this: #[resolve_table(path::to::fn)].";
+            hint = "Your resolver function should have the signature fn () -> &'static str."
+        )
+    };
+
+    Some(path)
+}
+
+/// Apply any struct-level `#[columns]`, by giving every field without its own `#[column]` one with its positional column name.
+/// 
+/// Skipped fields don't take a position, and `#[bind_multi]` fields aren't supported.
+pub fn apply_columns(ast: &mut DeriveInput) {
+    let columns = ast
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("columns")
+        });
+
+    let Some(columns) = columns else {
+        return
+    };
+
+    let span = columns.span();
+
+    let Ok(names) = columns.parse_args_with(punctuated::Punctuated::<LitStr, Token![,]>::parse_terminated) else {
+        abort!(
+            span,
+            "The #[columns] attribute expects a comma-separated list of string literals.";
+            hint = r#"Specify the columns like this: #[columns("column_a", "column_b")]."#
+        )
+    };
+
+    if ast.attrs.iter().any(|attr| attr.path().is_ident("column_prefix")) {
+        abort!(
+            span,
+            "The #[columns] and #[column_prefix] attributes can't be combined.";
+            hint = "Include the prefix in the listed column names instead."
+        )
+    }
+
+    let Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) = &mut ast.data else {
+        return
+    };
+
+    let fields: Vec<_> = fields
+        .named
+        .iter_mut()
+        .filter(|field| !is_skipped(field))
+        .collect();
+
+    if names.len() != fields.len() {
+        abort!(
+            span,
+            "The #[columns] attribute lists {} column(s), but the struct has {} non-skipped field(s).", names.len(), fields.len();
+            note = "Columns are mapped to fields positionally, so there must be exactly one per field."
+        )
+    }
+
+    for (field, name) in fields.into_iter().zip(names) {
+        if let Some(multi) = field.attrs.iter().find(|attr| attr.path().is_ident("bind_multi")) {
+            abort!(
+                multi.span(),
+                "The #[bind_multi] attribute can't be used with a struct-level #[columns] attribute.";
+                hint = "List the field's columns in #[bind_multi], and use #[column] on the other fields instead."
+            )
+        }
+
+        if field.attrs.iter().any(|attr| attr.path().is_ident("column")) {
+            continue;
+        }
+
+        field.attrs.push(parse_quote! { #[column(#name)] });
+    }
+}
+
+/// Apply any struct-level `#[column_prefix]`, by giving every field without its own `#[column]` (or `#[bind_multi]`) one with the prefixed field name.
+pub fn apply_column_prefix(ast: &mut DeriveInput) {
+    let prefix = ast
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("column_prefix")
+        });
+
+    let Some(prefix) = prefix else {
+        return
+    };
+
+    let Ok(prefix) = prefix.parse_args::<LitStr>() else {
+        abort!(
+            prefix.span(),
+            "The #[column_prefix] attribute expects a string literal as its argument.";
+            hint = r#"Specify the prefix like this: #[column_prefix("user_")]."#
+        )
+    };
+
+    let Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) = &mut ast.data else {
+        return
+    };
+
+    for field in &mut fields.named {
+        let overridden = field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("column") || attr.path().is_ident("bind_multi"));
+
+        if overridden {
+            continue;
+        }
+
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("All fields should have an identifier.");
+
+        let column = LitStr::new(
+            &format!("{}{}", prefix.value(), ident.unraw()),
+            ident.span()
+        );
+
+        field.attrs.push(parse_quote! { #[column(#column)] });
     }
 }
 
@@ -71,113 +411,1746 @@ pub fn get_table_name(ast: &DeriveInput) -> String {
     let Some(table) = table else {
         abort_call_site!(
             "Expected a #[table(...)] attribute.";
-            note = "Exemplar does not infer the name of the SQL table your type maps to.";
-            hint = r#"Specify the table like this: #[table("table_name")]."#
+            note = "Exemplar does not infer the name of the SQL table your type maps to by default.";
+            hint = r#"Specify the table like this: #[table("table_name")]."#;
+            hint = r#"Or opt into inference like this: #[table(infer = "snake_case_plural")]."#
         )
     };
 
-    let Ok(Lit::Str(str)) = table.parse_args::<Lit>() else {
-        abort!(
+    if let Ok(Lit::Str(str)) = table.parse_args::<Lit>() {
+        return str.value()
+    }
+
+    let rule = match table.parse_args::<MetaNameValue>() {
+        Ok(MetaNameValue {
+            path,
+            value: Expr::Lit(ExprLit { lit: Lit::Str(rule), .. }),
+            ..
+        }) if path.is_ident("infer") => rule,
+        _ => abort!(
             table.span(),
-            "The #[table] attribute expects a single string literal as its argument.";
-            hint = r#"Specify the table like this: #[table("table_name")]."#
+            "The #[table] attribute expects a single string literal (or an inference rule) as its argument.";
+            hint = r#"Specify the table like this: #[table("table_name")]."#;
+            hint = r#"Or opt into inference like this: #[table(infer = "snake_case_plural")]."#
         )
     };
 
-    str.value()
+    let name = snake_case(&ast.ident.to_string());
+
+    match rule.value().as_str() {
+        "snake_case" => name,
+        "snake_case_plural" => pluralize(name),
+        _ => abort!(
+            rule.span(),
+            "Unrecognized table name inference rule.";
+            hint = "Valid rules are snake_case and snake_case_plural."
+        )
+    }
 }
 
-pub fn get_check_path(ast: &DeriveInput) -> Option<String> {
-    let table = ast
+/// Convert a `CamelCase` identifier to `snake_case`, keeping acronyms together (`HTTPRequest` becomes `http_request`.)
+fn snake_case(ident: &str) -> String {
+    let chars: Vec<_> = ident.chars().collect();
+    let mut buf = String::with_capacity(ident.len() + 4);
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1);
+
+            let boundary = prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next.is_some_and(|next| next.is_lowercase()));
+
+            if boundary && prev != '_' {
+                buf.push('_');
+            }
+        }
+
+        buf.extend(ch.to_lowercase());
+    }
+
+    buf
+}
+
+/// Naively pluralize an English noun.
+fn pluralize(mut noun: String) -> String {
+    let consonant_y = noun.ends_with('y') && !noun
+        .trim_end_matches('y')
+        .ends_with(['a', 'e', 'i', 'o', 'u']);
+
+    if consonant_y {
+        noun.pop();
+        noun += "ies";
+    }
+    else if ["s", "x", "z", "ch", "sh"].iter().any(|end| noun.ends_with(end)) {
+        noun += "es";
+    }
+    else {
+        noun += "s";
+    }
+
+    noun
+}
+
+pub fn get_on_conflict(ast: &DeriveInput) -> Strategy {
+    let on_conflict = ast
         .attrs
         .iter()
         .find(|attr| {
-            attr.path().is_ident("check")
+            attr.path().is_ident("on_conflict")
         });
 
-    let table = table?;
+    let Some(on_conflict) = on_conflict else {
+        return Strategy::default()
+    };
 
-    let Ok(Lit::Str(str)) = table.parse_args::<Lit>() else {
+    let Ok(Lit::Str(str)) = on_conflict.parse_args::<Lit>() else {
         abort!(
-            table.span(),
-            "The #[check] attribute expects a single string literal as its argument.";
-            hint = r#"Specify the schema path like this: #[check("path/to/schema")]."#;
-            hint = "The path should be specified relative to the current file."
+            on_conflict.span(),
+            "The #[on_conflict] attribute expects a single string literal as its argument.";
+            hint = r#"Specify the strategy like this: #[on_conflict("ignore")]."#
         )
     };
 
-    Some(
-        str.value()
-    )
+    let value = str.value().to_ascii_lowercase();
+
+    let Some((_, variant, clause)) = STRATEGIES.iter().find(|(name, ..)| *name == value) else {
+        abort!(
+            str.span(),
+            "Unrecognized conflict resolution strategy.";
+            hint = "Valid strategies are abort, fail, ignore, replace and rollback."
+        )
+    };
+
+    Strategy {
+        variant,
+        clause: *clause
+    }
 }
 
-pub fn get_col_name(field: &Field) -> String {
-    let column = field
+pub fn get_converts(ast: &DeriveInput) -> Vec<Convert> {
+    let mut converts: Vec<Convert> = vec![];
+
+    let attrs = ast
         .attrs
         .iter()
-        .find(|attr| {
-            attr.path().is_ident("column")
+        .filter(|attr| {
+            attr.path().is_ident("convert")
         });
-    
-    if let Some(column) = column {
-        let Ok(Lit::Str(str)) = column.parse_args::<Lit>() else {
+
+    for attr in attrs {
+        let Ok(rule) = attr.parse_args_with(parse_convert_args) else {
             abort!(
-                column.span(),
-                "The #[column] attribute expects a single string literal as its argument.";
-                hint = r#"Specify the column like this: #[column("column_name")]."#
+                attr.span(),
+                "The #[convert] attribute expects a type, followed by bind and extraction function paths.";
+                hint = "Specify the conversion like this: #[convert(Type => path::to::bind, path::to::extr)]."
             )
         };
 
-        return str.value();
+        let ty = rule.ty.to_token_stream().to_string();
+
+        if converts.iter().any(|other| other.ty.to_token_stream().to_string() == ty) {
+            abort!(
+                rule.ty.span(),
+                "Duplicate #[convert] rule for this type.";
+                hint = "Each type can only have one conversion rule."
+            )
+        }
+
+        converts.push(rule);
     }
 
-    field
-        .ident
-        .as_ref()
-        .expect("All fields should have an identifier.")
-        .to_string()
+    converts
 }
 
-pub fn get_bind_path(field: &Field) -> Option<Path> {
-    let bind = field
+fn parse_convert_args(input: parse::ParseStream) -> Result<Convert> {
+    let ty = input.parse()?;
+    input.parse::<Token![=>]>()?;
+    let bind = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let extr = input.parse()?;
+
+    if input.peek(Token![,]) {
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok(Convert {
+        ty,
+        bind,
+        extr
+    })
+}
+
+pub fn get_check(ast: &DeriveInput) -> Option<Check> {
+    let check = ast
         .attrs
         .iter()
         .find(|attr| {
-            attr.path().is_ident("bind")
+            attr.path().is_ident("check")
         });
 
-    let bind = bind?;
+    let check = check?;
 
-    let Ok(path) = bind.parse_args::<Path>() else {
+    let Ok(args) = check.parse_args_with(parse_check_args) else {
         abort!(
-            bind.span(),
-            "The #[bind] attribute expects a single path for its argument.";
-            hint = r#"Specify the bind function like this: #[bind(path::to::fn)]."#;
-            hint = "Your bind function should have the signature fn (&T) -> Result<ToSqlOutput>, where T is the type of the annotated field."
+            check.span(),
+            "The #[check] attribute expects a comma-separated list of arguments.";
+            hint = r#"Specify the schema path like this: #[check("path/to/schema")]."#;
+            hint = r#"Or specify an inline schema like this: #[check(inline = "CREATE TABLE ...")]."#
         )
     };
-    
-    Some(path)
-}
 
-pub fn get_extr_path(field: &Field) -> Option<ExprPath> {
-    let extr = field
-        .attrs
-        .iter()
-        .find(|attr| {
-            attr.path().is_ident("extr")
-        });
+    let mut schema = None;
+    let mut without_rowid = false;
+    let mut indexes = vec![];
 
-    let extr = extr?;
+    for arg in args {
+        let (span, parsed) = match arg {
+            CheckArg::Lit(str) => (str.span(), Schema::Path(str.value())),
+            CheckArg::Meta(meta) if meta.path().is_ident("inline") => {
+                let Meta::NameValue(meta) = *meta else {
+                    abort!(
+                        meta.span(),
+                        "The inline schema must be specified as a name-value pair.";
+                        hint = r#"Specify an inline schema like this: #[check(inline = "CREATE TABLE ...")]."#
+                    )
+                };
 
-    let Ok(path) = extr.parse_args::<ExprPath>() else {
+                let Expr::Lit(ExprLit { lit: Lit::Str(str), .. }) = &meta.value else {
+                    abort!(
+                        meta.value.span(),
+                        "The inline schema must be a string literal.";
+                        hint = r#"Specify an inline schema like this: #[check(inline = "CREATE TABLE ...")]."#
+                    )
+                };
+
+                (meta.span(), Schema::Inline(str.value()))
+            },
+            CheckArg::Meta(meta) if meta.path().is_ident("migrations") => {
+                let Meta::NameValue(meta) = *meta else {
+                    abort!(
+                        meta.span(),
+                        "The migrations directory must be specified as a name-value pair.";
+                        hint = r#"Specify a migrations directory like this: #[check(migrations = "path/to/migrations")]."#
+                    )
+                };
+
+                let Expr::Lit(ExprLit { lit: Lit::Str(str), .. }) = &meta.value else {
+                    abort!(
+                        meta.value.span(),
+                        "The migrations directory must be a string literal.";
+                        hint = r#"Specify a migrations directory like this: #[check(migrations = "path/to/migrations")]."#
+                    )
+                };
+
+                (meta.span(), Schema::Migrations(str.value()))
+            },
+            CheckArg::Meta(meta) if meta.path().is_ident("without_rowid") => {
+                let Meta::Path(_) = *meta else {
+                    abort!(
+                        meta.span(),
+                        "The without_rowid flag does not take a value.";
+                        hint = r#"Specify the flag like this: #[check("path/to/schema", without_rowid)]."#
+                    )
+                };
+
+                without_rowid = true;
+                continue;
+            },
+            CheckArg::Meta(meta) if meta.path().is_ident("index") => {
+                let Meta::NameValue(meta) = *meta else {
+                    abort!(
+                        meta.span(),
+                        "The index must be specified as a name-value pair.";
+                        hint = r#"Specify an index like this: #[check("path/to/schema", index = "idx_name")]."#
+                    )
+                };
+
+                let Expr::Lit(ExprLit { lit: Lit::Str(str), .. }) = &meta.value else {
+                    abort!(
+                        meta.value.span(),
+                        "The index name must be a string literal.";
+                        hint = r#"Specify an index like this: #[check("path/to/schema", index = "idx_name")]."#
+                    )
+                };
+
+                indexes.push(str.value());
+                continue;
+            },
+            CheckArg::Meta(meta) => {
+                abort!(
+                    meta.span(),
+                    "Unrecognized #[check] argument.";
+                    hint = r#"Specify the schema path like this: #[check("path/to/schema")]."#;
+                    hint = r#"Or specify an inline schema like this: #[check(inline = "CREATE TABLE ...")]."#
+                )
+            }
+        };
+
+        if schema.replace(parsed).is_some() {
+            abort!(
+                span,
+                "The #[check] attribute expects exactly one schema.";
+                note = "Only one of a path, an inline schema or a migrations directory may be specified."
+            )
+        }
+    }
+
+    let Some(schema) = schema else {
         abort!(
-            extr.span(),
-            "The #[extr] attribute expects a single path for its argument.";
-            hint = r#"Specify the extraction function like this: #[extr(path::to::fn)]."#;
-            hint = "Your extraction function should have the signature fn (ValueRef) -> FromSqlResult<T>, where T is the type of the annotated field."
+            check.span(),
+            "The #[check] attribute expects a schema.";
+            hint = r#"Specify the schema path like this: #[check("path/to/schema")]."#;
+            hint = "The path should be specified relative to the current file."
         )
     };
-    
-    Some(path)
-}
\ No newline at end of file
+
+    Some(Check {
+        schema,
+        without_rowid,
+        indexes
+    })
+}
+
+enum CheckArg {
+    Lit(LitStr),
+    Meta(Box<Meta>),
+}
+
+fn parse_check_args(input: parse::ParseStream) -> Result<Vec<CheckArg>> {
+    let mut args = vec![];
+
+    while !input.is_empty() {
+        if input.peek(LitStr) {
+            args.push(CheckArg::Lit(input.parse()?));
+        }
+        else {
+            args.push(CheckArg::Meta(Box::new(input.parse()?)));
+        }
+
+        if input.is_empty() {
+            break;
+        }
+
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok(args)
+}
+
+struct ColumnArgs {
+    name: LitStr,
+    aliases: Vec<LitStr>,
+}
+
+fn parse_column_args(input: parse::ParseStream) -> Result<ColumnArgs> {
+    let name = input.parse()?;
+    let mut aliases = vec![];
+
+    while !input.is_empty() {
+        input.parse::<Token![,]>()?;
+
+        if input.is_empty() {
+            break;
+        }
+
+        let key: Ident = input.parse()?;
+
+        if key != "alias" {
+            return Err(Error::new(key.span(), "unknown argument"))
+        }
+
+        input.parse::<Token![=]>()?;
+        aliases.push(input.parse()?);
+    }
+
+    Ok(ColumnArgs {
+        name,
+        aliases
+    })
+}
+
+fn get_col_args(field: &Field) -> Option<ColumnArgs> {
+    let column = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("column")
+        })?;
+
+    let Ok(args) = column.parse_args_with(parse_column_args) else {
+        abort!(
+            column.span(),
+            "The #[column] attribute expects a string literal as its argument, optionally followed by aliases.";
+            hint = r#"Specify the column like this: #[column("column_name")]."#;
+            hint = r#"Specify alternate names to accept when reading like this: #[column("column_name", alias = "old_name")]."#
+        )
+    };
+
+    Some(args)
+}
+
+pub fn get_col_name(field: &Field) -> String {
+    if let Some(args) = get_col_args(field) {
+        return args.name.value();
+    }
+
+    field
+        .ident
+        .as_ref()
+        .expect("All fields should have an identifier.")
+        .to_string()
+}
+
+/// The names of the columns a field maps to - one, unless it has `#[bind_multi]`.
+pub fn get_field_columns(field: &Field) -> Vec<String> {
+    match get_multi(field) {
+        Some(multi) => multi.columns,
+        None => vec![get_col_name(field)]
+    }
+}
+
+pub fn get_col_aliases(field: &Field) -> Vec<String> {
+    get_col_args(field)
+        .map(|args| args.aliases)
+        .unwrap_or_default()
+        .iter()
+        .map(LitStr::value)
+        .collect()
+}
+
+pub fn is_reserved(column: &str) -> bool {
+    RESERVED.contains(
+        &column.to_ascii_lowercase().as_str()
+    )
+}
+
+pub fn get_col_span(field: &Field) -> Span {
+    match get_col_args(field) {
+        Some(args) => args.name.span(),
+        None => field
+            .ident
+            .as_ref()
+            .expect("All fields should have an identifier.")
+            .span()
+    }
+}
+
+/// Check if a type is (syntactically) a `String`.
+pub fn is_string(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false
+    };
+
+    is_std_path(path, "string", "String") && path
+        .path
+        .segments
+        .last()
+        .is_some_and(|seg| seg.arguments.is_none())
+}
+
+/// Check if a type path names the standard library item `name` from `module` - either unqualified, or as `std::<module>::<name>`
+/// or `alloc::<module>::<name>`. User types that happen to share the name are only matched when written unqualified.
+fn is_std_path(path: &TypePath, module: &str, name: &str) -> bool {
+    if path.qself.is_some() {
+        return false
+    }
+
+    let segments: Vec<_> = path
+        .path
+        .segments
+        .iter()
+        .collect();
+
+    let Some((last, prefix)) = segments.split_last() else {
+        return false
+    };
+
+    if last.ident != name || prefix.iter().any(|seg| !seg.arguments.is_none()) {
+        return false
+    }
+
+    match prefix {
+        [] => path.path.leading_colon.is_none(),
+        [krate, module_seg] => (krate.ident == "std" || krate.ident == "alloc") && module_seg.ident == module,
+        _ => false
+    }
+}
+
+/// Check if a type is (syntactically) an `Option<T>`.
+pub fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false
+    };
+
+    path.qself.is_none() && path
+        .path
+        .segments
+        .last()
+        .is_some_and(|seg| seg.ident == "Option" && matches!(seg.arguments, PathArguments::AngleBracketed(_)))
+}
+
+/// Check if a type is (syntactically) a `PhantomData<T>`.
+pub fn is_phantom_data(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false
+    };
+
+    path.qself.is_none() && path
+        .path
+        .segments
+        .last()
+        .is_some_and(|seg| seg.ident == "PhantomData" && matches!(seg.arguments, PathArguments::AngleBracketed(_)))
+}
+
+/// If a field is (syntactically) typed `Result<T, E>` (or a `Result<T>` alias), return a copy of it typed as `T`.
+/// 
+/// Such fields capture any error encountered while reading them, rather than propagating it.
+pub fn captured_field(field: &Field) -> Option<Field> {
+    let Type::Path(path) = &field.ty else {
+        return None
+    };
+
+    let seg = path.path.segments.last()?;
+
+    let PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None
+    };
+
+    if path.qself.is_some() || seg.ident != "Result" || !(1..=2).contains(&args.args.len()) {
+        return None
+    }
+
+    let Some(GenericArgument::Type(inner)) = args.args.first() else {
+        return None
+    };
+
+    let mut field = field.clone();
+    field.ty = inner.clone();
+
+    Some(field)
+}
+
+/// Check if a type is (syntactically) a boxed `ToSql` trait object, like `Box<dyn ToSql>` or `Box<dyn ToSql + Send>`.
+pub fn is_boxed_to_sql(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false
+    };
+
+    let Some(seg) = path.path.segments.last() else {
+        return false
+    };
+
+    let PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return false
+    };
+
+    if path.qself.is_some() || seg.ident != "Box" || args.args.len() != 1 {
+        return false
+    }
+
+    let Some(GenericArgument::Type(Type::TraitObject(object))) = args.args.first() else {
+        return false
+    };
+
+    object
+        .bounds
+        .iter()
+        .any(|bound| matches!(
+            bound,
+            TypeParamBound::Trait(bound) if bound.path.segments.last().is_some_and(|seg| seg.ident == "ToSql")
+        ))
+}
+
+/// Ensure every `Box<dyn ToSql>` field can be read back, as trait objects don't implement `FromSql`.
+pub fn check_boxed_to_sql(derivee: &Derivee) {
+    let unreadable = derivee
+        .fields
+        .iter()
+        .find(|field| {
+            is_boxed_to_sql(&field.ty) && derivee.extr_path(field).is_none() && get_extr_named_path(field).is_none()
+        });
+
+    if let Some(field) = unreadable {
+        abort!(
+            field.ty.span(),
+            "A Box<dyn ToSql> field cannot be read from a row without an extraction function.";
+            note = "Trait objects can be bound directly, but don't implement FromSql.";
+            hint = "Specify how to read the field with #[extr(path::to::fn)], returning an ExtrResult<Box<dyn ToSql>>."
+        )
+    }
+}
+
+/// Check if a type is (syntactically) a `Vec<u8>`.
+pub fn is_byte_vec(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false
+    };
+
+    let Some(seg) = path.path.segments.last() else {
+        return false
+    };
+
+    if !is_std_path(path, "vec", "Vec") {
+        return false
+    }
+
+    let PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return false
+    };
+
+    matches!(
+        args.args.first(),
+        Some(GenericArgument::Type(Type::Path(inner))) if args.args.len() == 1 && inner.path.is_ident("u8")
+    )
+}
+
+pub fn is_skipped(field: &Field) -> bool {
+    let skip = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("skip")
+        });
+
+    let Some(skip) = skip else {
+        // Marker fields carry no data, so they're skipped implicitly.
+        return is_phantom_data(&field.ty)
+    };
+
+    if !matches!(skip.meta, Meta::Path(_)) {
+        abort!(
+            skip.span(),
+            "The #[skip] attribute does not take any arguments.";
+            hint = "Mark the field to skip like this: #[skip]."
+        )
+    }
+
+    true
+}
+
+pub fn is_primary_key(field: &Field) -> bool {
+    let primary_key = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("primary_key")
+        });
+
+    let Some(primary_key) = primary_key else {
+        return false
+    };
+
+    if !matches!(primary_key.meta, Meta::Path(_)) {
+        abort!(
+            primary_key.span(),
+            "The #[primary_key] attribute does not take any arguments.";
+            hint = "Mark the primary key field like this: #[primary_key]."
+        )
+    }
+
+    true
+}
+
+pub fn is_composite_key(ast: &DeriveInput) -> bool {
+    let composite_key = ast
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("composite_key")
+        });
+
+    let Some(composite_key) = composite_key else {
+        return false
+    };
+
+    if !matches!(composite_key.meta, Meta::Path(_)) {
+        abort!(
+            composite_key.span(),
+            "The #[composite_key] attribute does not take any arguments.";
+            hint = "Opt into composite primary keys like this: #[composite_key]."
+        )
+    }
+
+    true
+}
+
+pub fn is_positional_fallback(ast: &DeriveInput) -> bool {
+    let positional = ast
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("positional_fallback")
+        });
+
+    let Some(positional) = positional else {
+        return false
+    };
+
+    if !matches!(positional.meta, Meta::Path(_)) {
+        abort!(
+            positional.span(),
+            "The #[positional_fallback] attribute does not take any arguments.";
+            hint = "Opt into positional column lookup like this: #[positional_fallback]."
+        )
+    }
+
+    true
+}
+
+pub fn is_filter(ast: &DeriveInput) -> bool {
+    let filter = ast
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("filter")
+        });
+
+    let Some(filter) = filter else {
+        return false
+    };
+
+    if !matches!(filter.meta, Meta::Path(_)) {
+        abort!(
+            filter.span(),
+            "The #[filter] attribute does not take any arguments.";
+            hint = "Opt into the generated filter builder like this: #[filter]."
+        )
+    }
+
+    true
+}
+
+pub fn is_hash_by_pk(ast: &DeriveInput, primary_key: &[&Field]) -> bool {
+    let hash_by_pk = ast
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("hash_by_pk")
+        });
+
+    let Some(hash_by_pk) = hash_by_pk else {
+        return false
+    };
+
+    if !matches!(hash_by_pk.meta, Meta::Path(_)) {
+        abort!(
+            hash_by_pk.span(),
+            "The #[hash_by_pk] attribute does not take any arguments.";
+            hint = "Opt into identity-based equality like this: #[hash_by_pk]."
+        )
+    }
+
+    if primary_key.is_empty() {
+        abort!(
+            hash_by_pk.span(),
+            "The #[hash_by_pk] attribute requires a primary key.";
+            hint = "Mark the primary key field(s) with #[primary_key]."
+        )
+    }
+
+    true
+}
+
+/// Check for `#[order_by_pk]`, which must be paired with `#[hash_by_pk]` so that the ordering agrees with equality.
+pub fn is_order_by_pk(ast: &DeriveInput, hash_by_pk: bool) -> bool {
+    let order_by_pk = ast
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("order_by_pk")
+        });
+
+    let Some(order_by_pk) = order_by_pk else {
+        return false
+    };
+
+    if !matches!(order_by_pk.meta, Meta::Path(_)) {
+        abort!(
+            order_by_pk.span(),
+            "The #[order_by_pk] attribute does not take any arguments.";
+            hint = "Opt into identity-based ordering like this: #[order_by_pk]."
+        )
+    }
+
+    if !hash_by_pk {
+        abort!(
+            order_by_pk.span(),
+            "The #[order_by_pk] attribute requires #[hash_by_pk].";
+            note = "Ord must agree with PartialEq, so equality has to be identity-based too.";
+            hint = "Add #[hash_by_pk] to the struct (and don't derive PartialEq, Eq or Hash.)"
+        )
+    }
+
+    true
+}
+
+/// Collect the fields marked with `#[primary_key]`, in order of definition.
+/// 
+/// More than one is only allowed if the struct opts into composite keys with `#[composite_key]`.
+pub fn get_primary_key<'a>(ast: &DeriveInput, fields: &[&'a Field], skipped: &[&'a Field]) -> Vec<&'a Field> {
+    if let Some(field) = skipped.iter().find(|field| is_primary_key(field)) {
+        abort!(
+            field.span(),
+            "A field marked with #[skip] cannot be a primary key.";
+            note = "Skipped fields have no corresponding column."
+        )
+    }
+
+    let primary_key: Vec<_> = fields
+        .iter()
+        .copied()
+        .filter(|field| is_primary_key(field))
+        .collect();
+
+    if let Some(field) = primary_key.iter().find(|field| get_multi(field).is_some()) {
+        abort!(
+            field.span(),
+            "A field marked with #[bind_multi] cannot be a primary key.";
+            note = "Key columns are matched individually, so each must map to exactly one field.";
+            hint = "Split the key into one field per column, and add #[composite_key] to the struct."
+        )
+    }
+
+    if primary_key.len() > 1 && !is_composite_key(ast) {
+        abort!(
+            primary_key[1].span(),
+            "Only one field can be marked with #[primary_key].";
+            note = "Methods keyed on the primary key would be ambiguous.";
+            hint = "If the table has a composite primary key, add #[composite_key] to the struct."
+        )
+    }
+
+    primary_key
+}
+
+pub fn is_as_text(field: &Field) -> bool {
+    let as_text = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("as_text")
+        });
+
+    let Some(as_text) = as_text else {
+        return false
+    };
+
+    if !matches!(as_text.meta, Meta::Path(_)) {
+        abort!(
+            as_text.span(),
+            "The #[as_text] attribute does not take any arguments.";
+            hint = "Mark the field to store as text like this: #[as_text]."
+        )
+    }
+
+    let conflict = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            ["bind", "extr", "extr_named"]
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        });
+
+    if let Some(conflict) = conflict {
+        abort!(
+            conflict.span(),
+            "The #[as_text] attribute cannot be combined with #[bind], #[extr] or #[extr_named].";
+            note = "#[as_text] is shorthand for using the field type's Display and FromStr implementations as its bind and extraction functions."
+        )
+    }
+
+    true
+}
+
+pub fn is_transparent(field: &Field) -> bool {
+    let transparent = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("transparent")
+        });
+
+    let Some(transparent) = transparent else {
+        return false
+    };
+
+    if !matches!(transparent.meta, Meta::Path(_)) {
+        abort!(
+            transparent.span(),
+            "The #[transparent] attribute does not take any arguments.";
+            hint = "Mark the newtype field like this: #[transparent]."
+        )
+    }
+
+    let conflict = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            ["bind", "extr", "extr_named", "as_text", "bind_multi", "extr_multi"]
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        });
+
+    if let Some(conflict) = conflict {
+        abort!(
+            conflict.span(),
+            "The #[transparent] attribute cannot be combined with #[bind], #[extr], #[extr_named], #[as_text], #[bind_multi] or #[extr_multi].";
+            note = "#[transparent] binds and extracts the field through the ToSql and FromSql implementations of the newtype's inner value."
+        )
+    }
+
+    if is_option(&field.ty) {
+        abort!(
+            field.ty.span(),
+            "The #[transparent] attribute cannot be used on an Option field.";
+            hint = "Use a newtype that implements ToSql and FromSql itself, or #[bind]/#[extr] functions."
+        )
+    }
+
+    true
+}
+
+/// Get the path used to construct a transparent newtype field's type, in expression position (`Id::<User>` rather than `Id<User>`.)
+pub fn get_transparent_ctor(field: &Field) -> Path {
+    let Type::Path(TypePath { qself: None, path }) = &field.ty else {
+        abort!(
+            field.ty.span(),
+            "The #[transparent] attribute can only be used on a field typed as a (single-field) tuple struct.";
+            hint = "Declare the newtype like this: struct UserId(i64);"
+        )
+    };
+
+    let mut path = path.clone();
+
+    for seg in &mut path.segments {
+        if let PathArguments::AngleBracketed(args) = &mut seg.arguments {
+            args.colon2_token = Some(Default::default());
+        }
+    }
+
+    path
+}
+
+pub fn get_bind_path(field: &Field) -> Option<Path> {
+    if is_as_text(field) {
+        return Some(parse_quote! { ::exemplar::convert::bind_display })
+    }
+
+    let bind = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("bind")
+        });
+
+    let (path, _) = parse_bind(bind?);
+    
+    Some(path)
+}
+
+fn parse_bind(bind: &Attribute) -> (Path, bool) {
+    let Ok(args) = bind.parse_args_with(|input: parse::ParseStream| Ok((input.parse()?, parse_lift(input)?))) else {
+        abort!(
+            bind.span(),
+            "The #[bind] attribute expects a single path for its argument, optionally followed by `lift`.";
+            hint = r#"Specify the bind function like this: #[bind(path::to::fn)]."#;
+            hint = "Your bind function should have the signature fn (&T) -> Result<ToSqlOutput>, where T is the type of the annotated field.";
+            hint = r#"For Option<T> fields, apply a function for T with #[bind(path::to::fn, lift)]."#
+        )
+    };
+
+    args
+}
+
+/// Parse the optional `lift` flag trailing the function path of a `#[bind]` or `#[extr_named]` attribute.
+fn parse_lift(input: parse::ParseStream) -> Result<bool> {
+    if input.is_empty() {
+        return Ok(false)
+    }
+
+    input.parse::<Token![,]>()?;
+
+    if input.is_empty() {
+        return Ok(false)
+    }
+
+    let key: Ident = input.parse()?;
+
+    if key != "lift" {
+        return Err(Error::new(key.span(), "unknown argument"))
+    }
+
+    if !input.is_empty() {
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok(true)
+}
+
+/// Check if a field's bind function should be lifted over its `Option` type - that is, if it has `#[bind(..., lift)]` or `#[as_text]`.
+pub fn is_bind_lifted(field: &Field) -> bool {
+    if is_as_text(field) {
+        return is_option(&field.ty)
+    }
+
+    let bind = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("bind")
+        });
+
+    let Some(bind) = bind else {
+        return false
+    };
+
+    let (_, lift) = parse_bind(bind);
+
+    check_lift(field, bind, lift)
+}
+
+/// Check if a field's extraction function should be lifted over its `Option` type - that is, if it has `#[extr(..., lift)]`,
+/// `#[extr_named(..., lift)]` or `#[as_text]`.
+pub fn is_extr_lifted(field: &Field) -> bool {
+    if is_as_text(field) {
+        return is_option(&field.ty)
+    }
+
+    let extr = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("extr") || attr.path().is_ident("extr_named")
+        });
+
+    let Some(extr) = extr else {
+        return false
+    };
+
+    let lift = match extr.path().is_ident("extr") {
+        true => parse_extr(extr).lift,
+        false => parse_extr_named(extr).1,
+    };
+
+    check_lift(field, extr, lift)
+}
+
+fn check_lift(field: &Field, attr: &Attribute, lift: bool) -> bool {
+    if lift && !is_option(&field.ty) {
+        abort!(
+            attr.span(),
+            "The `lift` flag can only be used on Option<T> fields.";
+            hint = "Remove `lift`, or change the field type to an Option."
+        )
+    }
+
+    lift
+}
+
+pub fn get_extr_path(field: &Field) -> Option<ExprPath> {
+    if is_as_text(field) {
+        return Some(parse_quote! { ::exemplar::convert::extr_from_str })
+    }
+
+    let extr = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("extr")
+        });
+
+    let ExtrArgs { path, .. } = parse_extr(extr?);
+    
+    Some(path)
+}
+
+/// Get the fallback function from a field's `#[extr(path, default = fallback)]` attribute, if it has one.
+pub fn get_extr_default(field: &Field) -> Option<ExprPath> {
+    let extr = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("extr")
+        });
+
+    let ExtrArgs { default, .. } = parse_extr(extr?);
+
+    default
+}
+
+/// The arguments of an `#[extr(path, default = fallback, lift)]` attribute.
+struct ExtrArgs {
+    path: ExprPath,
+    default: Option<ExprPath>,
+    lift: bool,
+}
+
+fn parse_extr(extr: &Attribute) -> ExtrArgs {
+    let Ok(args) = extr.parse_args_with(parse_extr_args) else {
+        abort!(
+            extr.span(),
+            "The #[extr] attribute expects a single path for its argument, optionally followed by a default and/or `lift`.";
+            hint = r#"Specify the extraction function like this: #[extr(path::to::fn)]."#;
+            hint = "Your extraction function should have the signature fn (ValueRef) -> FromSqlResult<T>, where T is the type of the annotated field.";
+            hint = r#"Specify a fallback for malformed values like this: #[extr(path::to::fn, default = path::to::default_fn)]."#;
+            hint = r#"For Option<T> fields, apply a function for T with #[extr(path::to::fn, lift)]."#
+        )
+    };
+
+    args
+}
+
+fn parse_extr_args(input: parse::ParseStream) -> Result<ExtrArgs> {
+    let mut args = ExtrArgs {
+        path: input.parse()?,
+        default: None,
+        lift: false,
+    };
+
+    while !input.is_empty() {
+        input.parse::<Token![,]>()?;
+
+        if input.is_empty() {
+            break
+        }
+
+        let key: Ident = input.parse()?;
+
+        if key == "default" && args.default.is_none() {
+            input.parse::<Token![=]>()?;
+            args.default = Some(input.parse()?);
+        }
+        else if key == "lift" && !args.lift {
+            args.lift = true;
+        }
+        else {
+            return Err(Error::new(key.span(), "unknown or repeated argument"))
+        }
+    }
+
+    Ok(args)
+}
+
+pub fn get_extr_named_path(field: &Field) -> Option<ExprPath> {
+    let extr = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("extr_named")
+        });
+
+    let extr = extr?;
+
+    if field.attrs.iter().any(|attr| attr.path().is_ident("extr")) {
+        abort!(
+            extr.span(),
+            "The #[extr_named] attribute cannot be combined with #[extr].";
+            hint = "Use one or the other to specify the extraction function for this field."
+        )
+    }
+
+    let (path, _) = parse_extr_named(extr);
+    
+    Some(path)
+}
+
+fn parse_extr_named(extr: &Attribute) -> (ExprPath, bool) {
+    let Ok(args) = extr.parse_args_with(|input: parse::ParseStream| Ok((input.parse()?, parse_lift(input)?))) else {
+        abort!(
+            extr.span(),
+            "The #[extr_named] attribute expects a single path for its argument, optionally followed by `lift`.";
+            hint = r#"Specify the extraction function like this: #[extr_named(path::to::fn)]."#;
+            hint = "Your extraction function should have the signature fn (&ValueRef, &str) -> FromSqlResult<T>, where T is the type of the annotated field.";
+            hint = r#"For Option<T> fields, apply a function for T with #[extr_named(path::to::fn, lift)]."#
+        )
+    };
+
+    args
+}
+
+pub fn get_multi(field: &Field) -> Option<Multi> {
+    let find = |name: &str| field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(name));
+
+    let (bind, extr) = match (find("bind_multi"), find("extr_multi")) {
+        (None, None) => return None,
+        (Some(bind), Some(extr)) => (bind, extr),
+        (Some(attr), None) | (None, Some(attr)) => abort!(
+            attr.span(),
+            "The #[bind_multi] and #[extr_multi] attributes must be used together.";
+            note = "A field split across several columns needs both a function to split it, and one to reassemble it."
+        )
+    };
+
+    let conflict = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            ["bind", "extr", "extr_named", "as_text", "column", "primary_key"]
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        });
+
+    if let Some(conflict) = conflict {
+        abort!(
+            conflict.span(),
+            "The #[bind_multi] and #[extr_multi] attributes cannot be combined with #[bind], #[extr], #[extr_named], #[as_text], #[column] or #[primary_key].";
+            note = "#[bind_multi] names the field's columns and specifies how to bind them, and #[extr_multi] how to extract them."
+        )
+    }
+
+    let Ok((columns, bind)) = bind.parse_args_with(parse_bind_multi_args) else {
+        abort!(
+            bind.span(),
+            "The #[bind_multi] attribute expects a non-empty array of column names, followed by a path.";
+            hint = r#"Specify the columns and bind function like this: #[bind_multi(["date", "time"], path::to::fn)]."#;
+            hint = "Your bind function should have the signature fn (&T) -> BindMultiResult<N>, where T is the type of the annotated field and N is the number of columns."
+        )
+    };
+
+    let Ok(extr) = extr.parse_args::<ExprPath>() else {
+        abort!(
+            extr.span(),
+            "The #[extr_multi] attribute expects a single path for its argument.";
+            hint = "Specify the extraction function like this: #[extr_multi(path::to::fn)].";
+            hint = "Your extraction function should have the signature fn (&[ValueRef; N]) -> ExtrResult<T>, where T is the type of the annotated field and N is the number of columns."
+        )
+    };
+
+    Some(Multi {
+        columns,
+        bind,
+        extr
+    })
+}
+
+/// Get the expression given by a field's `#[select_expr]` attribute, if it has one.
+pub fn get_select_expr(field: &Field) -> Option<String> {
+    let select_expr = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            attr.path().is_ident("select_expr")
+        })?;
+
+    let Ok(expr) = select_expr.parse_args::<LitStr>() else {
+        abort!(
+            select_expr.span(),
+            "The #[select_expr] attribute expects a string literal as its argument.";
+            hint = r#"Specify the expression like this: #[select_expr("LENGTH(pwd)")]."#
+        )
+    };
+
+    let conflict = field
+        .attrs
+        .iter()
+        .find(|attr| {
+            ["bind", "bind_multi", "extr_multi", "primary_key", "skip", "sql_check"]
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        });
+
+    if let Some(conflict) = conflict {
+        abort!(
+            conflict.span(),
+            "The #[select_expr] attribute cannot be combined with #[bind], #[bind_multi], #[extr_multi], #[primary_key], #[skip] or #[sql_check].";
+            note = "Fields with #[select_expr] are computed when reading, and are never written."
+        )
+    }
+
+    Some(expr.value())
+}
+
+/// Render a `#[select_expr]` field as it appears in a `SELECT` list - the expression, aliased to the field's column name.
+pub fn select_expr_sql(field: &Field) -> String {
+    let expr = get_select_expr(field).expect("Field should have a #[select_expr] attribute.");
+
+    format!("{} AS {}", expr, get_col_name(field))
+}
+
+/// Collect the `CHECK` expressions specified by a field's `#[sql_check]` attributes, in order of definition.
+pub fn get_sql_checks(field: &Field) -> Vec<String> {
+    let checks: Vec<_> = field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("sql_check"))
+        .collect();
+
+    if let Some(check) = checks.first() {
+        if field.attrs.iter().any(|attr| attr.path().is_ident("bind_multi")) {
+            abort!(
+                check.span(),
+                "The #[sql_check] attribute cannot be used on fields with #[bind_multi].";
+                note = "#[sql_check] constrains a single column, but #[bind_multi] fields span several."
+            )
+        }
+    }
+
+    checks
+        .into_iter()
+        .map(|check| {
+            let Ok(expr) = check.parse_args::<LitStr>() else {
+                abort!(
+                    check.span(),
+                    "The #[sql_check] attribute expects a string literal as its argument.";
+                    hint = r#"Specify the constraint like this: #[sql_check("age >= 0")]."#
+                )
+            };
+
+            expr.value()
+        })
+        .collect()
+}
+
+fn parse_bind_multi_args(input: parse::ParseStream) -> Result<(Vec<String>, Path)> {
+    let content;
+    bracketed!(content in input);
+
+    let columns: Vec<_> = punctuated::Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+        .iter()
+        .map(LitStr::value)
+        .collect();
+
+    if columns.is_empty() {
+        return Err(input.error("expected at least one column"))
+    }
+
+    input.parse::<Token![,]>()?;
+
+    let bind = input.parse()?;
+
+    if input.peek(Token![,]) {
+        input.parse::<Token![,]>()?;
+    }
+
+    Ok((columns, bind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(input: &DeriveInput) -> Vec<&Field> {
+        let Data::Struct(data) = &input.data else {
+            unreachable!()
+        };
+
+        data.fields.iter().collect()
+    }
+
+    /// Build a [`Derivee`] for `input` like the derive does, minus the struct-level attributes that tests don't exercise.
+    fn derivee<'a>(input: &'a DeriveInput, table: &str) -> Derivee<'a> {
+        let (skipped, fields): (Vec<_>, Vec<_>) = fields(input)
+            .into_iter()
+            .partition(|field| is_skipped(field));
+
+        let (computed, fields) = fields
+            .into_iter()
+            .partition(|field| get_select_expr(field).is_some());
+
+        Derivee {
+            name: input.ident.clone(),
+            vis: input.vis.clone(),
+            table: table.to_owned(),
+            fields,
+            skipped,
+            computed,
+            check: None,
+            on_conflict: Strategy::default(),
+            converts: get_converts(input),
+            primary_key: vec![],
+            resolve_table: None,
+            positional: false,
+        }
+    }
+
+    #[test]
+    fn gen_query_single() {
+        let input: DeriveInput = parse_quote! {
+            struct Tag {
+                #[column("label")]
+                name: String,
+            }
+        };
+
+        let derivee = derivee(&input, "tags");
+
+        assert_eq!(
+            derivee.gen_query(None).to_string(),
+            r#""INSERT INTO tags (label) VALUES(:label);""#
+        );
+
+        assert_eq!(
+            derivee.gen_query(Some("IGNORE")).to_string(),
+            r#""INSERT OR IGNORE INTO tags (label) VALUES(:label);""#
+        );
+    }
+
+    #[test]
+    fn gen_select() {
+        let input: DeriveInput = parse_quote! {
+            struct User {
+                username: String,
+                #[column("pwd")]
+                password: Vec<u8>,
+                #[skip]
+                cache: Option<String>,
+            }
+        };
+
+        let derivee = derivee(&input, "users");
+
+        assert_eq!(
+            derivee.gen_select().to_string(),
+            r#""SELECT username, pwd FROM users;""#
+        );
+    }
+
+    #[test]
+    fn gen_select_computed() {
+        let input: DeriveInput = parse_quote! {
+            struct User {
+                username: String,
+                #[select_expr("LENGTH(pwd)")]
+                #[column("pwd_len")]
+                password_len: i64,
+                #[column("pwd")]
+                password: Vec<u8>,
+            }
+        };
+
+        let derivee = derivee(&input, "users");
+
+        assert_eq!(
+            derivee.gen_select().to_string(),
+            r#""SELECT username, pwd, LENGTH(pwd) AS pwd_len FROM users;""#
+        );
+
+        assert_eq!(
+            derivee.gen_query(None).to_string(),
+            r#""INSERT INTO users (username, pwd) VALUES(:username, :pwd);""#
+        );
+
+        let ordinals: Vec<_> = derivee
+            .read_ordinals()
+            .map(|(field, ordinal)| (get_col_name(field), ordinal))
+            .collect();
+
+        assert_eq!(ordinals, [("username".to_owned(), 0), ("pwd".to_owned(), 1), ("pwd_len".to_owned(), 2)]);
+    }
+
+    #[test]
+    fn gen_query_multiple() {
+        let input: DeriveInput = parse_quote! {
+            struct Person {
+                name: String,
+                age: u16,
+                alive: bool,
+            }
+        };
+
+        let derivee = derivee(&input, "people");
+
+        assert_eq!(
+            derivee.gen_query(None).to_string(),
+            r#""INSERT INTO people (name, age, alive) VALUES(:name, :age, :alive);""#
+        );
+    }
+
+    #[test]
+    fn col_aliases() {
+        let input: DeriveInput = parse_quote! {
+            struct Account {
+                username: String,
+                #[column("password", alias = "pwd", alias = "passwd",)]
+                password: Vec<u8>,
+            }
+        };
+
+        let fields = fields(&input);
+
+        assert_eq!(get_col_name(fields[0]), "username");
+        assert!(get_col_aliases(fields[0]).is_empty());
+
+        assert_eq!(get_col_name(fields[1]), "password");
+        assert_eq!(get_col_aliases(fields[1]), ["pwd", "passwd"]);
+    }
+
+    #[test]
+    fn convert_rules() {
+        let input: DeriveInput = parse_quote! {
+            #[convert(PathBuf => bind_path, extr_path)]
+            struct Project {
+                root: PathBuf,
+                #[bind(bind_other)]
+                config: PathBuf,
+                output: std::path::PathBuf,
+            }
+        };
+
+        let derivee = derivee(&input, "projects");
+
+        let bind = |idx: usize| derivee.bind_path(derivee.fields[idx]).map(|path| path.to_token_stream().to_string());
+        let extr = |idx: usize| derivee.extr_path(derivee.fields[idx]).map(|path| path.to_token_stream().to_string());
+
+        assert_eq!(bind(0).as_deref(), Some("bind_path"));
+        assert_eq!(extr(0).as_deref(), Some("extr_path"));
+
+        assert_eq!(bind(1).as_deref(), Some("bind_other"));
+        assert_eq!(extr(1).as_deref(), Some("extr_path"));
+
+        assert_eq!(bind(2), None);
+        assert_eq!(extr(2), None);
+    }
+
+    #[test]
+    fn table_inference() {
+        let cases = [
+            ("User", "users"),
+            ("Category", "categories"),
+            ("Key", "keys"),
+            ("Box", "boxes"),
+            ("Address", "addresses"),
+            ("Match", "matches"),
+            ("LogEntry", "log_entries"),
+            ("HTTPRequest", "http_requests"),
+            ("Sha256Hash", "sha256_hashes"),
+        ];
+
+        for (ident, table) in cases {
+            assert_eq!(pluralize(snake_case(ident)), table);
+        }
+
+        let input: DeriveInput = parse_quote! {
+            #[table(infer = "snake_case")]
+            struct UserAccount {
+                name: String,
+            }
+        };
+
+        assert_eq!(get_table_name(&input), "user_account");
+    }
+
+    #[test]
+    fn primary_key() {
+        let input: DeriveInput = parse_quote! {
+            #[table("memberships")]
+            #[composite_key]
+            struct Membership {
+                #[primary_key]
+                user: i64,
+                role: String,
+                #[primary_key]
+                #[column("team_id")]
+                team: i64,
+            }
+        };
+
+        let fields = fields(&input);
+        let primary_key = get_primary_key(&input, &fields, &[]);
+
+        assert!(is_composite_key(&input));
+        assert_eq!(primary_key.len(), 2);
+        assert_eq!(get_col_name(primary_key[0]), "user");
+        assert_eq!(get_col_name(primary_key[1]), "team_id");
+    }
+
+    #[test]
+    fn std_types() {
+        let strings: Vec<Type> = vec![
+            parse_quote! { String },
+            parse_quote! { std::string::String },
+            parse_quote! { ::std::string::String },
+            parse_quote! { alloc::string::String },
+        ];
+
+        let other: Vec<Type> = vec![
+            parse_quote! { my::String },
+            parse_quote! { std::String },
+            parse_quote! { ::String },
+            parse_quote! { String<u8> },
+        ];
+
+        assert!(strings.iter().all(is_string));
+        assert!(!other.iter().any(is_string));
+
+        assert!(is_byte_vec(&parse_quote! { Vec<u8> }));
+        assert!(is_byte_vec(&parse_quote! { std::vec::Vec<u8> }));
+        assert!(!is_byte_vec(&parse_quote! { smallvec::Vec<u8> }));
+        assert!(!is_byte_vec(&parse_quote! { Vec<u16> }));
+    }
+
+    #[test]
+    fn boxed_to_sql() {
+        let boxed: Vec<Type> = vec![
+            parse_quote! { Box<dyn ToSql> },
+            parse_quote! { Box<dyn rusqlite::ToSql + Send> },
+            parse_quote! { std::boxed::Box<dyn ::rusqlite::types::ToSql + Send + Sync> },
+        ];
+
+        let other: Vec<Type> = vec![
+            parse_quote! { Box<str> },
+            parse_quote! { Box<dyn Display> },
+            parse_quote! { Vec<Box<dyn ToSql>> },
+            parse_quote! { Option<Box<dyn ToSql>> },
+        ];
+
+        assert!(boxed.iter().all(is_boxed_to_sql));
+        assert!(!other.iter().any(is_boxed_to_sql));
+    }
+
+    #[test]
+    fn extr_default() {
+        let input: DeriveInput = parse_quote! {
+            struct Service {
+                #[extr(parse_port, default = defaults::port)]
+                port: u16,
+                #[extr(parse_port)]
+                admin_port: u16,
+            }
+        };
+
+        let fields = fields(&input);
+
+        assert_eq!(get_extr_path(fields[0]).to_token_stream().to_string(), "parse_port");
+        assert_eq!(get_extr_default(fields[0]).to_token_stream().to_string(), "defaults :: port");
+        assert!(get_extr_default(fields[1]).is_none());
+    }
+
+    #[test]
+    fn lift() {
+        let input: DeriveInput = parse_quote! {
+            struct Mount {
+                #[bind(bind_path, lift)]
+                #[extr(extr_path, default = defaults::path, lift)]
+                target: Option<PathBuf>,
+                #[bind(bind_optional_path)]
+                #[extr_named(extr_optional_path)]
+                options: Option<PathBuf>,
+                #[as_text]
+                port: Option<u16>,
+                #[extr_named(extr_path, lift,)]
+                backup: Option<PathBuf>,
+            }
+        };
+
+        let fields = fields(&input);
+
+        assert!(is_bind_lifted(fields[0]));
+        assert!(is_extr_lifted(fields[0]));
+        assert_eq!(get_extr_path(fields[0]).to_token_stream().to_string(), "extr_path");
+        assert_eq!(get_extr_default(fields[0]).to_token_stream().to_string(), "defaults :: path");
+
+        assert!(!is_bind_lifted(fields[1]));
+        assert!(!is_extr_lifted(fields[1]));
+
+        assert!(is_bind_lifted(fields[2]));
+        assert!(is_extr_lifted(fields[2]));
+
+        assert!(is_extr_lifted(fields[3]));
+        assert_eq!(get_extr_named_path(fields[3]).to_token_stream().to_string(), "extr_path");
+    }
+
+    #[test]
+    fn phantom_data() {
+        let input: DeriveInput = parse_quote! {
+            struct Id {
+                value: i64,
+                marker: PhantomData<User>,
+                #[skip]
+                cached: Option<String>,
+                other: std::marker::PhantomData<fn() -> User>,
+            }
+        };
+
+        let fields = fields(&input);
+
+        assert!(!is_skipped(fields[0]));
+        assert!(is_skipped(fields[1]));
+        assert!(is_skipped(fields[2]));
+        assert!(is_skipped(fields[3]));
+    }
+
+    #[test]
+    fn transparent_ctor() {
+        let input: DeriveInput = parse_quote! {
+            struct Post {
+                #[transparent]
+                id: PostId,
+                #[transparent]
+                author: ids::Id<User>,
+                title: String,
+            }
+        };
+
+        let fields = fields(&input);
+
+        assert!(is_transparent(fields[0]));
+        assert!(is_transparent(fields[1]));
+        assert!(!is_transparent(fields[2]));
+
+        assert_eq!(
+            get_transparent_ctor(fields[1]).to_token_stream().to_string(),
+            "ids :: Id :: < User >"
+        );
+    }
+
+    #[test]
+    fn multi_columns() {
+        let input: DeriveInput = parse_quote! {
+            struct Event {
+                name: String,
+                #[bind_multi(["date", "time"], split_datetime)]
+                #[extr_multi(join_datetime)]
+                at: DateTime,
+                #[column("loc")]
+                location: String,
+            }
+        };
+
+        let derivee = derivee(&input, "events");
+
+        let multi = get_multi(derivee.fields[1]).unwrap();
+
+        assert_eq!(multi.columns, ["date", "time"]);
+        assert_eq!(multi.bind.to_token_stream().to_string(), "split_datetime");
+        assert_eq!(multi.extr.to_token_stream().to_string(), "join_datetime");
+        assert!(get_multi(derivee.fields[0]).is_none());
+
+        assert_eq!(derivee.col_names().collect::<Vec<_>>(), ["name", "date", "time", "loc"]);
+        
+        let ordinals: Vec<_> = derivee
+            .field_ordinals()
+            .map(|(_, ordinal)| ordinal)
+            .collect();
+
+        assert_eq!(ordinals, [0, 1, 3]);
+
+        assert_eq!(
+            derivee.gen_select().to_string(),
+            r#""SELECT name, date, time, loc FROM events;""#
+        );
+    }
+
+    #[test]
+    fn sql_transparent_inline_hint() {
+        let input: DeriveInput = parse_quote! {
+            struct UserId(i64);
+        };
+
+        let output = crate::sql_transparent(&input).to_string();
+        let hints = output.matches("# [inline]").count();
+
+        if cfg!(feature = "no-inline") {
+            assert_eq!(hints, 0);
+        }
+        else {
+            assert_eq!(hints, 2);
+        }
+    }
+}